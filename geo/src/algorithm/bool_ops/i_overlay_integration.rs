@@ -1,7 +1,14 @@
-use crate::geometry::Coord;
-use crate::GeoNum;
+use super::OpType;
+use crate::geometry::{Coord, Line, LineString, MultiPolygon};
+use crate::sweep::Intersections;
+use crate::{GeoNum, LineIntersection};
+use convert::multi_polygon_from_shapes;
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::core::overlay::{Overlay as IntOverlay, ShapeType};
 use i_overlay::i_float::float::compatible::FloatPointCompatible;
 use i_overlay::i_float::float::number::FloatNumber;
+use i_overlay::i_float::int::point::IntPoint;
+use rstar::{RTree, AABB};
 
 /// A geometry coordinate scalar suitable for performing geometric boolean operations.
 pub trait BoolOpsNum: GeoNum + FloatNumber {}
@@ -26,6 +33,208 @@ impl<T: BoolOpsNum> FloatPointCompatible<T> for BoolOpsCoord<T> {
     }
 }
 
+/// A grid of fixed precision, which maps coordinates to the integer coordinates used by the
+/// `i_overlay` core.
+///
+/// As with a fixed `PrecisionModel` in JTS, a coordinate is rounded to the nearest multiple of
+/// `1 / scale`. The integer coordinates are taken relative to a grid point near the center of
+/// the input, so that their range is only limited by its extent.
+pub(super) struct PrecisionGrid<T: BoolOpsNum> {
+    scale: T,
+    origin: (i64, i64),
+}
+
+impl<T: BoolOpsNum> PrecisionGrid<T> {
+    pub fn new<'a>(scale: T, coords: impl Iterator<Item = &'a Coord<T>>) -> Self
+    where
+        T: 'a,
+    {
+        assert!(scale > T::zero(), "the scale of the grid must be positive");
+        let mut grid = Self {
+            scale,
+            origin: (0, 0),
+        };
+        let mut min = (i64::MAX, i64::MAX);
+        let mut max = (i64::MIN, i64::MIN);
+        for coord in coords {
+            let Coord { x, y } = grid.to_grid(coord);
+            let (x, y) = (round(x) as i64, round(y) as i64);
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        if min.0 <= max.0 {
+            grid.origin = (min.0 + (max.0 - min.0) / 2, min.1 + (max.1 - min.1) / 2);
+        }
+        grid
+    }
+
+    /// The coordinate in units of the grid, relative to its origin.
+    fn to_grid(&self, coord: &Coord<T>) -> Coord<f64> {
+        let scale = self.scale.to_f64();
+        Coord {
+            x: coord.x.to_f64() * scale - self.origin.0 as f64,
+            y: coord.y.to_f64() * scale - self.origin.1 as f64,
+        }
+    }
+
+    pub fn coord(&self, point: IntPoint) -> BoolOpsCoord<T> {
+        BoolOpsCoord(Coord {
+            x: T::from_i64(point.x as i64 + self.origin.0) / self.scale,
+            y: T::from_i64(point.y as i64 + self.origin.1) / self.scale,
+        })
+    }
+
+    /// The point of the grid nearest to `coord`.
+    pub fn snap(&self, coord: &Coord<T>) -> Coord<T> {
+        let Coord { x, y } = self.to_grid(coord);
+        self.coord(IntPoint::new(to_int(round(x)), to_int(round(y))))
+            .0
+    }
+
+    /// Snap-round the lines to the grid.
+    ///
+    /// Every vertex of the lines, and every point at which their segments intersect, is rounded
+    /// to the grid; the pixels of the grid around these points are the "hot pixels". Each segment
+    /// is then replaced by the path through the centers of the hot pixels it passes through,
+    /// which yields linework without any crossings away from the grid points.
+    pub fn snap_round(&self, lines: &[&LineString<T>]) -> Vec<Vec<IntPoint>> {
+        let rings: Vec<Vec<Coord<f64>>> = lines
+            .iter()
+            .map(|ring| ring.coords().map(|coord| self.to_grid(coord)).collect())
+            .collect();
+        let segments = rings
+            .iter()
+            .flat_map(|ring| ring.windows(2).map(|w| Line::new(w[0], w[1])))
+            .filter(|line| line.start != line.end);
+
+        let mut hot_pixels: Vec<[f64; 2]> = rings
+            .iter()
+            .flatten()
+            .map(|coord| [round(coord.x), round(coord.y)])
+            .collect();
+        for (_, _, intersection) in Intersections::from_iter(segments) {
+            if let LineIntersection::SinglePoint { intersection, .. } = intersection {
+                hot_pixels.push([round(intersection.x), round(intersection.y)]);
+            }
+        }
+        hot_pixels.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+        hot_pixels.dedup();
+        let hot_pixels = RTree::bulk_load(hot_pixels);
+
+        rings
+            .iter()
+            .map(|ring| {
+                let mut path: Vec<IntPoint> = vec![];
+                for w in ring.windows(2) {
+                    let (start, end) = (w[0], w[1]);
+                    let envelope = AABB::from_corners(
+                        [start.x.min(end.x) - 0.5, start.y.min(end.y) - 0.5],
+                        [start.x.max(end.x) + 0.5, start.y.max(end.y) + 0.5],
+                    );
+                    let delta = end - start;
+                    let length_squared = delta.x * delta.x + delta.y * delta.y;
+                    let mut pixels: Vec<(f64, [f64; 2])> = hot_pixels
+                        .locate_in_envelope(&envelope)
+                        .filter(|pixel| passes_through_pixel(start, end, **pixel))
+                        .map(|pixel| {
+                            let param = if length_squared > 0. {
+                                ((pixel[0] - start.x) * delta.x + (pixel[1] - start.y) * delta.y)
+                                    / length_squared
+                            } else {
+                                0.
+                            };
+                            (param, *pixel)
+                        })
+                        .collect();
+                    pixels.sort_by(|a, b| a.0.total_cmp(&b.0));
+                    for (_, pixel) in pixels {
+                        let point = IntPoint::new(to_int(pixel[0]), to_int(pixel[1]));
+                        if path.last() != Some(&point) {
+                            path.push(point);
+                        }
+                    }
+                }
+                path
+            })
+            .collect()
+    }
+
+    /// The result of `op` on the areas enclosed by the snap-rounded rings of the subject and the
+    /// clip.
+    pub fn boolean_op(
+        &self,
+        subject: Vec<Vec<IntPoint>>,
+        clip: Vec<Vec<IntPoint>>,
+        op: OpType,
+    ) -> MultiPolygon<T> {
+        let mut overlay = IntOverlay::new(0);
+        let paths = subject
+            .into_iter()
+            .map(|path| (path, ShapeType::Subject))
+            .chain(clip.into_iter().map(|path| (path, ShapeType::Clip)));
+        for (mut path, shape_type) in paths {
+            // the path is implicitly closed
+            if path.len() > 1 && path.first() == path.last() {
+                path.pop();
+            }
+            overlay.add_path_iter(path.into_iter(), shape_type);
+        }
+        let shapes = overlay.overlay(op.into(), FillRule::EvenOdd);
+        let shapes = shapes.into_iter().map(|shape| {
+            let paths = shape.into_iter();
+            paths
+                .map(|path| path.into_iter().map(|p| self.coord(p)).collect())
+                .collect()
+        });
+        multi_polygon_from_shapes(shapes.collect())
+    }
+}
+
+/// Convert a value in units of the grid, which is a whole number, to an integer coordinate.
+fn to_int(value: f64) -> i32 {
+    i32::try_from(value as i64)
+        .expect("the extent of the input is too large for the precision grid")
+}
+
+/// Round a value in units of the grid to the grid, rounding halves up like JTS.
+fn round(value: f64) -> f64 {
+    (value + 0.5).floor()
+}
+
+/// Whether the segment from `start` to `end` passes through the pixel centered at `center`.
+///
+/// Like in JTS, the pixel is a half-open square, which doesn't contain its top and right edges,
+/// so that each point is in exactly one pixel.
+fn passes_through_pixel(start: Coord<f64>, end: Coord<f64>, center: [f64; 2]) -> bool {
+    let delta = end - start;
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    for (p, q) in [
+        (-delta.x, start.x - (center[0] - 0.5)),
+        (delta.x, (center[0] + 0.5) - start.x),
+        (-delta.y, start.y - (center[1] - 0.5)),
+        (delta.y, (center[1] + 0.5) - start.y),
+    ] {
+        if p == 0. {
+            if q < 0. {
+                return false;
+            }
+        } else {
+            let r = q / p;
+            if p < 0. {
+                t0 = t0.max(r);
+            } else {
+                t1 = t1.min(r);
+            }
+        }
+    }
+    if t0 > t1 {
+        return false;
+    }
+    // the part of the segment inside the closed square must not only be on its top or right edge
+    let mid = start + delta * ((t0 + t1) / 2.);
+    mid.x < center[0] + 0.5 && mid.y < center[1] + 0.5
+}
+
 pub(super) mod convert {
     use super::super::OpType;
     use super::BoolOpsNum;
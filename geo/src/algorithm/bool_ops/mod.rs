@@ -1,10 +1,12 @@
 mod i_overlay_integration;
+mod overlay;
 #[cfg(test)]
 mod tests;
 
 use i_overlay_integration::convert::{multi_polygon_from_shapes, ring_to_shape_path};
-use i_overlay_integration::BoolOpsCoord;
 pub use i_overlay_integration::BoolOpsNum;
+use i_overlay_integration::{BoolOpsCoord, PrecisionGrid};
+pub use overlay::Overlay;
 
 use crate::geometry::{LineString, MultiLineString, MultiPolygon, Polygon};
 use crate::winding_order::{Winding, WindingOrder};
@@ -26,6 +28,8 @@ use i_overlay::string::clip::ClipRule;
 /// These operations are implemented on [`Polygon`] and the [`MultiPolygon`]
 /// geometries.
 ///
+/// To combine geometries which may contain points or lines, see [`Overlay`].
+///
 /// # Validity
 ///
 /// Note that the operations are strictly well-defined only on *valid*
@@ -64,6 +68,46 @@ pub trait BooleanOps {
         multi_polygon_from_shapes(shapes)
     }
 
+    /// Perform a boolean operation on a grid of fixed precision, like JTS does with a fixed
+    /// `PrecisionModel`.
+    ///
+    /// The input coordinates are rounded to the nearest multiple of `1 / scale`, and the
+    /// linework is noded by snap-rounding, so that every vertex of the output lies on the grid
+    /// and the output is still valid. Parts of the output which collapse to lines or points on
+    /// the grid are removed; [`Overlay::overlay_with_precision`] keeps them as lines and points.
+    /// Since the output only depends on the rounded input, it is the same wherever the
+    /// computation runs.
+    ///
+    /// # Panics
+    ///
+    /// If `scale` isn't positive, or if the extent of the input, in units of the grid, doesn't
+    /// fit into an `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::{wkt, BooleanOps, OpType};
+    ///
+    /// let a = wkt!(POLYGON((0. 0.,2.4 0.,2.4 2.4,0. 2.4,0. 0.)));
+    /// let b = wkt!(POLYGON((1.3 1.3,3. 1.3,3. 3.,1.3 3.,1.3 1.3)));
+    ///
+    /// let intersection = a.boolean_op_with_precision(&b, OpType::Intersection, 1.0);
+    /// assert_eq!(intersection, wkt!(MULTIPOLYGON(((1. 1.,2. 1.,2. 2.,1. 2.,1. 1.)))));
+    /// ```
+    fn boolean_op_with_precision(
+        &self,
+        other: &impl BooleanOps<Scalar = Self::Scalar>,
+        op: OpType,
+        scale: Self::Scalar,
+    ) -> MultiPolygon<Self::Scalar> {
+        let coords = self.rings().chain(other.rings()).flat_map(|ring| &ring.0);
+        let grid = PrecisionGrid::new(scale, coords);
+        let rings: Vec<_> = self.rings().chain(other.rings()).collect();
+        let mut subject = grid.snap_round(&rings);
+        let clip = subject.split_off(self.rings().count());
+        grid.boolean_op(subject, clip, op)
+    }
+
     /// Returns the overlapping regions shared by both `self` and `other`.
     fn intersection(
         &self,
@@ -193,6 +237,65 @@ pub fn unary_union<'a, B: BooleanOps + 'a>(
     multi_polygon_from_shapes(shapes)
 }
 
+/// The union of `subjects` minus the union of `clips`, computed in a single pass.
+///
+/// This avoids the sliver artifacts that can appear when first taking the union of `clips` and
+/// then subtracting it. The same winding requirements as for [`unary_union`] apply to both
+/// collections, which must be wound consistently with each other.
+pub(crate) fn unary_difference<'a, B: BooleanOps + 'a>(
+    subjects: impl IntoIterator<Item = &'a B>,
+    clips: impl IntoIterator<Item = &'a B>,
+) -> MultiPolygon<B::Scalar> {
+    let mut winding_order: Option<WindingOrder> = None;
+    let mut to_shape_paths = |boppables: &mut dyn Iterator<Item = &'a B>| {
+        boppables
+            .flat_map(|boppable| {
+                boppable
+                    .rings()
+                    .map(|ring| {
+                        if winding_order.is_none() {
+                            winding_order = ring.winding_order();
+                        }
+                        ring_to_shape_path(ring)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    let subject = to_shape_paths(&mut subjects.into_iter());
+    let clip = to_shape_paths(&mut clips.into_iter());
+
+    let fill_rule = if winding_order == Some(WindingOrder::Clockwise) {
+        FillRule::Positive
+    } else {
+        FillRule::Negative
+    };
+
+    let shapes = FloatOverlay::with_subj_and_clip(&subject, &clip)
+        .overlay(OverlayRule::Difference, fill_rule);
+    multi_polygon_from_shapes(shapes)
+}
+
+/// The area enclosed by an odd number of the `rings`.
+///
+/// The rings may be unclosed, self-intersecting or overlap each other.
+pub(crate) fn even_odd_area<'a, T: BoolOpsNum + 'a>(
+    rings: impl IntoIterator<Item = &'a LineString<T>>,
+) -> MultiPolygon<T> {
+    let subject = rings
+        .into_iter()
+        .map(|ring| {
+            if ring.is_closed() {
+                ring_to_shape_path(ring)
+            } else {
+                ring.coords().copied().map(BoolOpsCoord).collect()
+            }
+        })
+        .collect::<Vec<_>>();
+    let shapes = FloatOverlay::with_subj(&subject).overlay(OverlayRule::Subject, FillRule::EvenOdd);
+    multi_polygon_from_shapes(shapes)
+}
+
 impl<T: BoolOpsNum> BooleanOps for Polygon<T> {
     type Scalar = T;
 
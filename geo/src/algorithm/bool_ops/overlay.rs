@@ -0,0 +1,1121 @@
+use super::{BoolOpsNum, BooleanOps, OpType, PrecisionGrid};
+use crate::coordinate_position::{CoordPos, CoordinatePosition};
+use crate::geometry::*;
+use crate::sweep::{Cross, Intersections, LineOrPoint};
+use crate::utils::lex_cmp;
+use crate::winding_order::{Winding, WindingOrder};
+use crate::{GeoFloat, Intersects, LineIntersection, LineMerge};
+use i_overlay::i_float::int::point::IntPoint;
+
+/// Boolean operations on any pair of geometries, including points and lines.
+///
+/// Unlike [`BooleanOps`], which only combines the areas of (Multi)Polygons, the result of an
+/// overlay contains all the points, lines and polygons it consists of, such as the points at
+/// which two lines cross or the parts of a line inside a polygon. As in the [OGC simple features]
+/// model, each part of the result is only returned in its highest dimension: a line running
+/// along the boundary of a polygon of the result is part of the polygon, and is not returned
+/// separately.
+///
+/// The result is a `MultiPoint`, `MultiLineString` or `MultiPolygon` if it has parts of a single
+/// dimension, a `GeometryCollection` of its points, lines and polygons otherwise, and an empty
+/// `GeometryCollection` if it is empty.
+///
+/// The areas of the two geometries are combined with [`BooleanOps`], so the same validity
+/// requirements apply to them; in particular, the polygons of a geometry should not overlap.
+/// Where the polygons of the two geometries only touch each other, their intersection contains the
+/// lines and points at which their boundaries meet, as in JTS.
+///
+/// [OGC simple features]: https://www.ogc.org/standard/sfa/
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, Geometry, OpType, Overlay};
+///
+/// let a = wkt!(LINESTRING(0. 0.,4. 4.));
+/// let b = wkt!(LINESTRING(0. 4.,4. 0.));
+/// assert_eq!(
+///     a.overlay(&b, OpType::Intersection),
+///     Geometry::MultiPoint(wkt!(MULTIPOINT(2. 2.)))
+/// );
+///
+/// // removing a polygon from a line keeps the parts of the line outside it
+/// let line = wkt!(LINESTRING(0. 1.,6. 1.));
+/// let square = wkt!(POLYGON((2. 0.,4. 0.,4. 2.,2. 2.,2. 0.)));
+/// assert_eq!(
+///     line.overlay(&square, OpType::Difference),
+///     Geometry::MultiLineString(wkt!(MULTILINESTRING((0. 1.,2. 1.),(4. 1.,6. 1.))))
+/// );
+/// ```
+pub trait Overlay<T: BoolOpsNum + GeoFloat>: private::Components<T> {
+    /// Compute the overlay of `self` and `other` for the given operation.
+    fn overlay(&self, other: &impl Overlay<T>, op: OpType) -> Geometry<T> {
+        let mut a = private::Parts::default();
+        self.add_components(&mut a);
+        let mut b = private::Parts::default();
+        other.add_components(&mut b);
+        overlay([a.into(), b.into()], op)
+    }
+
+    /// Compute the overlay of `self` and `other` on a grid of fixed precision, like JTS
+    /// `OverlayNG` with a fixed `PrecisionModel`.
+    ///
+    /// The points, lines and polygon rings of both geometries are snap-rounded together to the
+    /// grid of multiples of `1 / scale`, as with [`BooleanOps::boolean_op_with_precision`], so
+    /// that every vertex of the result lies on the grid. Lines which collapse to a point on the
+    /// grid are removed.
+    ///
+    /// # Panics
+    ///
+    /// If `scale` isn't positive, or if the extent of the input, in units of the grid, doesn't
+    /// fit into an `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::{wkt, Geometry, OpType, Overlay};
+    ///
+    /// let line = wkt!(LINESTRING(0.2 2.1,4.1 1.9));
+    /// let square = wkt!(POLYGON((1.1 1.,3. 1.,3. 3.,1.1 3.,1.1 1.)));
+    /// assert_eq!(
+    ///     line.overlay_with_precision(&square, OpType::Intersection, 1.0),
+    ///     Geometry::MultiLineString(wkt!(MULTILINESTRING((1. 2.,3. 2.))))
+    /// );
+    /// ```
+    fn overlay_with_precision(&self, other: &impl Overlay<T>, op: OpType, scale: T) -> Geometry<T> {
+        let mut a = private::Parts::default();
+        self.add_components(&mut a);
+        let mut b = private::Parts::default();
+        other.add_components(&mut b);
+        overlay_with_precision([a.into(), b.into()], op, scale)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat, G: private::Components<T>> Overlay<T> for G {}
+
+// seal the trait providing the parts of a geometry, so that it doesn't leak into the public
+// interface.
+mod private {
+    use super::*;
+    use crate::CoordNum;
+
+    pub struct Parts<T: CoordNum> {
+        pub points: Vec<Coord<T>>,
+        pub lines: Vec<LineString<T>>,
+        pub polygons: Vec<Polygon<T>>,
+    }
+
+    impl<T: CoordNum> Default for Parts<T> {
+        fn default() -> Self {
+            Self {
+                points: vec![],
+                lines: vec![],
+                polygons: vec![],
+            }
+        }
+    }
+
+    pub trait Components<T: BoolOpsNum + GeoFloat> {
+        fn add_components(&self, parts: &mut Parts<T>);
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for Point<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.points.push(self.0);
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for Line<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.lines.push(LineString::from(*self));
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for LineString<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.lines.push(self.clone());
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for Polygon<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.polygons.push(self.clone());
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for Rect<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.polygons.push(self.to_polygon());
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for Triangle<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.polygons.push(self.to_polygon());
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for MultiPoint<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.points.extend(self.iter().map(|point| point.0));
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for MultiLineString<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.lines.extend(self.iter().cloned());
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for MultiPolygon<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            parts.polygons.extend(self.iter().cloned());
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for GeometryCollection<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            for geometry in self {
+                geometry.add_components(parts);
+            }
+        }
+    }
+
+    impl<T: BoolOpsNum + GeoFloat> Components<T> for Geometry<T> {
+        fn add_components(&self, parts: &mut Parts<T>) {
+            match self {
+                Geometry::Point(g) => g.add_components(parts),
+                Geometry::Line(g) => g.add_components(parts),
+                Geometry::LineString(g) => g.add_components(parts),
+                Geometry::Polygon(g) => g.add_components(parts),
+                Geometry::MultiPoint(g) => g.add_components(parts),
+                Geometry::MultiLineString(g) => g.add_components(parts),
+                Geometry::MultiPolygon(g) => g.add_components(parts),
+                Geometry::GeometryCollection(g) => g.add_components(parts),
+                Geometry::Rect(g) => g.add_components(parts),
+                Geometry::Triangle(g) => g.add_components(parts),
+            }
+        }
+    }
+}
+
+/// One of the two geometries of an overlay, split into its parts by dimension.
+struct Operand<T: BoolOpsNum + GeoFloat> {
+    points: Vec<Coord<T>>,
+    lines: MultiLineString<T>,
+    area: MultiPolygon<T>,
+}
+
+impl<T: BoolOpsNum + GeoFloat> From<private::Parts<T>> for Operand<T> {
+    fn from(mut parts: private::Parts<T>) -> Self {
+        // drop empty parts
+        parts.lines.retain(|line| line.0.len() > 1);
+        parts
+            .polygons
+            .retain(|polygon| !polygon.exterior().0.is_empty());
+        Self {
+            points: parts.points,
+            lines: MultiLineString::new(parts.lines),
+            area: MultiPolygon::new(parts.polygons),
+        }
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Operand<T> {
+    /// Where `coord` lies relative to the operand: on its lines, and relative to its area.
+    fn locate(&self, coord: Coord<T>) -> Location {
+        let on_lines = self
+            .lines
+            .iter()
+            .any(|line| line.coordinate_position(&coord) != CoordPos::Outside)
+            || self.points.contains(&coord);
+        Location {
+            on_lines,
+            area: self.area_position(coord),
+        }
+    }
+
+    /// The position of `coord` relative to the area of the operand. Each polygon is considered
+    /// separately, as the boundaries of polygons which touch don't cancel out.
+    fn area_position(&self, coord: Coord<T>) -> CoordPos {
+        let mut position = CoordPos::Outside;
+        for polygon in &self.area {
+            match polygon.coordinate_position(&coord) {
+                CoordPos::Inside => return CoordPos::Inside,
+                CoordPos::OnBoundary => position = CoordPos::OnBoundary,
+                CoordPos::Outside => {}
+            }
+        }
+        position
+    }
+}
+
+/// Where a point, or the interior of a piece of line, lies relative to an operand.
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    /// Whether it is on the points or lines of the operand.
+    on_lines: bool,
+    /// Its position relative to the area of the operand.
+    area: CoordPos,
+}
+
+impl Location {
+    const OUTSIDE: Location = Location {
+        on_lines: false,
+        area: CoordPos::Outside,
+    };
+
+    fn is_inside(&self) -> bool {
+        self.on_lines || self.area != CoordPos::Outside
+    }
+
+    fn merge(&mut self, other: Location) {
+        self.on_lines |= other.on_lines;
+        if other.area == CoordPos::OnBoundary {
+            self.area = CoordPos::OnBoundary;
+        }
+    }
+}
+
+/// Whether something at the given locations is part of the result of `op`.
+fn is_in_result(op: OpType, locations: [Location; 2]) -> bool {
+    let [a, b] = locations.map(|location| location.is_inside());
+    match op {
+        OpType::Intersection => a && b,
+        OpType::Union => a || b,
+        OpType::Difference => a && !b,
+        OpType::Xor => a != b,
+    }
+}
+
+/// Whether something in the result of `op` at the given locations is covered by the closure of
+/// the area of the result, so that it is part of a polygon of the result.
+fn is_covered_by_area(op: OpType, [a, b]: [Location; 2]) -> bool {
+    match op {
+        OpType::Intersection => {
+            (a.area == CoordPos::Inside && b.area != CoordPos::Outside)
+                || (b.area == CoordPos::Inside && a.area != CoordPos::Outside)
+        }
+        // For a difference or xor, anything in the result is outside the area of the operand it
+        // isn't part of, so it is covered if it is in the area of the other one.
+        OpType::Union | OpType::Difference | OpType::Xor => {
+            a.area != CoordPos::Outside || b.area != CoordPos::Outside
+        }
+    }
+}
+
+/// The sides of a segment, looking along it, on which the area of an operand lies.
+#[derive(Debug, Clone, Copy, Default)]
+struct Sides {
+    left: bool,
+    right: bool,
+}
+
+impl Sides {
+    fn reversed(self) -> Self {
+        Self {
+            left: self.right,
+            right: self.left,
+        }
+    }
+
+    fn merge(&mut self, other: Sides) {
+        self.left |= other.left;
+        self.right |= other.right;
+    }
+}
+
+/// The rings of a polygon, each with the side of its segments on which the polygon lies.
+fn rings_with_interior<T: GeoFloat>(
+    polygon: &Polygon<T>,
+) -> impl Iterator<Item = (&LineString<T>, Sides)> {
+    let exterior = (polygon.exterior(), WindingOrder::CounterClockwise);
+    let interiors = polygon
+        .interiors()
+        .iter()
+        .map(|ring| (ring, WindingOrder::Clockwise));
+    std::iter::once(exterior)
+        .chain(interiors)
+        .filter_map(|(ring, interior_on_left)| {
+            let left = ring.winding_order()? == interior_on_left;
+            Some((ring, Sides { left, right: !left }))
+        })
+}
+
+/// A segment of the linework of an operand, used to node it against all the other linework.
+#[derive(Debug, Clone)]
+struct NodingSegment<T: GeoFloat> {
+    line: Line<T>,
+    operand: usize,
+    /// The index of the segment in the segments which are split at their nodes, if it is one.
+    index: Option<usize>,
+    /// For a segment of the boundary of the area of the operand, the side the area lies on.
+    interior: Option<Sides>,
+}
+
+impl<T: GeoFloat> NodingSegment<T> {
+    /// The side on which the area of the operand lies, looking along `line`, if this is a
+    /// segment of its boundary.
+    fn interior_along(&self, line: &Line<T>) -> Option<Sides> {
+        let (a, b) = (self.line.delta(), line.delta());
+        let interior = self.interior?;
+        if a.x * b.x + a.y * b.y < T::zero() {
+            Some(interior.reversed())
+        } else {
+            Some(interior)
+        }
+    }
+}
+
+impl<T: GeoFloat> Cross for NodingSegment<T> {
+    type Scalar = T;
+
+    fn line(&self) -> LineOrPoint<T> {
+        self.line.into()
+    }
+}
+
+/// The linework overlapping a piece of a segment: the operand it belongs to and, if it is part of
+/// the boundary of its area, the side of the piece on which that area lies.
+type Covering = Vec<(usize, Option<Sides>)>;
+
+/// Where a segment of the linework of an operand is split, and which parts of it overlap other
+/// linework.
+struct SegmentNodes<T: GeoFloat> {
+    line: Line<T>,
+    operand: usize,
+    /// For a segment of the boundary of the area of the operand, the side the area lies on.
+    interior: Option<Sides>,
+    nodes: Vec<Coord<T>>,
+    /// The overlapping parts, with the operand and, if it is part of the boundary of its area
+    /// rather than of its lines, the side of the segment on which that area lies.
+    overlaps: Vec<(Line<T>, usize, Option<Sides>)>,
+}
+
+impl<T: GeoFloat> SegmentNodes<T> {
+    /// The position of `coord`, which is on the line of the segment, along it.
+    fn param(&self, coord: Coord<T>) -> T {
+        let delta = self.line.delta();
+        let offset = coord - self.line.start;
+        (offset.x * delta.x + offset.y * delta.y) / (delta.x * delta.x + delta.y * delta.y)
+    }
+
+    /// Split the segment at its nodes, returning each piece with the overlapping parts which
+    /// cover it.
+    fn split(mut self) -> Vec<(Line<T>, Covering)> {
+        self.nodes.push(self.line.start);
+        self.nodes.push(self.line.end);
+        let mut nodes: Vec<(T, Coord<T>)> = self
+            .nodes
+            .iter()
+            .map(|&node| (self.param(node), node))
+            .collect();
+        nodes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        nodes.dedup_by(|a, b| a.1 == b.1);
+
+        let overlaps: Vec<(T, T, usize, Option<Sides>)> = self
+            .overlaps
+            .iter()
+            .map(|&(line, operand, interior)| {
+                let (start, end) = (self.param(line.start), self.param(line.end));
+                (start.min(end), start.max(end), operand, interior)
+            })
+            .collect();
+        nodes
+            .windows(2)
+            .map(|w| {
+                let mid = (w[0].0 + w[1].0) / (T::one() + T::one());
+                let covering = overlaps
+                    .iter()
+                    .filter(|&&(start, end, _, _)| start <= mid && mid <= end)
+                    .map(|&(_, _, operand, interior)| (operand, interior))
+                    .collect();
+                (Line::new(w[0].1, w[1].1), covering)
+            })
+            .collect()
+    }
+
+    /// Split a segment of the lines of an operand at its nodes, locating the pieces relative to
+    /// the linework they overlap. Their position relative to the areas of the operands remains to
+    /// be determined.
+    fn pieces(self) -> Vec<(Line<T>, [Location; 2])> {
+        let operand = self.operand;
+        self.split()
+            .into_iter()
+            .map(|(line, covering)| {
+                let mut locations = [Location::OUTSIDE; 2];
+                locations[operand].on_lines = true;
+                for (other, interior) in covering {
+                    if interior.is_some() {
+                        locations[other].area = CoordPos::OnBoundary;
+                    } else {
+                        locations[other].on_lines = true;
+                    }
+                }
+                (line, locations)
+            })
+            .collect()
+    }
+
+    /// Split a segment of the boundary of the area of an operand at its nodes, finding the sides
+    /// of the pieces on which the areas of the operands lie, where they are known from the
+    /// boundaries the pieces overlap.
+    fn boundary_pieces(self) -> Vec<(Line<T>, [Option<Sides>; 2])> {
+        let (operand, interior) = (self.operand, self.interior.unwrap_or_default());
+        self.split()
+            .into_iter()
+            .map(|(line, covering)| {
+                let mut sides = [None; 2];
+                sides[operand] = Some(interior);
+                for (other, interior) in covering {
+                    if let Some(interior) = interior {
+                        sides[other]
+                            .get_or_insert_with(Sides::default)
+                            .merge(interior);
+                    }
+                }
+                (line, sides)
+            })
+            .collect()
+    }
+}
+
+fn overlay<T: BoolOpsNum + GeoFloat>(operands: [Operand<T>; 2], op: OpType) -> Geometry<T> {
+    let area = match (
+        operands[0].area.0.is_empty(),
+        operands[1].area.0.is_empty(),
+        op,
+    ) {
+        (false, false, _) => operands[0].area.boolean_op(&operands[1].area, op),
+        (_, _, OpType::Intersection) | (true, _, OpType::Difference) => MultiPolygon::new(vec![]),
+        (false, _, _) => operands[0].area.clone(),
+        (true, _, _) => operands[1].area.clone(),
+    };
+
+    let (points, lines) = linework(&operands, op);
+    into_geometry(points, lines, area)
+}
+
+/// Overlay the operands on a grid of fixed precision.
+///
+/// All the linework of the operands is snap-rounded together, so that it only meets at points of
+/// the grid, and the vertices of the result are snapped to the grid as well.
+fn overlay_with_precision<T: BoolOpsNum + GeoFloat>(
+    operands: [Operand<T>; 2],
+    op: OpType,
+    scale: T,
+) -> Geometry<T> {
+    let coords = operands.iter().flat_map(|operand| {
+        let rings = operand.area.iter().flat_map(|polygon| polygon.rings());
+        let lines = operand.lines.iter().chain(rings);
+        operand.points.iter().chain(lines.flat_map(|line| &line.0))
+    });
+    let grid = PrecisionGrid::new(scale, coords);
+
+    // As in JTS, where only points can be in the result of an overlay with a point operand,
+    // the rounded points are located relative to the other operand as it is.
+    let is_puntal = |operand: &Operand<T>| operand.lines.0.is_empty() && operand.area.0.is_empty();
+    let is_points_only = match op {
+        OpType::Intersection => is_puntal(&operands[0]) || is_puntal(&operands[1]),
+        OpType::Difference => is_puntal(&operands[0]),
+        OpType::Union | OpType::Xor => false,
+    };
+    if is_points_only {
+        let operands = operands.map(|operand| {
+            if !is_puntal(&operand) {
+                return operand;
+            }
+            let points = operand
+                .points
+                .iter()
+                .map(|point| grid.snap(point))
+                .collect();
+            Operand { points, ..operand }
+        });
+        let (points, _) = linework(&operands, op);
+        return into_geometry(
+            points,
+            MultiLineString::new(vec![]),
+            MultiPolygon::new(vec![]),
+        );
+    }
+
+    let all_lines: Vec<&LineString<T>> = operands
+        .iter()
+        .flat_map(|operand| {
+            let rings = operand.area.iter().flat_map(|polygon| polygon.rings());
+            operand.lines.iter().chain(rings)
+        })
+        .collect();
+    let mut paths = grid.snap_round(&all_lines).into_iter();
+    let mut area_paths = [vec![], vec![]];
+    let to_line_string = |path: &Vec<IntPoint>| -> LineString<T> {
+        path.iter().map(|&point| grid.coord(point).0).collect()
+    };
+    let mut snapped = vec![];
+    for (operand, area_paths) in operands.iter().zip(&mut area_paths) {
+        let mut lines: Vec<LineString<T>> = operand
+            .lines
+            .iter()
+            .map(|_| to_line_string(&paths.next().expect("a path for each line")))
+            .filter(|line| line.0.len() > 1)
+            .collect();
+        let mut rings = vec![];
+        for _ in operand.area.iter().flat_map(|polygon| polygon.rings()) {
+            let path = paths.next().expect("a path for each ring");
+            rings.push(to_line_string(&path));
+            area_paths.push(path);
+        }
+        // Parts of the area which collapse on the grid become lines of the operand, like in JTS.
+        let area = grid.boolean_op(area_paths.clone(), vec![], OpType::Union);
+        lines.extend(collapsed_edges(&rings, &area));
+        snapped.push(Operand {
+            points: operand
+                .points
+                .iter()
+                .map(|point| grid.snap(point))
+                .collect(),
+            lines: MultiLineString::new(lines),
+            area,
+        });
+    }
+    let operands: [Operand<T>; 2] = snapped.try_into().ok().expect("two operands");
+
+    let [subject, clip] = area_paths;
+    let area = grid.boolean_op(subject, clip, op);
+    let (mut points, lines) = linework(&operands, op);
+    points
+        .iter_mut()
+        .for_each(|point| *point = grid.snap(point));
+    points.sort_by(lex_cmp);
+    points.dedup();
+    let lines = lines
+        .into_iter()
+        .map(|line| {
+            let mut coords: Vec<Coord<T>> = line.0.iter().map(|coord| grid.snap(coord)).collect();
+            coords.dedup();
+            LineString::new(coords)
+        })
+        .filter(|line| line.0.len() > 1)
+        .collect();
+    into_geometry(points, lines, area)
+}
+
+/// The segments of the rings which don't bound the area they enclose, as the rings collapsed
+/// onto each other there.
+fn collapsed_edges<T: BoolOpsNum + GeoFloat>(
+    rings: &[LineString<T>],
+    area: &MultiPolygon<T>,
+) -> Vec<LineString<T>> {
+    let mut segments: Vec<Line<T>> = rings
+        .iter()
+        .flat_map(|ring| ring.lines())
+        .filter(|line| line.start != line.end)
+        .map(|line| {
+            if lex_cmp(&line.start, &line.end).is_gt() {
+                Line::new(line.end, line.start)
+            } else {
+                line
+            }
+        })
+        .collect();
+    segments.sort_by(|a, b| lex_cmp(&a.start, &b.start).then_with(|| lex_cmp(&a.end, &b.end)));
+    // a segment which is traversed an even number of times has the area on both sides or neither
+    segments
+        .chunk_by(|a, b| a == b)
+        .filter(|run| run.len() % 2 == 0)
+        .map(|run| run[0])
+        .filter(|line| {
+            let mid = (line.start + line.end) / (T::one() + T::one());
+            area.coordinate_position(&mid) == CoordPos::Outside
+        })
+        .map(LineString::from)
+        .collect()
+}
+
+/// The points and lines of the result of `op` on the operands, which aren't part of its area.
+fn linework<T: BoolOpsNum + GeoFloat>(
+    operands: &[Operand<T>; 2],
+    op: OpType,
+) -> (Vec<Coord<T>>, MultiLineString<T>) {
+    // Node the lines of both operands against all the linework, so that each piece of them is
+    // either entirely in, or entirely outside of, each part of the operands. For an
+    // intersection, the boundaries of the areas are noded too, to find where they touch.
+    let mut segments: Vec<SegmentNodes<T>> = vec![];
+    let mut noding: Vec<NodingSegment<T>> = vec![];
+    for (operand, geometry) in operands.iter().enumerate() {
+        let lines = geometry
+            .lines
+            .iter()
+            .flat_map(|line| line.lines())
+            .map(|line| (line, None));
+        let boundaries = geometry
+            .area
+            .iter()
+            .flat_map(rings_with_interior)
+            .flat_map(|(ring, interior)| ring.lines().map(move |line| (line, Some(interior))));
+        for (line, interior) in lines.chain(boundaries) {
+            if line.start == line.end {
+                continue;
+            }
+            let index = if interior.is_none() || op == OpType::Intersection {
+                segments.push(SegmentNodes {
+                    line,
+                    operand,
+                    interior,
+                    nodes: vec![],
+                    overlaps: vec![],
+                });
+                Some(segments.len() - 1)
+            } else {
+                None
+            };
+            noding.push(NodingSegment {
+                line,
+                operand,
+                index,
+                interior,
+            });
+        }
+    }
+
+    // The points at which the linework of the operands meet are candidates for the points of
+    // an intersection.
+    let mut points: Vec<Coord<T>> = vec![];
+    for (a, b, intersection) in Intersections::from_iter(noding) {
+        if a.index.is_none() && b.index.is_none() {
+            continue;
+        }
+        match intersection {
+            LineIntersection::SinglePoint { intersection, .. } => {
+                if op == OpType::Intersection && a.operand != b.operand {
+                    points.push(intersection);
+                }
+                for segment in [&a, &b] {
+                    if let Some(i) = segment.index {
+                        segments[i].nodes.push(intersection);
+                    }
+                }
+            }
+            LineIntersection::Collinear { intersection } => {
+                for (segment, other) in [(&a, &b), (&b, &a)] {
+                    if let Some(i) = segment.index {
+                        let nodes = &mut segments[i];
+                        nodes.nodes.extend([intersection.start, intersection.end]);
+                        let interior = other.interior_along(&segment.line);
+                        nodes.overlaps.push((intersection, other.operand, interior));
+                    }
+                }
+            }
+        }
+    }
+
+    let (boundaries, segments): (Vec<_>, Vec<_>) = segments
+        .into_iter()
+        .partition(|segment| segment.interior.is_some());
+
+    // Collect the distinct pieces of the lines, wherever they came from.
+    let mut pieces: Vec<(Line<T>, [Location; 2])> = segments
+        .into_iter()
+        .flat_map(SegmentNodes::pieces)
+        .map(|(line, locations)| {
+            if lex_cmp(&line.start, &line.end).is_gt() {
+                (Line::new(line.end, line.start), locations)
+            } else {
+                (line, locations)
+            }
+        })
+        .collect();
+    pieces
+        .sort_by(|a, b| lex_cmp(&a.0.start, &b.0.start).then_with(|| lex_cmp(&a.0.end, &b.0.end)));
+    pieces.dedup_by(|piece, kept| {
+        if piece.0 != kept.0 {
+            return false;
+        }
+        for i in 0..2 {
+            kept.1[i].merge(piece.1[i]);
+        }
+        true
+    });
+
+    let mut lines: Vec<LineString<T>> = vec![];
+    for (line, mut locations) in pieces {
+        let mid = (line.start + line.end) / (T::one() + T::one());
+        for (location, operand) in locations.iter_mut().zip(operands) {
+            // pieces along the boundary of an area are already known to be on it
+            if location.area != CoordPos::OnBoundary {
+                location.area = operand.area_position(mid);
+            }
+        }
+        if is_in_result(op, locations) && !is_covered_by_area(op, locations) {
+            lines.push(line.into());
+        }
+    }
+
+    // Where the boundaries of the areas of an intersection meet, the pieces with the areas on
+    // the same side are edges of the area of the result, and the others are where they touch.
+    let mut boundary_pieces: Vec<(Line<T>, [Option<Sides>; 2])> = boundaries
+        .into_iter()
+        .flat_map(SegmentNodes::boundary_pieces)
+        .map(|(line, sides)| {
+            if lex_cmp(&line.start, &line.end).is_gt() {
+                let sides = sides.map(|sides| sides.map(Sides::reversed));
+                (Line::new(line.end, line.start), sides)
+            } else {
+                (line, sides)
+            }
+        })
+        .collect();
+    boundary_pieces
+        .sort_by(|a, b| lex_cmp(&a.0.start, &b.0.start).then_with(|| lex_cmp(&a.0.end, &b.0.end)));
+    boundary_pieces.dedup_by(|piece, kept| {
+        if piece.0 != kept.0 {
+            return false;
+        }
+        for i in 0..2 {
+            if let Some(sides) = piece.1[i] {
+                kept.1[i].get_or_insert_with(Sides::default).merge(sides);
+            }
+        }
+        true
+    });
+    let mut area_edges: Vec<Line<T>> = vec![];
+    let mut touching: Vec<LineString<T>> = vec![];
+    for (line, sides) in boundary_pieces {
+        let mid = (line.start + line.end) / (T::one() + T::one());
+        let [a, b] = [0, 1].map(|i| {
+            sides[i].unwrap_or_else(|| {
+                let inside = operands[i].area_position(mid) == CoordPos::Inside;
+                Sides {
+                    left: inside,
+                    right: inside,
+                }
+            })
+        });
+        if (a.left && b.left) || (a.right && b.right) {
+            area_edges.push(line);
+        } else if (a.left || a.right) && (b.left || b.right) {
+            touching.push(line.into());
+        }
+    }
+    // the lines of the operands may run along where the areas touch, and are sorted like the
+    // pieces they consist of
+    touching.retain(|touching| {
+        lines
+            .binary_search_by(|line| {
+                lex_cmp(&line.0[0], &touching.0[0])
+                    .then_with(|| lex_cmp(&line.0[1], &touching.0[1]))
+            })
+            .is_err()
+    });
+    lines.extend(touching);
+    let lines = MultiLineString::new(lines);
+
+    points.extend(operands.iter().flat_map(|operand| &operand.points));
+    points.sort_by(lex_cmp);
+    points.dedup();
+    points.retain(|&point| {
+        let locations = [operands[0].locate(point), operands[1].locate(point)];
+        is_in_result(op, locations)
+            && !is_covered_by_area(op, locations)
+            && !area_edges.iter().any(|edge| edge.intersects(&point))
+            && lines
+                .iter()
+                .all(|line| line.coordinate_position(&point) == CoordPos::Outside)
+    });
+
+    (points, lines.line_merge())
+}
+
+/// Assemble the parts of the result of an overlay into a geometry of the right type.
+fn into_geometry<T: BoolOpsNum + GeoFloat>(
+    points: Vec<Coord<T>>,
+    lines: MultiLineString<T>,
+    area: MultiPolygon<T>,
+) -> Geometry<T> {
+    match (points.is_empty(), lines.0.is_empty(), area.0.is_empty()) {
+        (true, true, true) => Geometry::GeometryCollection(GeometryCollection::default()),
+        (false, true, true) => Geometry::MultiPoint(points.into_iter().map(Point).collect()),
+        (true, false, true) => Geometry::MultiLineString(lines),
+        (true, true, false) => Geometry::MultiPolygon(area),
+        _ => {
+            let points = points
+                .into_iter()
+                .map(|coord| Geometry::Point(Point(coord)));
+            let lines = lines.into_iter().map(Geometry::LineString);
+            let polygons = area.into_iter().map(Geometry::Polygon);
+            Geometry::GeometryCollection(points.chain(lines).chain(polygons).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Relate};
+
+    fn assert_overlay<A: Overlay<f64>, B: Overlay<f64>>(
+        a: &A,
+        b: &B,
+        op: OpType,
+        expected: Geometry<f64>,
+    ) {
+        let actual = a.overlay(b, op);
+        assert!(
+            actual.relate(&expected).is_equal_topo(),
+            "{op:?}: expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn crossing_lines() {
+        let a = wkt!(LINESTRING(0. 0.,2. 2.));
+        let b = wkt!(LINESTRING(0. 2.,2. 0.));
+        assert_eq!(
+            a.overlay(&b, OpType::Intersection),
+            Geometry::MultiPoint(wkt!(MULTIPOINT(1. 1.)))
+        );
+        assert_overlay(
+            &a,
+            &b,
+            OpType::Union,
+            wkt!(MULTILINESTRING((0. 0.,2. 2.),(0. 2.,2. 0.))).into(),
+        );
+        assert_overlay(&a, &b, OpType::Difference, a.clone().into());
+        assert_overlay(
+            &a,
+            &b,
+            OpType::Xor,
+            wkt!(MULTILINESTRING((0. 0.,2. 2.),(0. 2.,2. 0.))).into(),
+        );
+    }
+
+    #[test]
+    fn overlapping_lines() {
+        let a = wkt!(LINESTRING(0. 0.,2. 2.,4. 0.));
+        let b = wkt!(LINESTRING(1. 1.,2. 2.,4. 4.));
+        assert_overlay(
+            &a,
+            &b,
+            OpType::Intersection,
+            wkt!(LINESTRING(1. 1.,2. 2.)).into(),
+        );
+        assert_overlay(
+            &a,
+            &b,
+            OpType::Difference,
+            wkt!(MULTILINESTRING((0. 0.,1. 1.),(2. 2.,4. 0.))).into(),
+        );
+        assert_overlay(
+            &a,
+            &b,
+            OpType::Xor,
+            wkt!(MULTILINESTRING((0. 0.,1. 1.),(2. 2.,4. 0.),(2. 2.,4. 4.))).into(),
+        );
+        let union = a.overlay(&b, OpType::Union);
+        assert_overlay(
+            &union,
+            &wkt!(MULTILINESTRING((0. 0.,2. 2.,4. 0.),(2. 2.,4. 4.))),
+            OpType::Xor,
+            Geometry::GeometryCollection(GeometryCollection::default()),
+        );
+    }
+
+    #[test]
+    fn line_and_polygon() {
+        let line = wkt!(LINESTRING(0. 1.,2. 1.,2. 3.,5. 3.));
+        let square = wkt!(POLYGON((1. 0.,4. 0.,4. 3.,1. 3.,1. 0.)));
+
+        // the part along the boundary is part of the intersection
+        assert_overlay(
+            &line,
+            &square,
+            OpType::Intersection,
+            wkt!(LINESTRING(1. 1.,2. 1.,2. 3.,4. 3.)).into(),
+        );
+        assert_overlay(
+            &line,
+            &square,
+            OpType::Difference,
+            wkt!(MULTILINESTRING((0. 1.,1. 1.),(4. 3.,5. 3.))).into(),
+        );
+        assert_eq!(
+            square.overlay(&line, OpType::Difference),
+            Geometry::MultiPolygon(square.clone().into())
+        );
+
+        let expected = GeometryCollection::new_from(vec![
+            wkt!(LINESTRING(0. 1.,1. 1.)).into(),
+            wkt!(LINESTRING(4. 3.,5. 3.)).into(),
+            square.clone().into(),
+        ]);
+        assert_overlay(
+            &line,
+            &square,
+            OpType::Union,
+            Geometry::GeometryCollection(expected.clone()),
+        );
+        assert_overlay(
+            &line,
+            &square,
+            OpType::Xor,
+            Geometry::GeometryCollection(expected),
+        );
+    }
+
+    #[test]
+    fn line_touching_polygon() {
+        let line = wkt!(LINESTRING(0. 2.,2. 0.));
+        let square = wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 2.,1. 1.)));
+        assert_eq!(
+            line.overlay(&square, OpType::Intersection),
+            Geometry::MultiPoint(wkt!(MULTIPOINT(1. 1.)))
+        );
+    }
+
+    #[test]
+    fn points() {
+        let points = wkt!(MULTIPOINT(0. 0.,1. 1.,2. 2.,1. 1.));
+        let square = wkt!(POLYGON((1. 1.,3. 1.,3. 3.,1. 3.,1. 1.)));
+        assert_eq!(
+            points.overlay(&square, OpType::Intersection),
+            Geometry::MultiPoint(wkt!(MULTIPOINT(1. 1.,2. 2.)))
+        );
+        assert_eq!(
+            points.overlay(&square, OpType::Difference),
+            Geometry::MultiPoint(wkt!(MULTIPOINT(0. 0.)))
+        );
+        assert_eq!(
+            square.overlay(&points, OpType::Union),
+            Geometry::GeometryCollection(GeometryCollection::new_from(vec![
+                wkt!(POINT(0. 0.)).into(),
+                square.clone().into(),
+            ]))
+        );
+
+        let line = wkt!(LINESTRING(0. 0.,2. 0.));
+        let points = wkt!(MULTIPOINT(0. 0.,1. 0.,1. 1.));
+        assert_eq!(
+            points.overlay(&line, OpType::Xor),
+            Geometry::GeometryCollection(GeometryCollection::new_from(vec![
+                wkt!(POINT(1. 1.)).into(),
+                line.into(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        let a = wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)));
+        let b = wkt!(POLYGON((1. 1.,3. 1.,3. 3.,1. 3.,1. 1.)));
+        for op in [
+            OpType::Intersection,
+            OpType::Union,
+            OpType::Difference,
+            OpType::Xor,
+        ] {
+            assert_eq!(a.overlay(&b, op), a.boolean_op(&b, op).into());
+        }
+    }
+
+    #[test]
+    fn touching_polygons() {
+        let square = wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)));
+        assert_overlay(
+            &square,
+            &wkt!(POLYGON((2. 1.,4. 1.,4. 3.,2. 3.,2. 1.))),
+            OpType::Intersection,
+            wkt!(LINESTRING(2. 1.,2. 2.)).into(),
+        );
+        assert_eq!(
+            square.overlay(
+                &wkt!(POLYGON((2. 2.,3. 2.,3. 3.,2. 3.,2. 2.))),
+                OpType::Intersection
+            ),
+            Geometry::MultiPoint(wkt!(MULTIPOINT(2. 2.)))
+        );
+
+        // an edge shared by polygons on the same side of it is part of their intersection
+        let inner = wkt!(POLYGON((0. 0.,1. 0.,1. 2.,0. 2.,0. 0.)));
+        assert_overlay(&square, &inner, OpType::Intersection, inner.clone().into());
+
+        let polygons = wkt!(MULTIPOLYGON(
+            ((1. 0.,3. 0.,3. 1.,1. 1.,1. 0.)),
+            ((2. 1.5,3. 1.5,3. 2.,2. 2.,2. 1.5))
+        ));
+        let expected = GeometryCollection::new_from(vec![
+            wkt!(POLYGON((1. 0.,2. 0.,2. 1.,1. 1.,1. 0.))).into(),
+            wkt!(LINESTRING(2. 1.5,2. 2.)).into(),
+        ]);
+        assert_overlay(
+            &square,
+            &polygons,
+            OpType::Intersection,
+            Geometry::GeometryCollection(expected),
+        );
+    }
+
+    #[test]
+    fn fixed_precision() {
+        let line = wkt!(LINESTRING(0.2 0.9,2.8 3.1));
+        let square = wkt!(POLYGON((1.1 1.,3. 1.,3. 3.,1.1 3.,1.1 1.)));
+        assert_eq!(
+            square.overlay_with_precision(&line, OpType::Difference, 1.0),
+            Geometry::MultiPolygon(wkt!(MULTIPOLYGON(((1. 1.,3. 1.,3. 3.,1. 3.,1. 1.)))))
+        );
+        assert_overlay(
+            &line.overlay_with_precision(&square, OpType::Union, 1.0),
+            &wkt!(GEOMETRYCOLLECTION(
+                LINESTRING(0. 1.,1. 1.),
+                POLYGON((1. 1.,3. 1.,3. 3.,1. 3.,1. 1.))
+            )),
+            OpType::Xor,
+            Geometry::GeometryCollection(GeometryCollection::default()),
+        );
+
+        // a sliver which collapses to a line on the grid is kept as a line
+        let sliver = wkt!(POLYGON((0. 0.,4. 0.,4. 0.2,0. 0.)));
+        let cutter = wkt!(POLYGON((1. -1.,3. -1.,3. 1.,1. 1.,1. -1.)));
+        assert_overlay(
+            &sliver.overlay_with_precision(&cutter, OpType::Intersection, 1.0),
+            &wkt!(LINESTRING(1. 0.,3. 0.)),
+            OpType::Xor,
+            Geometry::GeometryCollection(GeometryCollection::default()),
+        );
+
+        // points are located relative to the geometry as it is when only points can result
+        let point = wkt!(POINT(10.1 10.4));
+        let line = wkt!(LINESTRING(9.6 10.,20.1 19.6));
+        assert_eq!(
+            point.overlay_with_precision(&line, OpType::Difference, 1.0),
+            Geometry::MultiPoint(wkt!(MULTIPOINT(10. 10.)))
+        );
+        assert_eq!(
+            point.overlay_with_precision(&line, OpType::Union, 1.0),
+            Geometry::MultiLineString(wkt!(MULTILINESTRING((10. 10.,20. 20.))))
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let line = wkt!(LINESTRING(0. 0.,1. 1.));
+        let empty = GeometryCollection::<f64>::default();
+        assert_eq!(
+            line.overlay(&empty, OpType::Intersection),
+            Geometry::GeometryCollection(GeometryCollection::default())
+        );
+        assert_eq!(
+            empty.overlay(&line, OpType::Union),
+            Geometry::MultiLineString(MultiLineString::new(vec![line]))
+        );
+    }
+
+    #[test]
+    fn jts_test_suite() {
+        jts_test_runner::assert_jts_tests_succeed("*OverlayLL*.xml");
+        jts_test_runner::assert_jts_tests_succeed("*OverlayLA*.xml");
+        jts_test_runner::assert_jts_tests_succeed("*OverlayPL*.xml");
+        jts_test_runner::assert_jts_tests_succeed("*OverlayPP*.xml");
+        jts_test_runner::assert_jts_tests_succeed("*OverlayPA*.xml");
+    }
+}
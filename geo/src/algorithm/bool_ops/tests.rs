@@ -94,6 +94,43 @@ fn jts_overlay_tests() {
     jts_test_runner::assert_jts_tests_succeed("*Overlay*.xml");
 }
 
+#[test]
+fn test_boolean_op_with_precision() {
+    use super::OpType;
+    use crate::Validation;
+
+    let a: Polygon = wkt!(POLYGON((0.03 0.01,10.04 0.02,9.98 10.01,0.01 0.03)));
+    let b: Polygon = wkt!(POLYGON((4.97 0.52,10.51 5.03,5.26 9.48,4.97 0.52)));
+    for op in [
+        OpType::Intersection,
+        OpType::Union,
+        OpType::Difference,
+        OpType::Xor,
+    ] {
+        let result = a.boolean_op_with_precision(&b, op, 10.0);
+        assert!(!result.0.is_empty());
+        assert!(result.is_valid(), "{op:?}: {}", result.to_wkt());
+        for polygon in &result {
+            for coord in polygon
+                .exterior()
+                .coords()
+                .chain(polygon.interiors().iter().flat_map(|ring| ring.coords()))
+            {
+                assert_eq!(coord.x * 10.0, (coord.x * 10.0).round());
+                assert_eq!(coord.y * 10.0, (coord.y * 10.0).round());
+            }
+        }
+    }
+
+    // the segment from (0 0) to (4 4) passes through the pixel of the vertex (2.2 1.8) of b, so
+    // it is snapped to (2 2), where the polygons now touch
+    let a: Polygon = wkt!(POLYGON((0. 0.,4. 4.,0. 4.,0. 0.)));
+    let b: Polygon = wkt!(POLYGON((2.2 1.8,4. 0.,4. 2.,2.2 1.8)));
+    let union = a.boolean_op_with_precision(&b, OpType::Union, 1.0);
+    assert_eq!(union.0.len(), 2);
+    assert!(union.is_valid());
+}
+
 #[test]
 fn jts_test_overlay_la_1() {
     // From TestOverlayLA.xml test case with description "mLmA - A and B complex, overlapping and touching #1"
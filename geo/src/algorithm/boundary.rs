@@ -0,0 +1,409 @@
+use crate::relate::geomgraph::node_map::{NodeFactory, NodeMap};
+use crate::{
+    Coord, GeoFloat, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, Rect, Triangle,
+};
+
+/// Rules for deciding whether the endpoints of lines are on the boundary of a geometry, based on
+/// the number of line endpoints at the same coordinate.
+///
+/// These match the `BoundaryNodeRule`s of JTS.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryNodeRule {
+    /// The OGC Simple Features rule: a point is on the boundary if an odd number of endpoints
+    /// meet there. Notably, the ends of a closed `LineString` are not on its boundary.
+    ///
+    /// This is the rule used by [`Relate`](crate::Relate).
+    #[default]
+    Mod2,
+    /// Every endpoint is on the boundary.
+    EndPoint,
+    /// Only endpoints shared by more than one line end are on the boundary.
+    MultivalentEndPoint,
+    /// Only endpoints not shared with another line end are on the boundary.
+    MonovalentEndPoint,
+}
+
+impl BoundaryNodeRule {
+    /// Whether a point where `boundary_count` line endpoints meet is on the boundary.
+    pub fn is_in_boundary(&self, boundary_count: usize) -> bool {
+        match self {
+            BoundaryNodeRule::Mod2 => boundary_count % 2 == 1,
+            BoundaryNodeRule::EndPoint => boundary_count > 0,
+            BoundaryNodeRule::MultivalentEndPoint => boundary_count > 1,
+            BoundaryNodeRule::MonovalentEndPoint => boundary_count == 1,
+        }
+    }
+}
+
+/// Compute the topological boundary of a geometry, as defined by the OGC Simple Features
+/// specification.
+///
+/// - The boundary of a point is empty.
+/// - The boundary of a line is a set of its endpoints, chosen by a [`BoundaryNodeRule`].
+/// - The boundary of a polygon is its rings.
+///
+/// The endpoints of all the lines in a `MultiLineString` or `GeometryCollection` are considered
+/// together, so that e.g. the point where two lines of a `MultiLineString` meet is not on its
+/// boundary.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, Boundary};
+///
+/// let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.));
+/// assert_eq!(line_string.boundary(), wkt!(MULTIPOINT(0. 0.,10. 10.)));
+///
+/// // the ends of a closed line are not on its boundary
+/// let ring = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.,0. 0.));
+/// assert_eq!(ring.boundary(), wkt!(MULTIPOINT EMPTY));
+///
+/// let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 0.)));
+/// assert_eq!(polygon.boundary(), wkt!(MULTILINESTRING((0. 0.,10. 0.,10. 10.,0. 0.))));
+/// ```
+pub trait Boundary<T: GeoFloat> {
+    type Output;
+
+    /// The boundary of the geometry, using the [`BoundaryNodeRule::Mod2`] rule.
+    fn boundary(&self) -> Self::Output {
+        self.boundary_with_rule(BoundaryNodeRule::default())
+    }
+
+    /// The boundary of the geometry, using `rule` to decide which line endpoints are included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::boundary::{Boundary, BoundaryNodeRule};
+    /// use geo::wkt;
+    ///
+    /// let lines = wkt!(MULTILINESTRING((0. 0.,10. 0.),(10. 0.,20. 0.),(10. 0.,10. 10.)));
+    ///
+    /// assert_eq!(
+    ///     lines.boundary_with_rule(BoundaryNodeRule::Mod2),
+    ///     wkt!(MULTIPOINT(0. 0.,10. 0.,10. 10.,20. 0.))
+    /// );
+    /// assert_eq!(
+    ///     lines.boundary_with_rule(BoundaryNodeRule::MonovalentEndPoint),
+    ///     wkt!(MULTIPOINT(0. 0.,10. 10.,20. 0.))
+    /// );
+    /// assert_eq!(
+    ///     lines.boundary_with_rule(BoundaryNodeRule::MultivalentEndPoint),
+    ///     wkt!(MULTIPOINT(10. 0.))
+    /// );
+    /// ```
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output;
+}
+
+impl<T: GeoFloat> Boundary<T> for Point<T> {
+    /// Always empty.
+    type Output = GeometryCollection<T>;
+
+    fn boundary_with_rule(&self, _rule: BoundaryNodeRule) -> Self::Output {
+        GeometryCollection::default()
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for MultiPoint<T> {
+    /// Always empty.
+    type Output = GeometryCollection<T>;
+
+    fn boundary_with_rule(&self, _rule: BoundaryNodeRule) -> Self::Output {
+        GeometryCollection::default()
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for Line<T> {
+    type Output = MultiPoint<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        let mut builder = BoundaryBuilder::new();
+        builder.add_endpoints(self.start, self.end);
+        builder.points(rule)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for LineString<T> {
+    type Output = MultiPoint<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        let mut builder = BoundaryBuilder::new();
+        builder.add_line_string(self);
+        builder.points(rule)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for MultiLineString<T> {
+    type Output = MultiPoint<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        let mut builder = BoundaryBuilder::new();
+        for line_string in self {
+            builder.add_line_string(line_string);
+        }
+        builder.points(rule)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for Polygon<T> {
+    type Output = MultiLineString<T>;
+
+    fn boundary_with_rule(&self, _rule: BoundaryNodeRule) -> Self::Output {
+        let mut builder = BoundaryBuilder::new();
+        builder.add_polygon(self);
+        MultiLineString::new(builder.rings)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for MultiPolygon<T> {
+    type Output = MultiLineString<T>;
+
+    fn boundary_with_rule(&self, _rule: BoundaryNodeRule) -> Self::Output {
+        let mut builder = BoundaryBuilder::new();
+        for polygon in self {
+            builder.add_polygon(polygon);
+        }
+        MultiLineString::new(builder.rings)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for Rect<T> {
+    type Output = MultiLineString<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        self.to_polygon().boundary_with_rule(rule)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for Triangle<T> {
+    type Output = MultiLineString<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        self.to_polygon().boundary_with_rule(rule)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for GeometryCollection<T> {
+    /// The boundary points of the lines, followed by the rings of the polygons. Either is
+    /// omitted if it would be empty.
+    type Output = GeometryCollection<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        let mut builder = BoundaryBuilder::new();
+        for geometry in self {
+            builder.add_geometry(geometry);
+        }
+        let mut boundary = vec![];
+        let rings = std::mem::take(&mut builder.rings);
+        let points = builder.points(rule);
+        if !points.0.is_empty() {
+            boundary.push(Geometry::MultiPoint(points));
+        }
+        if !rings.is_empty() {
+            boundary.push(Geometry::MultiLineString(MultiLineString::new(rings)));
+        }
+        GeometryCollection::new_from(boundary)
+    }
+}
+
+impl<T: GeoFloat> Boundary<T> for Geometry<T> {
+    type Output = Geometry<T>;
+
+    fn boundary_with_rule(&self, rule: BoundaryNodeRule) -> Self::Output {
+        match self {
+            Geometry::Point(g) => Geometry::GeometryCollection(g.boundary_with_rule(rule)),
+            Geometry::Line(g) => Geometry::MultiPoint(g.boundary_with_rule(rule)),
+            Geometry::LineString(g) => Geometry::MultiPoint(g.boundary_with_rule(rule)),
+            Geometry::Polygon(g) => Geometry::MultiLineString(g.boundary_with_rule(rule)),
+            Geometry::MultiPoint(g) => Geometry::GeometryCollection(g.boundary_with_rule(rule)),
+            Geometry::MultiLineString(g) => Geometry::MultiPoint(g.boundary_with_rule(rule)),
+            Geometry::MultiPolygon(g) => Geometry::MultiLineString(g.boundary_with_rule(rule)),
+            Geometry::GeometryCollection(g) => {
+                Geometry::GeometryCollection(g.boundary_with_rule(rule))
+            }
+            Geometry::Rect(g) => Geometry::MultiLineString(g.boundary_with_rule(rule)),
+            Geometry::Triangle(g) => Geometry::MultiLineString(g.boundary_with_rule(rule)),
+        }
+    }
+}
+
+/// Counts the line endpoints at each coordinate.
+#[derive(PartialEq)]
+struct EndpointCounter;
+
+impl<F: GeoFloat> NodeFactory<F> for EndpointCounter {
+    type Node = (Coord<F>, usize);
+    fn create_node(coordinate: Coord<F>) -> Self::Node {
+        (coordinate, 0)
+    }
+}
+
+struct BoundaryBuilder<T: GeoFloat> {
+    endpoints: NodeMap<T, EndpointCounter>,
+    rings: Vec<LineString<T>>,
+}
+
+impl<T: GeoFloat> BoundaryBuilder<T> {
+    fn new() -> Self {
+        Self {
+            endpoints: NodeMap::new(),
+            rings: vec![],
+        }
+    }
+
+    fn add_endpoints(&mut self, start: Coord<T>, end: Coord<T>) {
+        self.endpoints.insert_node_with_coordinate(start).1 += 1;
+        self.endpoints.insert_node_with_coordinate(end).1 += 1;
+    }
+
+    fn add_line_string(&mut self, line_string: &LineString<T>) {
+        if let (Some(&start), Some(&end)) = (line_string.0.first(), line_string.0.last()) {
+            self.add_endpoints(start, end);
+        }
+    }
+
+    fn add_polygon(&mut self, polygon: &Polygon<T>) {
+        self.rings.extend(
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .filter(|ring| !ring.0.is_empty())
+                .cloned(),
+        );
+    }
+
+    fn add_geometry(&mut self, geometry: &Geometry<T>) {
+        match geometry {
+            Geometry::Point(_) | Geometry::MultiPoint(_) => {}
+            Geometry::Line(line) => self.add_endpoints(line.start, line.end),
+            Geometry::LineString(line_string) => self.add_line_string(line_string),
+            Geometry::MultiLineString(multi_line_string) => {
+                for line_string in multi_line_string {
+                    self.add_line_string(line_string);
+                }
+            }
+            Geometry::Polygon(polygon) => self.add_polygon(polygon),
+            Geometry::MultiPolygon(multi_polygon) => {
+                for polygon in multi_polygon {
+                    self.add_polygon(polygon);
+                }
+            }
+            Geometry::Rect(rect) => self.add_polygon(&rect.to_polygon()),
+            Geometry::Triangle(triangle) => self.add_polygon(&triangle.to_polygon()),
+            Geometry::GeometryCollection(collection) => {
+                for geometry in collection {
+                    self.add_geometry(geometry);
+                }
+            }
+        }
+    }
+
+    /// The endpoints on the boundary, in lexicographic order.
+    fn points(self, rule: BoundaryNodeRule) -> MultiPoint<T> {
+        self.endpoints
+            .into_iter()
+            .filter(|(_, count)| rule.is_in_boundary(*count))
+            .map(|(coord, _)| Point(coord))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt;
+
+    #[test]
+    fn points() {
+        assert!(wkt!(POINT(1. 1.)).boundary().0.is_empty());
+        assert!(wkt!(MULTIPOINT(1. 1.,2. 2.)).boundary().0.is_empty());
+    }
+
+    #[test]
+    fn line_strings() {
+        let line = Line::new((0., 0.), (1., 1.));
+        assert_eq!(line.boundary(), wkt!(MULTIPOINT(0. 0.,1. 1.)));
+
+        let empty = LineString::<f64>::new(vec![]);
+        assert_eq!(empty.boundary(), MultiPoint::new(vec![]));
+
+        let ring = wkt!(LINESTRING(0. 0.,1. 0.,1. 1.,0. 0.));
+        assert_eq!(ring.boundary(), MultiPoint::new(vec![]));
+        assert_eq!(
+            ring.boundary_with_rule(BoundaryNodeRule::EndPoint),
+            wkt!(MULTIPOINT(0. 0.))
+        );
+        assert_eq!(
+            ring.boundary_with_rule(BoundaryNodeRule::MultivalentEndPoint),
+            wkt!(MULTIPOINT(0. 0.))
+        );
+        assert_eq!(
+            ring.boundary_with_rule(BoundaryNodeRule::MonovalentEndPoint),
+            MultiPoint::new(vec![])
+        );
+    }
+
+    #[test]
+    fn multi_line_string_rules() {
+        // four lines meeting at (1 1)
+        let lines = wkt!(MULTILINESTRING(
+            (1. 1.,0. 0.),
+            (1. 1.,2. 0.),
+            (1. 1.,2. 2.),
+            (1. 1.,0. 2.)
+        ));
+        assert_eq!(lines.boundary(), wkt!(MULTIPOINT(0. 0.,0. 2.,2. 0.,2. 2.)));
+        assert_eq!(
+            lines.boundary_with_rule(BoundaryNodeRule::EndPoint),
+            wkt!(MULTIPOINT(0. 0.,0. 2.,1. 1.,2. 0.,2. 2.))
+        );
+        assert_eq!(
+            lines.boundary_with_rule(BoundaryNodeRule::MultivalentEndPoint),
+            wkt!(MULTIPOINT(1. 1.))
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 0.),(5. 1.,9. 1.,9. 5.,5. 1.)));
+        assert_eq!(
+            polygon.boundary(),
+            wkt!(MULTILINESTRING((0. 0.,10. 0.,10. 10.,0. 0.),(5. 1.,9. 1.,9. 5.,5. 1.)))
+        );
+
+        let rect = Rect::new((0., 0.), (1., 1.));
+        assert_eq!(
+            rect.boundary(),
+            MultiLineString::new(vec![rect.to_polygon().exterior().clone()])
+        );
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let collection = wkt!(GEOMETRYCOLLECTION(
+            POINT(5. 5.),
+            LINESTRING(0. 0.,1. 0.),
+            LINESTRING(1. 0.,2. 0.),
+            POLYGON((0. 0.,1. 1.,0. 1.,0. 0.))
+        ));
+        assert_eq!(
+            collection.boundary(),
+            wkt!(GEOMETRYCOLLECTION(
+                MULTIPOINT(0. 0.,2. 0.),
+                MULTILINESTRING((0. 0.,1. 1.,0. 1.,0. 0.))
+            ))
+        );
+        assert_eq!(
+            Geometry::GeometryCollection(collection).boundary(),
+            Geometry::GeometryCollection(wkt!(GEOMETRYCOLLECTION(
+                MULTIPOINT(0. 0.,2. 0.),
+                MULTILINESTRING((0. 0.,1. 1.,0. 1.,0. 0.))
+            )))
+        );
+    }
+
+    #[test]
+    fn jts_test_suite() {
+        jts_test_runner::assert_jts_tests_succeed("*Boundary*.xml");
+    }
+}
@@ -0,0 +1,821 @@
+use crate::algorithm::orient::{Direction, Orient};
+use crate::bool_ops::{unary_difference, unary_union, BoolOpsNum, BooleanOps};
+use crate::geometry::*;
+use crate::winding_order::Winding;
+use crate::{CoordNum, GeoFloat};
+
+/// Buffer a geometry by a (Euclidean) distance.
+///
+/// The buffer of a geometry is the region of the plane within `distance` of the geometry.
+/// Positive distances grow the geometry, negative distances shrink the areal parts of it
+/// (lines and points have no interior, so a non-positive buffer of them is empty).
+///
+/// Curved parts of the buffer (round caps and joins) are approximated by line segments, see
+/// [`BufferStyle::quadrant_segments`].
+///
+/// The output is always a valid [`MultiPolygon`], since the buffer is assembled from simple
+/// pieces and then merged with [`unary_union`].
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, Area, Buffer};
+/// use geo::buffer::{BufferStyle, LineCap, LineJoin};
+///
+/// let square = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)));
+///
+/// // Grow the square by 1 unit, keeping the corners sharp.
+/// let style = BufferStyle::new(1.0).line_join(LineJoin::Miter(5.0));
+/// let grown = square.buffer_with_style(style);
+/// assert_relative_eq!(grown.unsigned_area(), 144.0, epsilon = 1e-6);
+///
+/// // Shrink it instead.
+/// let shrunk = square.buffer(-1.0);
+/// assert_relative_eq!(shrunk.unsigned_area(), 64.0, epsilon = 1e-6);
+///
+/// // A line buffered with flat caps becomes a rectangle.
+/// let line = wkt!(LINESTRING(0. 0.,10. 0.));
+/// let ribbon = line.buffer_with_style(BufferStyle::new(2.0).line_cap(LineCap::Flat));
+/// assert_relative_eq!(ribbon.unsigned_area(), 40.0, epsilon = 1e-6);
+/// # use approx::assert_relative_eq;
+/// ```
+pub trait Buffer {
+    type Scalar: BoolOpsNum + GeoFloat;
+
+    /// Buffer the geometry by `distance`, with round caps and round joins.
+    fn buffer(&self, distance: Self::Scalar) -> MultiPolygon<Self::Scalar> {
+        self.buffer_with_style(BufferStyle::new(distance))
+    }
+
+    /// Buffer the geometry using the given [`BufferStyle`].
+    fn buffer_with_style(&self, style: BufferStyle<Self::Scalar>) -> MultiPolygon<Self::Scalar>;
+}
+
+/// The shape drawn at the ends of a buffered line (and around a buffered point).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    /// A half circle around the end point. Points are buffered into circles.
+    #[default]
+    Round,
+    /// The buffer ends exactly at the end point. Points are buffered into nothing.
+    Flat,
+    /// The buffer is extended past the end point by the buffer distance. Points are
+    /// buffered into axis-aligned squares.
+    Square,
+}
+
+/// The shape drawn on the outside of a corner where two segments meet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin<T> {
+    /// A circular arc around the corner.
+    #[default]
+    Round,
+    /// Extend the offset segments until they meet in a sharp corner.
+    ///
+    /// The parameter is the mitre limit: the maximum distance of the corner from the vertex, as a
+    /// multiple of the buffer distance. Corners which would reach further are cut off at that
+    /// distance.
+    Miter(T),
+    /// Cut the corner off with a straight line between the ends of the offset segments.
+    Bevel,
+}
+
+/// Parameters for [`Buffer::buffer_with_style`].
+///
+/// The defaults (round caps, round joins and 8 segments per quarter circle) match those of JTS and
+/// GEOS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferStyle<T> {
+    /// The buffer distance. Negative distances shrink polygons.
+    pub distance: T,
+    /// The shape of the ends of lines.
+    pub line_cap: LineCap,
+    /// The shape of the outside of corners.
+    pub line_join: LineJoin<T>,
+    /// The number of segments used to approximate a quarter of a circle.
+    pub quadrant_segments: usize,
+}
+
+impl<T: GeoFloat> BufferStyle<T> {
+    /// Default number of segments per quarter circle used by JTS and GEOS.
+    pub const DEFAULT_QUADRANT_SEGMENTS: usize = 8;
+
+    /// Default mitre limit used by JTS and GEOS, for [`LineJoin::Miter`].
+    pub fn default_miter_limit() -> T {
+        T::from(5).unwrap()
+    }
+
+    pub fn new(distance: T) -> Self {
+        Self {
+            distance,
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            quadrant_segments: Self::DEFAULT_QUADRANT_SEGMENTS,
+        }
+    }
+
+    pub fn distance(mut self, distance: T) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn line_cap(mut self, line_cap: LineCap) -> Self {
+        self.line_cap = line_cap;
+        self
+    }
+
+    pub fn line_join(mut self, line_join: LineJoin<T>) -> Self {
+        self.line_join = line_join;
+        self
+    }
+
+    /// Values lower than 1 are treated as 1.
+    pub fn quadrant_segments(mut self, quadrant_segments: usize) -> Self {
+        self.quadrant_segments = quadrant_segments;
+        self
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for Point<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_point(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for MultiPoint<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_multi_point(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for Line<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_line(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for LineString<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_line_string(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for MultiLineString<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_multi_line_string(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for Polygon<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_polygon(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for MultiPolygon<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_multi_polygon(self);
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for Rect<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_polygon(&self.to_polygon());
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for Triangle<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_polygon(&self.to_polygon());
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for GeometryCollection<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        for geometry in self {
+            parts.add_geometry(geometry);
+        }
+        parts.buffer(&style)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> Buffer for Geometry<T> {
+    type Scalar = T;
+    fn buffer_with_style(&self, style: BufferStyle<T>) -> MultiPolygon<T> {
+        let mut parts = BufferParts::default();
+        parts.add_geometry(self);
+        parts.buffer(&style)
+    }
+}
+
+/// The components of a geometry, sorted by dimension.
+///
+/// Geometries are buffered as a whole, rather than component by component, so that e.g. the
+/// members of a `MultiPolygon` which share an edge are not eroded along that edge by a negative
+/// buffer.
+pub(crate) struct BufferParts<T: CoordNum> {
+    pub(crate) polygons: Vec<Polygon<T>>,
+    pub(crate) lines: Vec<Vec<Coord<T>>>,
+    pub(crate) points: Vec<Coord<T>>,
+}
+
+impl<T: CoordNum> Default for BufferParts<T> {
+    fn default() -> Self {
+        Self {
+            polygons: vec![],
+            lines: vec![],
+            points: vec![],
+        }
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> BufferParts<T> {
+    pub(crate) fn add_geometry(&mut self, geometry: &Geometry<T>) {
+        match geometry {
+            Geometry::Point(g) => self.add_point(g),
+            Geometry::Line(g) => self.add_line(g),
+            Geometry::LineString(g) => self.add_line_string(g),
+            Geometry::Polygon(g) => self.add_polygon(g),
+            Geometry::MultiPoint(g) => self.add_multi_point(g),
+            Geometry::MultiLineString(g) => self.add_multi_line_string(g),
+            Geometry::MultiPolygon(g) => self.add_multi_polygon(g),
+            Geometry::GeometryCollection(g) => {
+                for geometry in g {
+                    self.add_geometry(geometry)
+                }
+            }
+            Geometry::Rect(g) => self.add_polygon(&g.to_polygon()),
+            Geometry::Triangle(g) => self.add_polygon(&g.to_polygon()),
+        }
+    }
+
+    fn add_point(&mut self, point: &Point<T>) {
+        self.points.push(point.0);
+    }
+
+    fn add_multi_point(&mut self, multi_point: &MultiPoint<T>) {
+        self.points.extend(multi_point.iter().map(|p| p.0));
+    }
+
+    fn add_line(&mut self, line: &Line<T>) {
+        self.add_coords(vec![line.start, line.end]);
+    }
+
+    fn add_line_string(&mut self, line_string: &LineString<T>) {
+        self.add_coords(line_string.0.clone());
+    }
+
+    fn add_multi_line_string(&mut self, multi_line_string: &MultiLineString<T>) {
+        for line_string in multi_line_string {
+            self.add_line_string(line_string);
+        }
+    }
+
+    fn add_polygon(&mut self, polygon: &Polygon<T>) {
+        if !polygon.exterior().0.is_empty() {
+            self.polygons.push(polygon.orient(Direction::Default));
+        }
+    }
+
+    fn add_multi_polygon(&mut self, multi_polygon: &MultiPolygon<T>) {
+        for polygon in multi_polygon {
+            self.add_polygon(polygon);
+        }
+    }
+
+    fn add_coords(&mut self, mut coords: Vec<Coord<T>>) {
+        coords.dedup();
+        match coords.len() {
+            0 => {}
+            // A zero length line is buffered like a point.
+            1 => self.points.push(coords[0]),
+            _ => self.lines.push(coords),
+        }
+    }
+
+    fn buffer(self, style: &BufferStyle<T>) -> MultiPolygon<T> {
+        let distance = style.distance;
+        let mut pieces = PieceBuilder::new(style);
+        if distance <= T::zero() {
+            let areal = if self.polygons.is_empty() {
+                MultiPolygon::new(vec![])
+            } else {
+                unary_union(&self.polygons).orient(Direction::Default)
+            };
+            if distance.is_zero() {
+                return areal;
+            }
+            // Erode the polygons by subtracting everything within `distance` of their boundary.
+            for polygon in &areal {
+                for ring in polygon.rings() {
+                    pieces.add_ring(&ring.0, Side::Left);
+                }
+            }
+            if pieces.pieces.is_empty() {
+                return areal;
+            }
+            return unary_difference(&areal.0, &pieces.pieces).orient(Direction::Default);
+        }
+
+        // Unlike for erosion, the polygons of the input can be used directly here, since the union
+        // of the buffers of the polygons is the buffer of their union. This way degenerate rings
+        // are still buffered like lines, and the polygons aren't rounded by a union before they're
+        // merged with the pieces.
+        for polygon in &self.polygons {
+            for ring in polygon.rings() {
+                pieces.add_ring(&ring.0, Side::Right);
+            }
+        }
+        for line in &self.lines {
+            if line.len() > 2 && line.first() == line.last() {
+                pieces.add_ring(line, Side::Both);
+            } else {
+                pieces.add_line(line);
+            }
+        }
+        for point in &self.points {
+            pieces.add_point(*point);
+        }
+
+        let mut pieces = pieces.pieces;
+        pieces.extend(self.polygons);
+        if pieces.is_empty() {
+            return MultiPolygon::new(vec![]);
+        }
+        unary_union(&pieces).orient(Direction::Default)
+    }
+}
+
+/// Which side(s) of a directed line to buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    Left,
+    Right,
+    Both,
+}
+
+impl Side {
+    fn includes(self, other: Side) -> bool {
+        self == Side::Both || self == other
+    }
+}
+
+/// Builds the simple polygons whose union is the buffer of a set of lines.
+///
+/// Each segment contributes a rectangle, each vertex a wedge filling the gap on the outside of the
+/// corner, and each line end a cap.
+pub(crate) struct PieceBuilder<'a, T: GeoFloat> {
+    style: &'a BufferStyle<T>,
+    distance: T,
+    pub(crate) pieces: Vec<Polygon<T>>,
+}
+
+impl<'a, T: GeoFloat> PieceBuilder<'a, T> {
+    pub(crate) fn new(style: &'a BufferStyle<T>) -> Self {
+        Self {
+            style,
+            distance: style.distance.abs(),
+            pieces: vec![],
+        }
+    }
+
+    /// Add the buffer of an open line, with caps at its ends.
+    ///
+    /// `coords` must not contain consecutive duplicates.
+    pub(crate) fn add_line(&mut self, coords: &[Coord<T>]) {
+        debug_assert!(coords.len() >= 2);
+        self.add_path(coords, Side::Both, false);
+        let n = coords.len();
+        self.add_cap(coords[1], coords[0]);
+        self.add_cap(coords[n - 2], coords[n - 1]);
+    }
+
+    /// Add the buffer of one or both sides of a closed ring.
+    pub(crate) fn add_ring(&mut self, ring: &[Coord<T>], side: Side) {
+        let mut coords = ring.to_vec();
+        coords.dedup();
+        if coords.len() < 2 {
+            return;
+        }
+        if coords.first() != coords.last() {
+            coords.push(coords[0]);
+        }
+        self.add_path(&coords, side, true);
+    }
+
+    /// Add the segments and joins of a path. If `closed`, the last coordinate must equal the first.
+    fn add_path(&mut self, coords: &[Coord<T>], side: Side, closed: bool) {
+        for window in coords.windows(2) {
+            self.add_segment(window[0], window[1], side);
+        }
+        for window in coords.windows(3) {
+            self.add_join(window[0], window[1], window[2], side);
+        }
+        let n = coords.len();
+        if closed && n > 2 {
+            self.add_join(coords[n - 2], coords[0], coords[1], side);
+        }
+    }
+
+    pub(crate) fn add_point(&mut self, center: Coord<T>) {
+        let d = self.distance;
+        match self.style.line_cap {
+            LineCap::Flat => {}
+            LineCap::Round => {
+                let start = center + Coord { x: d, y: T::zero() };
+                let mut ring = arc(center, start, start, true, d, self.style.quadrant_segments);
+                ring.pop();
+                self.push_piece(ring);
+            }
+            LineCap::Square => {
+                let ring = vec![
+                    center + Coord { x: -d, y: -d },
+                    center + Coord { x: d, y: -d },
+                    center + Coord { x: d, y: d },
+                    center + Coord { x: -d, y: d },
+                ];
+                self.push_piece(ring);
+            }
+        }
+    }
+
+    fn add_segment(&mut self, a: Coord<T>, b: Coord<T>, side: Side) {
+        let Some(u) = unit(b - a) else {
+            return;
+        };
+        let d = self.distance;
+        let left = left_normal(u) * d;
+        let ring = match side {
+            Side::Left => vec![a, b, b + left, a + left],
+            Side::Right => vec![a, a - left, b - left, b],
+            // the ends are vertices, so that the joins share edges with the rectangle exactly
+            Side::Both => vec![a - left, b - left, b, b + left, a + left, a],
+        };
+        self.push_piece(ring);
+    }
+
+    /// Add the join at `vertex`, on the outside of the corner, if that is a side being buffered.
+    pub(crate) fn add_join(
+        &mut self,
+        prev: Coord<T>,
+        vertex: Coord<T>,
+        next: Coord<T>,
+        side: Side,
+    ) {
+        let (Some(u1), Some(u2)) = (unit(vertex - prev), unit(next - vertex)) else {
+            return;
+        };
+        let cross = u1.x * u2.y - u1.y * u2.x;
+        let dot = u1.x * u2.x + u1.y * u2.y;
+        let epsilon = T::epsilon() * (T::one() + T::one()).powi(4);
+        if cross.abs() <= epsilon && dot > T::zero() {
+            // collinear, the segment rectangles already meet
+            return;
+        }
+        // The outside of a left turn is on the right, and vice versa. A line doubling back on
+        // itself is treated as a left turn.
+        let outer = if cross > T::zero() || cross.abs() <= epsilon {
+            Side::Right
+        } else {
+            Side::Left
+        };
+        if !side.includes(outer) {
+            return;
+        }
+        let mut ring = vec![vertex];
+        ring.extend(join(self.style, self.distance, vertex, u1, u2, outer));
+        self.push_piece(ring);
+    }
+
+    /// Add the cap at `end`, the last vertex of a line arriving from `prev`.
+    pub(crate) fn add_cap(&mut self, prev: Coord<T>, end: Coord<T>) {
+        let Some(u) = unit(end - prev) else {
+            return;
+        };
+        let d = self.distance;
+        let left = end + left_normal(u) * d;
+        let right = end - left_normal(u) * d;
+        match self.style.line_cap {
+            LineCap::Flat => {}
+            LineCap::Round => {
+                let mut ring = vec![end];
+                ring.extend(arc(end, right, left, true, d, self.style.quadrant_segments));
+                self.push_piece(ring);
+            }
+            LineCap::Square => {
+                let extension = u * d;
+                self.push_piece(vec![right, right + extension, left + extension, left]);
+            }
+        }
+    }
+
+    fn push_piece(&mut self, coords: Vec<Coord<T>>) {
+        let mut ring = LineString::new(coords);
+        ring.close();
+        ring.make_ccw_winding();
+        self.pieces.push(Polygon::new(ring, vec![]));
+    }
+}
+
+/// The outline of the join at `vertex`, between a segment with direction `u1` and one with
+/// direction `u2`, on the `outer` side of the corner. It runs from the end of the first offset
+/// segment to the start of the second.
+pub(crate) fn join<T: GeoFloat>(
+    style: &BufferStyle<T>,
+    distance: T,
+    vertex: Coord<T>,
+    u1: Coord<T>,
+    u2: Coord<T>,
+    outer: Side,
+) -> Vec<Coord<T>> {
+    let (n1, n2) = match outer {
+        Side::Right => (-left_normal(u1), -left_normal(u2)),
+        _ => (left_normal(u1), left_normal(u2)),
+    };
+    let d = distance;
+    let p1 = vertex + n1 * d;
+    let p2 = vertex + n2 * d;
+
+    match style.line_join {
+        LineJoin::Round => arc(
+            vertex,
+            p1,
+            p2,
+            outer == Side::Right,
+            d,
+            style.quadrant_segments,
+        ),
+        LineJoin::Bevel => vec![p1, p2],
+        LineJoin::Miter(limit) => {
+            let cos = dot_product(n1, n2);
+            let limit = limit.max(T::zero());
+            let miter_ratio_sq = (T::one() + T::one()) / (T::one() + cos);
+            let epsilon = T::epsilon() * (T::one() + T::one()).powi(4);
+            if T::one() + cos > epsilon && miter_ratio_sq <= limit * limit {
+                let miter = vertex + (n1 + n2) * (d / (T::one() + cos));
+                vec![p1, miter, p2]
+            } else {
+                // Cut the corner off perpendicular to the bisector, at the mitre limit.
+                let bisector = unit(n1 + n2).unwrap_or(u1);
+                let reach = limit * d;
+                let t1 = (reach - d * dot_product(n1, bisector)) / dot_product(u1, bisector);
+                let t2 = (reach - d * dot_product(n2, bisector)) / dot_product(u2, bisector);
+                if t1 > T::zero() && t2 < T::zero() {
+                    vec![p1, p1 + u1 * t1, p2 + u2 * t2, p2]
+                } else {
+                    vec![p1, p2]
+                }
+            }
+        }
+    }
+}
+
+/// Approximate the arc around `center` from `start` to `end`, both of which are at `radius`
+/// from the center. The returned points include `start` and `end`.
+///
+/// If `start == end`, a full circle is returned.
+pub(crate) fn arc<T: GeoFloat>(
+    center: Coord<T>,
+    start: Coord<T>,
+    end: Coord<T>,
+    ccw: bool,
+    radius: T,
+    quadrant_segments: usize,
+) -> Vec<Coord<T>> {
+    let two_pi = T::from(std::f64::consts::TAU).unwrap();
+    let angle_of = |c: Coord<T>| (c.y - center.y).atan2(c.x - center.x);
+    let start_angle = angle_of(start);
+    let mut sweep = if ccw {
+        angle_of(end) - start_angle
+    } else {
+        start_angle - angle_of(end)
+    };
+    if sweep <= T::zero() {
+        sweep = sweep + two_pi;
+    }
+    let quantum = two_pi / T::from(4 * quadrant_segments.max(1)).unwrap();
+    // Like JTS, round to the nearest number of segments, so that e.g. a half circle gets
+    // exactly twice the quadrant segments despite rounding errors.
+    let half = T::from(0.5).unwrap();
+    let segments = (sweep / quantum + half).floor().max(T::one());
+    let increment = if ccw { sweep } else { -sweep } / segments;
+    let segments = segments.to_usize().unwrap_or(1);
+
+    let mut coords = Vec::with_capacity(segments + 1);
+    coords.push(start);
+    for i in 1..segments {
+        let angle = start_angle + increment * T::from(i).unwrap();
+        coords.push(Coord {
+            x: center.x + radius * angle.cos(),
+            y: center.y + radius * angle.sin(),
+        });
+    }
+    coords.push(end);
+    coords
+}
+
+pub(crate) fn unit<T: GeoFloat>(c: Coord<T>) -> Option<Coord<T>> {
+    let length = c.x.hypot(c.y);
+    if length.is_zero() || !length.is_finite() {
+        None
+    } else {
+        Some(c / length)
+    }
+}
+
+pub(crate) fn left_normal<T: GeoFloat>(u: Coord<T>) -> Coord<T> {
+    Coord { x: -u.y, y: u.x }
+}
+
+fn dot_product<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> T {
+    a.x * b.x + a.y * b.y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Area, Contains, Relate, Validation};
+
+    fn circle_area(radius: f64) -> f64 {
+        // the area of the 32-gon inscribed in the circle
+        let n = 32.0;
+        0.5 * n * radius * radius * (std::f64::consts::TAU / n).sin()
+    }
+
+    #[test]
+    fn run_jts_buffer_tests() {
+        jts_test_runner::assert_jts_tests_succeed("*Buffer*.xml");
+    }
+
+    #[test]
+    fn point() {
+        let point = wkt!(POINT(1. 2.));
+        let buffered = point.buffer(3.0);
+        assert_eq!(buffered.0.len(), 1);
+        assert_eq!(buffered.0[0].exterior().0.len(), 33);
+        assert_relative_eq!(buffered.unsigned_area(), circle_area(3.0), epsilon = 1e-6);
+        assert!(buffered.is_valid());
+
+        let square = point.buffer_with_style(BufferStyle::new(3.0).line_cap(LineCap::Square));
+        assert_relative_eq!(square.unsigned_area(), 36.0);
+
+        let flat = point.buffer_with_style(BufferStyle::new(3.0).line_cap(LineCap::Flat));
+        assert!(flat.0.is_empty());
+
+        assert!(point.buffer(0.0).0.is_empty());
+        assert!(point.buffer(-1.0).0.is_empty());
+    }
+
+    #[test]
+    fn line_string() {
+        let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.));
+        let round = line_string.buffer(1.0);
+        assert!(round.is_valid());
+        assert_relative_eq!(
+            round.unsigned_area(),
+            39.0 + circle_area(1.0) * 1.25,
+            epsilon = 1e-6
+        );
+
+        let flat_miter = line_string.buffer_with_style(
+            BufferStyle::new(1.0)
+                .line_cap(LineCap::Flat)
+                .line_join(LineJoin::Miter(5.0)),
+        );
+        assert!(flat_miter
+            .relate(&wkt!(POLYGON((0. -1.,11. -1.,11. 10.,9. 10.,9. 1.,0. 1.,0. -1.))))
+            .is_equal_topo());
+
+        let square_bevel = line_string.buffer_with_style(
+            BufferStyle::new(1.0)
+                .line_cap(LineCap::Square)
+                .line_join(LineJoin::Bevel),
+        );
+        assert!(square_bevel
+            .relate(&wkt!(POLYGON((-1. -1.,10. -1.,11. 0.,11. 11.,9. 11.,9. 1.,-1. 1.,-1. -1.))))
+            .is_equal_topo());
+
+        assert!(line_string.buffer(-1.0).0.is_empty());
+    }
+
+    #[test]
+    fn limited_miter() {
+        // a sharp spike, whose mitre would reach far beyond the limit
+        let line_string = wkt!(LINESTRING(0. 0.,10. 1.,0. 2.));
+        let buffered = line_string.buffer_with_style(
+            BufferStyle::new(1.0)
+                .line_cap(LineCap::Flat)
+                .line_join(LineJoin::Miter(2.0)),
+        );
+        assert!(buffered.is_valid());
+        assert!(buffered.contains(&wkt!(POINT(11.9 1.))));
+        assert!(!buffered.contains(&wkt!(POINT(12.1 1.))));
+    }
+
+    #[test]
+    fn closed_line_string_has_no_caps() {
+        let ring = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.,0. 10.,0. 0.));
+        let buffered =
+            ring.buffer_with_style(BufferStyle::new(1.0).line_join(LineJoin::Miter(5.0)));
+        let expected = wkt!(POLYGON((-1. -1.,11. -1.,11. 11.,-1. 11.,-1. -1.),(1. 1.,1. 9.,9. 9.,9. 1.,1. 1.)));
+        assert!(buffered.relate(&expected).is_equal_topo());
+    }
+
+    #[test]
+    fn no_rounding_holes() {
+        // a part of a line from TestBufferFailure.xml, whose join met the sides of the segment
+        // rectangles only approximately, which the union rounded into a hole
+        let line_string = wkt!(LINESTRING(640770.447752096 216792.399898366,640769.98018796 216795.624688816,640769.110287021 216798.527305023));
+        let buffered = line_string.buffer(100.0);
+        assert!(buffered.is_valid());
+        assert_eq!(buffered.0.len(), 1);
+        assert!(buffered.0[0].interiors().is_empty());
+    }
+
+    #[test]
+    fn polygon() {
+        let polygon =
+            wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.),(4. 4.,6. 4.,6. 6.,4. 6.,4. 4.)));
+        let grown = polygon.buffer(1.0);
+        assert!(grown.is_valid());
+        assert_eq!(grown.0.len(), 1);
+        assert!(grown.0[0].interiors().is_empty());
+        assert_relative_eq!(
+            grown.unsigned_area(),
+            140.0 + circle_area(1.0),
+            epsilon = 1e-6
+        );
+
+        let shrunk = polygon.buffer_with_style(BufferStyle::new(-1.0).line_join(LineJoin::Bevel));
+        assert!(shrunk.is_valid());
+        let expected = wkt!(POLYGON((1. 1.,9. 1.,9. 9.,1. 9.,1. 1.),(4. 3.,3. 4.,3. 6.,4. 7.,6. 7.,7. 6.,7. 4.,6. 3.,4. 3.)));
+        assert!(shrunk.relate(&expected).is_equal_topo());
+
+        assert!(polygon.buffer(-5.0).0.is_empty());
+        assert!(polygon.buffer(0.0).relate(&polygon).is_equal_topo());
+    }
+
+    #[test]
+    fn concave_polygon() {
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,5. 1.,0. 10.,0. 0.)));
+        let grown =
+            polygon.buffer_with_style(BufferStyle::new(0.5).line_join(LineJoin::Miter(10.0)));
+        assert!(grown.is_valid());
+        assert!(grown.contains(&polygon));
+        let shrunk = polygon.buffer(-0.5);
+        assert!(shrunk.is_valid());
+        assert!(polygon.contains(&shrunk));
+    }
+
+    #[test]
+    fn multi_polygon_is_buffered_as_a_whole() {
+        let multi_polygon = wkt!(MULTIPOLYGON(((0. 0.,5. 0.,5. 5.,0. 5.,0. 0.)),((5. 0.,10. 0.,10. 5.,5. 5.,5. 0.))));
+        let shrunk =
+            multi_polygon.buffer_with_style(BufferStyle::new(-1.0).line_join(LineJoin::Miter(5.0)));
+        assert!(shrunk
+            .relate(&wkt!(POLYGON((1. 1.,9. 1.,9. 4.,1. 4.,1. 1.))))
+            .is_equal_topo());
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let collection = Geometry::GeometryCollection(
+            wkt!(GEOMETRYCOLLECTION(POINT(20. 20.),LINESTRING(20. 0.,30. 0.),POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)))),
+        );
+        let buffered = collection.buffer(1.0);
+        assert!(buffered.is_valid());
+        assert_eq!(buffered.0.len(), 3);
+
+        let empty: GeometryCollection = wkt!(GEOMETRYCOLLECTION EMPTY);
+        assert!(empty.buffer(1.0).0.is_empty());
+    }
+}
@@ -0,0 +1,490 @@
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, RTreeObject};
+
+use crate::coordinate_position::CoordPos;
+use crate::relate::geomgraph::node_map::{NodeFactory, NodeMap};
+use crate::utils::{least_index, lex_cmp, touch_at_ends};
+use crate::{BoundingRect, CoordinatePosition, Distance, Euclidean, GeoFloat, SimplifyIdx};
+use crate::{Coord, Line, LineString, MultiPolygon, Point, Polygon};
+
+/// Simplify a polygonal coverage: a set of polygons which don't overlap, and which share their
+/// common boundaries vertex for vertex, like the regions of a map.
+///
+/// Simplifying each polygon on its own with [`Simplify`](crate::Simplify) or
+/// [`SimplifyVwPreserve`](crate::SimplifyVwPreserve) would simplify the boundary between two
+/// neighbours differently on each side, opening gaps and overlaps between them. Instead, the
+/// rings are split into edges at the vertices where three or more edges meet, and each edge is
+/// simplified once, using the [Ramer–Douglas–Peucker](https://en.wikipedia.org/wiki/Ramer–Douglas–Peucker_algorithm)
+/// algorithm, before the polygons are rebuilt from the simplified edges. The vertices where edges
+/// meet are kept.
+///
+/// The topology of the coverage is preserved: an edge which would cross another edge after
+/// simplification, or pass to the other side of a vertex, is left unsimplified, and rings keep
+/// at least three distinct vertices.
+///
+/// An `epsilon` less than or equal to zero will return the coverage unaltered.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, CoverageSimplify};
+///
+/// // two squares, which share a wobbly edge
+/// let coverage = [
+///     wkt!(POLYGON((0. 0.,2. 0.,2.1 1.,2. 2.,0. 2.,0. 0.))),
+///     wkt!(POLYGON((2. 0.,4. 0.,4. 2.,2. 2.,2.1 1.,2. 0.))),
+/// ];
+///
+/// let simplified = coverage.simplify_coverage(0.5);
+///
+/// assert_eq!(
+///     simplified,
+///     vec![
+///         wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.))),
+///         wkt!(POLYGON((2. 0.,4. 0.,4. 2.,2. 2.,2. 0.))),
+///     ]
+/// );
+/// ```
+pub trait CoverageSimplify<T: GeoFloat> {
+    type Output;
+
+    fn simplify_coverage(&self, epsilon: T) -> Self::Output;
+}
+
+impl<T: GeoFloat> CoverageSimplify<T> for [Polygon<T>] {
+    type Output = Vec<Polygon<T>>;
+
+    fn simplify_coverage(&self, epsilon: T) -> Self::Output {
+        let coverage = Coverage::new(self);
+        let kept = coverage.simplify(epsilon);
+        coverage.polygons(&kept)
+    }
+}
+
+impl<T: GeoFloat> CoverageSimplify<T> for MultiPolygon<T> {
+    type Output = MultiPolygon<T>;
+
+    fn simplify_coverage(&self, epsilon: T) -> Self::Output {
+        MultiPolygon::new(self.0.simplify_coverage(epsilon))
+    }
+}
+
+/// The distinct neighbours of each vertex.
+#[derive(Debug, Clone, PartialEq)]
+struct NeighbourNodeFactory;
+
+impl<F: GeoFloat> NodeFactory<F> for NeighbourNodeFactory {
+    type Node = Vec<Coord<F>>;
+    fn create_node(_coordinate: Coord<F>) -> Self::Node {
+        vec![]
+    }
+}
+
+/// The second coordinate and the index of each edge starting at a vertex.
+#[derive(Debug, Clone, PartialEq)]
+struct EdgeNodeFactory;
+
+impl<F: GeoFloat> NodeFactory<F> for EdgeNodeFactory {
+    type Node = Vec<(Coord<F>, usize)>;
+    fn create_node(_coordinate: Coord<F>) -> Self::Node {
+        vec![]
+    }
+}
+
+enum Ring<T: GeoFloat> {
+    /// The edges of the ring, and whether each is traversed forwards, with the first coordinate
+    /// of the ring, which it's rebuilt from if that is kept.
+    Edges {
+        edges: Vec<(usize, bool)>,
+        start: Coord<T>,
+    },
+    /// A ring with fewer than three distinct vertices, which is left as is.
+    Degenerate(LineString<T>),
+}
+
+/// The polygons of a coverage, with their rings split into edges between nodes.
+struct Coverage<T: GeoFloat> {
+    /// The distinct edges, oriented so that each edge is stored only once.
+    edges: Vec<Vec<Coord<T>>>,
+    /// The rings of each polygon, exterior first.
+    polygons: Vec<Vec<Ring<T>>>,
+}
+
+impl<T: GeoFloat> Coverage<T> {
+    fn new(polygons: &[Polygon<T>]) -> Self {
+        let rings: Vec<Vec<Vec<Coord<T>>>> = polygons
+            .iter()
+            .map(|polygon| {
+                std::iter::once(polygon.exterior())
+                    .chain(polygon.interiors())
+                    .map(|ring| {
+                        let mut coords = ring.0.clone();
+                        coords.dedup();
+                        coords
+                    })
+                    .collect()
+            })
+            .collect();
+        let is_ring = |coords: &[Coord<T>]| coords.len() >= 4 && coords.first() == coords.last();
+
+        let mut neighbours = NodeMap::<T, NeighbourNodeFactory>::new();
+        for ring in rings.iter().flatten().filter(|ring| is_ring(ring)) {
+            for segment in ring.windows(2) {
+                neighbours
+                    .insert_node_with_coordinate(segment[0])
+                    .push(segment[1]);
+                neighbours
+                    .insert_node_with_coordinate(segment[1])
+                    .push(segment[0]);
+            }
+        }
+        for vertex_neighbours in neighbours.iter_mut() {
+            vertex_neighbours.sort_by(lex_cmp);
+            vertex_neighbours.dedup();
+        }
+        // edges meet at the vertices which don't just continue a single path
+        let is_node = |coord: Coord<T>| {
+            neighbours
+                .find(coord)
+                .is_some_and(|vertex_neighbours| vertex_neighbours.len() != 2)
+        };
+
+        let mut coverage = Coverage {
+            edges: vec![],
+            polygons: vec![],
+        };
+        let mut edge_index = NodeMap::<T, EdgeNodeFactory>::new();
+        for polygon in rings {
+            let polygon = polygon
+                .into_iter()
+                .map(|ring| {
+                    if !is_ring(&ring) {
+                        return Ring::Degenerate(LineString::new(ring));
+                    }
+                    let edges = split_ring(&ring, is_node)
+                        .into_iter()
+                        .map(|edge| coverage.add_edge(edge, &mut edge_index))
+                        .collect();
+                    Ring::Edges {
+                        edges,
+                        start: ring[0],
+                    }
+                })
+                .collect();
+            coverage.polygons.push(polygon);
+        }
+        coverage
+    }
+
+    /// Add an edge unless it was already added by a neighbouring ring, returning its index and
+    /// whether it's traversed forwards.
+    fn add_edge(
+        &mut self,
+        mut edge: Vec<Coord<T>>,
+        edge_index: &mut NodeMap<T, EdgeNodeFactory>,
+    ) -> (usize, bool) {
+        let last = edge.len() - 1;
+        let forward = lex_cmp(&edge[0], &edge[last])
+            .then_with(|| lex_cmp(&edge[1], &edge[last - 1]))
+            .is_le();
+        if !forward {
+            edge.reverse();
+        }
+        // two edges starting with the same segment are the same, as they only end at a node
+        let starting = edge_index.insert_node_with_coordinate(edge[0]);
+        if let Some(&(_, index)) = starting.iter().find(|(second, _)| *second == edge[1]) {
+            return (index, forward);
+        }
+        starting.push((edge[1], self.edges.len()));
+        self.edges.push(edge);
+        (self.edges.len() - 1, forward)
+    }
+
+    /// The indices of the vertices to keep of each edge.
+    fn simplify(&self, epsilon: T) -> Vec<Vec<usize>> {
+        let mut kept: Vec<Vec<usize>> = self
+            .edges
+            .iter()
+            .map(|edge| simplify_edge(edge, epsilon))
+            .collect();
+
+        // a ring needs three distinct vertices, so its edges may have to keep more
+        for ring in self.polygons.iter().flatten() {
+            let Ring::Edges { edges, .. } = ring else {
+                continue;
+            };
+            for &(edge, _) in edges {
+                let vertices: usize = edges.iter().map(|&(edge, _)| kept[edge].len() - 1).sum();
+                if vertices >= 3 {
+                    break;
+                }
+                if kept[edge].len() == 2 {
+                    kept[edge] = simplify_open_edge(&self.edges[edge], epsilon, true);
+                }
+            }
+        }
+
+        loop {
+            let conflicts = self.conflicts(&kept);
+            if conflicts.is_empty() {
+                return kept;
+            }
+            for edge in conflicts {
+                kept[edge] = (0..self.edges[edge].len()).collect();
+            }
+        }
+    }
+
+    /// The simplified edges which cross another edge, or have moved across a vertex.
+    fn conflicts(&self, kept: &[Vec<usize>]) -> Vec<usize> {
+        let segments = RTree::bulk_load(
+            kept.iter()
+                .enumerate()
+                .flat_map(|(edge, indices)| {
+                    indices.windows(2).enumerate().map(move |(k, pair)| {
+                        let coords = &self.edges[edge];
+                        GeomWithData::new(Line::new(coords[pair[0]], coords[pair[1]]), (edge, k))
+                    })
+                })
+                .collect(),
+        );
+        let vertices = RTree::bulk_load(
+            kept.iter()
+                .enumerate()
+                .flat_map(|(edge, indices)| {
+                    indices.iter().map(move |&i| Point(self.edges[edge][i]))
+                })
+                .collect(),
+        );
+
+        (0..self.edges.len())
+            .filter(|&edge| kept[edge].len() < self.edges[edge].len())
+            .filter(|&edge| {
+                let coords = &self.edges[edge];
+                kept[edge].windows(2).enumerate().any(|(k, pair)| {
+                    let segment = Line::new(coords[pair[0]], coords[pair[1]]);
+                    let crosses = segments
+                        .locate_in_envelope_intersecting(&segment.envelope())
+                        .any(|other| {
+                            other.data != (edge, k) && !touch_at_ends(segment, *other.geom())
+                        });
+                    if crosses || pair[1] == pair[0] + 1 {
+                        return crosses;
+                    }
+                    // the region between the removed vertices and the segment replacing them
+                    let removed = Polygon::new(coords[pair[0]..=pair[1]].to_vec().into(), vec![]);
+                    let bounds = removed.bounding_rect().unwrap();
+                    let envelope =
+                        rstar::AABB::from_corners(Point(bounds.min()), Point(bounds.max()));
+                    vertices.locate_in_envelope(&envelope).any(|vertex| {
+                        vertex.0 != segment.start
+                            && vertex.0 != segment.end
+                            && removed.coordinate_position(&vertex.0) == CoordPos::Inside
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn polygons(&self, kept: &[Vec<usize>]) -> Vec<Polygon<T>> {
+        let ring = |ring: &Ring<T>| match ring {
+            Ring::Degenerate(line_string) => line_string.clone(),
+            Ring::Edges { edges, start } => {
+                let mut coords = vec![];
+                for &(edge, forward) in edges {
+                    coords.pop();
+                    let simplified = kept[edge].iter().map(|&i| self.edges[edge][i]);
+                    if forward {
+                        coords.extend(simplified);
+                    } else {
+                        coords.extend(simplified.rev());
+                    }
+                }
+                if let Some(offset) = coords.iter().position(|coord| coord == start) {
+                    coords.pop();
+                    coords.rotate_left(offset);
+                    coords.push(coords[0]);
+                }
+                LineString::new(coords)
+            }
+        };
+        self.polygons
+            .iter()
+            .map(|rings| Polygon::new(ring(&rings[0]), rings[1..].iter().map(ring).collect()))
+            .collect()
+    }
+}
+
+/// Split a closed ring into edges between nodes. A ring without nodes is a single edge, starting
+/// at its least vertex so that it's split the same way for each polygon it belongs to.
+fn split_ring<T: GeoFloat>(
+    ring: &[Coord<T>],
+    is_node: impl Fn(Coord<T>) -> bool,
+) -> Vec<Vec<Coord<T>>> {
+    let open = &ring[..ring.len() - 1];
+    let start = open
+        .iter()
+        .position(|coord| is_node(*coord))
+        .unwrap_or_else(|| least_index(open));
+
+    let mut edges = vec![];
+    let mut edge = vec![open[start]];
+    for i in 1..=open.len() {
+        let coord = open[(start + i) % open.len()];
+        edge.push(coord);
+        if i == open.len() || is_node(coord) {
+            edges.push(std::mem::replace(&mut edge, vec![coord]));
+        }
+    }
+    edges
+}
+
+/// The indices of the vertices of an edge which are kept by the simplification.
+fn simplify_edge<T: GeoFloat>(edge: &[Coord<T>], epsilon: T) -> Vec<usize> {
+    let last = edge.len() - 1;
+    if edge[0] != edge[last] {
+        return simplify_open_edge(edge, epsilon, false);
+    }
+
+    // a closed edge is a whole ring, so split it at its furthest vertex and keep both halves
+    let split = (1..last)
+        .max_by(|&a, &b| {
+            let distance = |i: usize| Euclidean.distance(Point(edge[0]), Point(edge[i]));
+            distance(a).total_cmp(&distance(b))
+        })
+        .unwrap_or(last);
+    let mut indices = simplify_open_edge(&edge[..=split], epsilon, true);
+    indices.pop();
+    indices.extend(
+        simplify_open_edge(&edge[split..], epsilon, true)
+            .into_iter()
+            .map(|i| i + split),
+    );
+    indices
+}
+
+/// A `protect`ed edge keeps at least one vertex besides its ends, if it has any.
+fn simplify_open_edge<T: GeoFloat>(edge: &[Coord<T>], epsilon: T, protect: bool) -> Vec<usize> {
+    let mut indices = LineString::from(edge.to_vec()).simplify_idx(epsilon);
+    let last = edge.len() - 1;
+    if protect && indices.len() == 2 && last > 1 {
+        let chord = Line::new(edge[0], edge[last]);
+        let furthest = (1..last)
+            .max_by(|&a, &b| {
+                let distance = |i: usize| Euclidean.distance(edge[i], &chord);
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap();
+        indices.insert(1, furthest);
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Area, BooleanOps, Validation};
+    use wkt::TryFromWkt;
+
+    /// The polygons don't overlap, and together cover the same area as their union.
+    fn assert_is_coverage(polygons: &[Polygon<f64>]) {
+        let union = polygons
+            .iter()
+            .fold(MultiPolygon::new(vec![]), |union, polygon| {
+                union.union(polygon)
+            });
+        let total: f64 = polygons.iter().map(|polygon| polygon.unsigned_area()).sum();
+        assert_relative_eq!(union.unsigned_area(), total, epsilon = 1e-9);
+        for polygon in polygons {
+            assert!(polygon.is_valid(), "{polygon:?} is invalid");
+        }
+    }
+
+    #[test]
+    fn shared_edges_are_simplified_once() {
+        // three regions meeting along wobbly boundaries
+        let coverage = [
+            wkt!(POLYGON((0. 0.,5. 0.,5.2 1.,4.9 2.,5.1 3.,5. 5.,2. 5.1,0. 5.,0. 0.))),
+            wkt!(POLYGON((5. 0.,10. 0.,10. 5.,7. 5.2,5. 5.,5.1 3.,4.9 2.,5.2 1.,5. 0.))),
+            wkt!(POLYGON((0. 5.,2. 5.1,5. 5.,7. 5.2,10. 5.,10. 10.,0. 10.,0. 5.))),
+        ];
+        let simplified = coverage.simplify_coverage(0.5);
+        assert_eq!(
+            simplified,
+            vec![
+                wkt!(POLYGON((0. 0.,5. 0.,5. 5.,0. 5.,0. 0.))),
+                wkt!(POLYGON((5. 0.,10. 0.,10. 5.,5. 5.,5. 0.))),
+                wkt!(POLYGON((0. 5.,5. 5.,10. 5.,10. 10.,0. 10.,0. 5.))),
+            ]
+        );
+        assert_is_coverage(&simplified);
+        assert_is_coverage(&coverage.simplify_coverage(0.15));
+    }
+
+    #[test]
+    fn island_in_hole() {
+        let coverage = MultiPolygon::new(vec![
+            wkt!(POLYGON(
+                (0. 0.,10. 0.,10. 10.,0. 10.,0. 0.),
+                (3. 3.,5. 2.9,7. 3.,7.1 5.,7. 7.,3. 7.,3. 3.)
+            )),
+            wkt!(POLYGON((3. 3.,3. 7.,7. 7.,7.1 5.,7. 3.,5. 2.9,3. 3.))),
+        ]);
+        let simplified = coverage.simplify_coverage(0.5);
+        assert_eq!(simplified.0[0].interiors()[0].0.len(), 5);
+        assert_eq!(simplified.0[1].exterior().0.len(), 5);
+        assert_is_coverage(&simplified.0);
+    }
+
+    #[test]
+    fn rings_do_not_collapse() {
+        let coverage = [wkt!(POLYGON((0. 0.,1. 0.,1. 1.,0. 1.,0. 0.)))];
+        let simplified = coverage.simplify_coverage(100.);
+        assert_eq!(simplified[0], coverage[0]);
+
+        let coverage = [wkt!(POLYGON((0. 0.,1. 0.,2. 0.1,3. 0.,3. 1.,2. 1.1,1. 1.,0. 1.,0. 0.)))];
+        let simplified = coverage.simplify_coverage(100.);
+        assert_eq!(simplified[0].exterior().0.len(), 5);
+        assert_is_coverage(&simplified);
+    }
+
+    #[test]
+    fn topology_is_preserved() {
+        // a stack of polygons, with a small one in a hole of the second from the top, whose
+        // boundary with the one below bends by `bend`
+        let stack = |bend: &str, hole: &str| {
+            let polygons = [
+                "POLYGON((0 0,10 0,10 1,0 1,0 0))".to_string(),
+                format!("POLYGON((0 1,10 1,10 2,{bend},0 2,0 1))"),
+                format!("POLYGON((0 2,{bend},10 2,10 10,0 10,0 2),({hole}))"),
+                format!("POLYGON(({hole}))"),
+                "POLYGON((0 10,10 10,10 11,0 11,0 10))".to_string(),
+            ];
+            polygons
+                .iter()
+                .map(|wkt| Polygon::<f64>::try_from_wkt_str(wkt).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // straightening the bend would cut through the small polygon
+        let coverage = stack("5 1.5", "4.5 1.8,5.5 1.8,5 2.2,4.5 1.8");
+        assert_eq!(coverage.simplify_coverage(0.9), coverage);
+        // or move to the other side of it
+        let coverage = stack("5 1.2", "4.5 1.6,5.5 1.6,5 1.8,4.5 1.6");
+        assert_eq!(coverage.simplify_coverage(0.9), coverage);
+
+        let coverage = stack("5 1.5", "4.5 2.5,5.5 2.5,5 2.8,4.5 2.5");
+        let simplified = coverage.simplify_coverage(0.9);
+        assert_eq!(
+            simplified[1],
+            wkt!(POLYGON((0. 1.,10. 1.,10. 2.,0. 2.,0. 1.)))
+        );
+        assert_is_coverage(&simplified);
+    }
+
+    #[test]
+    fn non_positive_epsilon() {
+        let coverage = [wkt!(POLYGON((0. 0.,5. 0.,5.1 1.,5. 2.,0. 2.,0. 0.)))];
+        assert_eq!(coverage.simplify_coverage(0.).to_vec(), coverage.to_vec());
+    }
+}
@@ -0,0 +1,535 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rstar::{RTree, AABB};
+
+use crate::geometry::*;
+use crate::relate::geomgraph::index::Segment;
+use crate::relate::geomgraph::Edge;
+use crate::{
+    coord, BoundingRect, Distance, Euclidean, GeoFloat, GeometryCow, Intersects, LinesIter,
+    PreparedGeometry,
+};
+
+/// Test whether two geometries are within a given Euclidean distance of each other.
+///
+/// This is equivalent to `Euclidean.distance(a, b) <= distance`, but much cheaper: geometries
+/// whose bounding rectangles are further apart than `distance` are rejected straight away, and
+/// otherwise the search stops at the first pair of segments or points which are close enough,
+/// rather than computing the distance between every pair.
+///
+/// A [`PreparedGeometry`] uses the R-tree of segments it has already built, so testing many
+/// geometries against the same prepared geometry doesn't scan all of its segments each time.
+///
+/// Empty geometries, negative distances and `NaN` are never within distance.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, DistanceWithin, PreparedGeometry};
+///
+/// let field = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)));
+/// let well = wkt!(POINT(13. 4.));
+/// let road = wkt!(LINESTRING(-5. 20.,15. 20.));
+///
+/// assert!(field.is_within_distance(&well, 3.0));
+/// assert!(!field.is_within_distance(&road, 9.0));
+/// assert!(road.is_within_distance(&field, 10.0));
+///
+/// // test many geometries against the same field
+/// let prepared_field = PreparedGeometry::from(&field);
+/// assert!(prepared_field.is_within_distance(&well, 3.0));
+/// assert!(!prepared_field.is_within_distance(&road, 9.0));
+/// ```
+pub trait DistanceWithin<Rhs = Self, F: GeoFloat = f64> {
+    fn is_within_distance(&self, rhs: &Rhs, distance: F) -> bool;
+}
+
+impl<F, Lhs, Rhs> DistanceWithin<Rhs, F> for Lhs
+where
+    F: GeoFloat,
+    Lhs: private::Facets<F>,
+    Rhs: private::Facets<F>,
+{
+    fn is_within_distance(&self, rhs: &Rhs, distance: F) -> bool {
+        if distance.is_nan() || distance < F::zero() {
+            return false;
+        }
+        let (Some(lhs_rect), Some(rhs_rect)) = (self.envelope(), rhs.envelope()) else {
+            return false;
+        };
+        if rect_gap(lhs_rect, rhs_rect) > distance {
+            return false;
+        }
+        if self.segment_index().is_none() && rhs.segment_index().is_some() {
+            is_within_distance(rhs, self, distance)
+        } else {
+            is_within_distance(self, rhs, distance)
+        }
+    }
+}
+
+pub(crate) mod private {
+    use super::*;
+
+    /// The parts of a geometry which the distance to another geometry is measured from, which
+    /// [`nearest_points`](crate::nearest_points) is also built upon.
+    pub trait Facets<F: GeoFloat> {
+        fn envelope(&self) -> Option<Rect<F>>;
+
+        fn facets<'a>(&'a self, facets: &mut Collected<'a, F>);
+
+        /// An index of the segments of the geometry, if it has already been built.
+        fn segment_index(&self) -> Option<SegmentIndex<'_, F>> {
+            None
+        }
+    }
+
+    pub struct Collected<'a, F: GeoFloat> {
+        /// Whether to collect the segments, which isn't needed if they are indexed
+        pub(crate) with_segments: bool,
+        pub(crate) segments: Vec<Line<F>>,
+        pub(crate) points: Vec<Coord<F>>,
+        pub(crate) polygons: Vec<Cow<'a, Polygon<F>>>,
+        /// A coordinate of every point, line and polygon, to test whether it lies inside one of
+        /// the polygons of the other geometry
+        pub(crate) representatives: Vec<Coord<F>>,
+    }
+
+    pub struct SegmentIndex<'a, F: GeoFloat> {
+        pub(super) tree: Rc<RTree<Segment<F>>>,
+        pub(super) edges: &'a [Rc<RefCell<Edge<F>>>],
+    }
+}
+
+use private::{Collected, SegmentIndex};
+
+impl<'a, F: GeoFloat> Collected<'a, F> {
+    pub(super) fn new(with_segments: bool) -> Self {
+        Self {
+            with_segments,
+            segments: vec![],
+            points: vec![],
+            polygons: vec![],
+            representatives: vec![],
+        }
+    }
+
+    fn add_point(&mut self, point: Coord<F>) {
+        self.points.push(point);
+        self.representatives.push(point);
+    }
+
+    fn add_line_string(&mut self, line_string: &LineString<F>) {
+        let Some(first) = line_string.0.first() else {
+            return;
+        };
+        self.representatives.push(*first);
+        if line_string.0.len() == 1 {
+            self.points.push(*first);
+        } else if self.with_segments {
+            self.segments.extend(line_string.lines());
+        }
+    }
+
+    fn add_polygon(&mut self, polygon: Cow<'a, Polygon<F>>) {
+        let Some(first) = polygon.exterior().0.first() else {
+            return;
+        };
+        // a ring of a single coordinate has no segments, and no interior to lie in
+        if polygon.exterior().0.len() == 1 {
+            self.add_point(*first);
+            return;
+        }
+        self.representatives.push(*first);
+        if self.with_segments {
+            self.segments.extend(polygon.lines_iter());
+        }
+        self.polygons.push(polygon);
+    }
+
+    /// The segments and points, the latter as degenerate segments.
+    pub(super) fn elements(&self) -> impl Iterator<Item = Line<F>> + '_ {
+        self.segments
+            .iter()
+            .copied()
+            .chain(self.points.iter().map(|point| Line::new(*point, *point)))
+    }
+}
+
+impl<F: GeoFloat> SegmentIndex<'_, F> {
+    /// Whether any indexed segment is within `distance` of `line`.
+    fn any_within(&self, line: &Line<F>, distance: F) -> bool {
+        let rect = expanded_envelope(line, distance);
+        self.tree
+            .locate_in_envelope_intersecting(&AABB::from_corners(rect.min(), rect.max()))
+            .any(|segment| {
+                let edge = RefCell::borrow(&self.edges[segment.edge_idx]);
+                let coords = &edge.coords()[segment.segment_idx..];
+                Euclidean.distance(&Line::new(coords[0], coords[1]), line) <= distance
+            })
+    }
+}
+
+fn is_within_distance<F: GeoFloat>(
+    lhs: &impl private::Facets<F>,
+    rhs: &impl private::Facets<F>,
+    distance: F,
+) -> bool {
+    let index = lhs.segment_index();
+    let mut lhs_facets = Collected::new(index.is_none());
+    lhs.facets(&mut lhs_facets);
+    let mut rhs_facets = Collected::new(true);
+    rhs.facets(&mut rhs_facets);
+
+    let rhs_elements: Vec<_> = rhs_facets.elements().collect();
+    if let Some(index) = index {
+        if rhs_elements
+            .iter()
+            .any(|element| index.any_within(element, distance))
+        {
+            return true;
+        }
+    }
+    let lhs_elements: Vec<_> = lhs_facets.elements().collect();
+    if any_pair_within(&lhs_elements, &rhs_elements, distance) {
+        return true;
+    }
+
+    // No part of the boundaries is close, so the only way left for the geometries to be within
+    // distance is that one of them lies inside a polygon of the other.
+    is_inside_any(&rhs_facets.representatives, &lhs_facets.polygons)
+        || is_inside_any(&lhs_facets.representatives, &rhs_facets.polygons)
+}
+
+/// Below this many pairs of segments, building an index doesn't pay off.
+pub(super) const BRUTE_FORCE_PAIRS: usize = 64;
+
+/// Whether any pair of segments is within `distance`, stopping at the first such pair.
+fn any_pair_within<F: GeoFloat>(lhs: &[Line<F>], rhs: &[Line<F>], distance: F) -> bool {
+    let is_within = |a: &Line<F>, b: &Line<F>| Euclidean.distance(a, b) <= distance;
+    if lhs.len() * rhs.len() <= BRUTE_FORCE_PAIRS {
+        return lhs.iter().any(|a| rhs.iter().any(|b| is_within(a, b)));
+    }
+    let (queries, indexed) = if lhs.len() < rhs.len() {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    let tree = RTree::bulk_load(indexed.to_vec());
+    queries.iter().any(|query| {
+        let rect = expanded_envelope(query, distance);
+        tree.locate_in_envelope_intersecting(&AABB::from_corners(
+            rect.min().into(),
+            rect.max().into(),
+        ))
+        .any(|candidate| is_within(query, candidate))
+    })
+}
+
+fn is_inside_any<F: GeoFloat>(coords: &[Coord<F>], polygons: &[Cow<Polygon<F>>]) -> bool {
+    polygons.iter().any(|polygon| {
+        let Some(rect) = polygon.bounding_rect() else {
+            return false;
+        };
+        coords
+            .iter()
+            .any(|coord| rect.intersects(coord) && polygon.intersects(coord))
+    })
+}
+
+/// The bounding rectangle of `line`, grown by `distance` on every side.
+pub(super) fn expanded_envelope<F: GeoFloat>(line: &Line<F>, distance: F) -> Rect<F> {
+    let rect = line.bounding_rect();
+    let offset = coord! { x: distance, y: distance };
+    Rect::new(rect.min() - offset, rect.max() + offset)
+}
+
+/// The distance between two rectangles, zero if they intersect.
+pub(super) fn rect_gap<F: GeoFloat>(a: Rect<F>, b: Rect<F>) -> F {
+    let gap =
+        |a_min: F, a_max: F, b_min: F, b_max: F| (b_min - a_max).max(a_min - b_max).max(F::zero());
+    let dx = gap(a.min().x, a.max().x, b.min().x, b.max().x);
+    let dy = gap(a.min().y, a.max().y, b.min().y, b.max().y);
+    dx.hypot(dy)
+}
+
+// ┌─────────────────────────────┐
+// │ Implementations of Facets   │
+// └─────────────────────────────┘
+
+impl<F: GeoFloat> private::Facets<F> for Point<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        Some(self.bounding_rect())
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        facets.add_point(self.0);
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for MultiPoint<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        for point in self {
+            facets.add_point(point.0);
+        }
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for Line<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        Some(self.bounding_rect())
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        facets.representatives.push(self.start);
+        if facets.with_segments {
+            facets.segments.push(*self);
+        }
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for LineString<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        facets.add_line_string(self);
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for MultiLineString<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        for line_string in self {
+            facets.add_line_string(line_string);
+        }
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for Polygon<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        facets.add_polygon(Cow::Borrowed(self));
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for MultiPolygon<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        for polygon in self {
+            facets.add_polygon(Cow::Borrowed(polygon));
+        }
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for Rect<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        Some(*self)
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        facets.add_polygon(Cow::Owned(self.to_polygon()));
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for Triangle<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        Some(self.bounding_rect())
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        facets.add_polygon(Cow::Owned(self.to_polygon()));
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for GeometryCollection<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        for geometry in self {
+            geometry.facets(facets);
+        }
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for Geometry<F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        match self {
+            Geometry::Point(g) => g.facets(facets),
+            Geometry::Line(g) => g.facets(facets),
+            Geometry::LineString(g) => g.facets(facets),
+            Geometry::Polygon(g) => g.facets(facets),
+            Geometry::MultiPoint(g) => g.facets(facets),
+            Geometry::MultiLineString(g) => g.facets(facets),
+            Geometry::MultiPolygon(g) => g.facets(facets),
+            Geometry::GeometryCollection(g) => g.facets(facets),
+            Geometry::Rect(g) => g.facets(facets),
+            Geometry::Triangle(g) => g.facets(facets),
+        }
+    }
+}
+
+impl<F: GeoFloat> private::Facets<F> for GeometryCow<'_, F> {
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect()
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        match self {
+            GeometryCow::Point(g) => g.as_ref().facets(facets),
+            GeometryCow::Line(g) => g.as_ref().facets(facets),
+            GeometryCow::LineString(g) => g.as_ref().facets(facets),
+            GeometryCow::Polygon(g) => g.as_ref().facets(facets),
+            GeometryCow::MultiPoint(g) => g.as_ref().facets(facets),
+            GeometryCow::MultiLineString(g) => g.as_ref().facets(facets),
+            GeometryCow::MultiPolygon(g) => g.as_ref().facets(facets),
+            GeometryCow::GeometryCollection(g) => g.as_ref().facets(facets),
+            GeometryCow::Rect(g) => g.as_ref().facets(facets),
+            GeometryCow::Triangle(g) => g.as_ref().facets(facets),
+        }
+    }
+}
+
+impl<'p, G, F> private::Facets<F> for PreparedGeometry<'p, G, F>
+where
+    F: GeoFloat,
+    G: Into<GeometryCow<'p, F>>,
+{
+    fn envelope(&self) -> Option<Rect<F>> {
+        self.bounding_rect
+    }
+
+    fn facets<'a>(&'a self, facets: &mut Collected<'a, F>) {
+        self.geometry_graph.geometry().facets(facets);
+    }
+
+    fn segment_index(&self) -> Option<SegmentIndex<'_, F>> {
+        Some(SegmentIndex {
+            tree: self.geometry_graph.get_or_build_tree(),
+            edges: self.geometry_graph.edges(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt;
+
+    /// Check `is_within_distance` against the full distance, for both argument orders and with
+    /// the first geometry prepared.
+    fn check(a: &Geometry, b: &Geometry, distance: f64) -> bool {
+        let expected = Euclidean.distance(a, b) <= distance;
+        assert_eq!(a.is_within_distance(b, distance), expected);
+        assert_eq!(b.is_within_distance(a, distance), expected);
+        let prepared = PreparedGeometry::from(a);
+        assert_eq!(prepared.is_within_distance(b, distance), expected);
+        assert_eq!(b.is_within_distance(&prepared, distance), expected);
+        assert_eq!(
+            prepared.is_within_distance(&PreparedGeometry::from(b), distance),
+            expected
+        );
+        expected
+    }
+
+    #[test]
+    fn point_and_polygon() {
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.))).into();
+        let outside = wkt!(POINT(13. 4.)).into();
+        let inside = wkt!(POINT(5. 5.)).into();
+        assert!(check(&polygon, &outside, 3.));
+        assert!(!check(&polygon, &outside, 2.9));
+        assert!(check(&polygon, &inside, 0.));
+    }
+
+    #[test]
+    fn polygon_in_hole() {
+        let with_hole = wkt!(POLYGON(
+            (0. 0.,20. 0.,20. 20.,0. 20.,0. 0.),
+            (5. 5.,15. 5.,15. 15.,5. 15.,5. 5.)
+        ))
+        .into();
+        let in_hole = wkt!(POLYGON((8. 8.,12. 8.,12. 12.,8. 12.,8. 8.))).into();
+        assert!(!check(&with_hole, &in_hole, 2.9));
+        assert!(check(&with_hole, &in_hole, 3.));
+
+        let island = wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 2.,1. 1.))).into();
+        assert!(check(&with_hole, &island, 0.));
+    }
+
+    #[test]
+    fn lines() {
+        let a = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.)).into();
+        let b = wkt!(LINESTRING(0. 1.,9. 1.,9. 9.)).into();
+        assert!(check(&a, &b, 1.));
+        assert!(!check(&a, &b, 0.99));
+
+        let crossing = Line::new(coord! { x: 0., y: 5. }, coord! { x: 12., y: 5. }).into();
+        assert!(check(&a, &crossing, 0.));
+    }
+
+    #[test]
+    fn many_segments() {
+        // enough segments on both sides to use an index
+        let a = Geometry::LineString(
+            (0..100)
+                .map(|i| coord! { x: i as f64, y: (i % 2) as f64 })
+                .collect(),
+        );
+        let b = Geometry::LineString(
+            (0..100)
+                .map(|i| coord! { x: i as f64, y: 3.0 + (i % 3) as f64 })
+                .collect(),
+        );
+        assert!(check(&a, &b, 2.));
+        assert!(!check(&a, &b, 1.99));
+    }
+
+    #[test]
+    fn collections() {
+        let a = Geometry::GeometryCollection(
+            wkt!(GEOMETRYCOLLECTION(POINT(0. 0.),LINESTRING(10. 0.,10. 10.))),
+        );
+        let b = wkt!(MULTIPOINT(20. 20.,12. 5.)).into();
+        assert!(check(&a, &b, 2.));
+        assert!(!check(&a, &b, 1.9));
+    }
+
+    #[test]
+    fn empty_and_invalid_distances() {
+        let point = wkt!(POINT(0. 0.));
+        let empty: Polygon = wkt!(POLYGON EMPTY);
+        assert!(!point.is_within_distance(&empty, 100.));
+        assert!(!empty.is_within_distance(&point, 100.));
+        assert!(!point.is_within_distance(&point, -1.));
+        assert!(!point.is_within_distance(&point, f64::NAN));
+        assert!(point.is_within_distance(&point, 0.));
+    }
+
+    #[test]
+    fn jts_test_suite() {
+        jts_test_runner::assert_jts_tests_succeed("*WithinDistance*.xml");
+    }
+}
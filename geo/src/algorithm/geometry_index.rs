@@ -0,0 +1,445 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use rstar::{ParentNode, RTree, RTreeNode, RTreeObject, AABB};
+
+use super::distance_within::{private, rect_gap};
+use crate::geometry::*;
+use crate::{nearest_points, DistanceWithin, GeoFloat, Intersects, Relate};
+
+/// A spatial index over [`Geometry`] values, each tagged with a user supplied id.
+///
+/// The bounding rectangles of the geometries are bulk loaded into an R*-tree, which finds the
+/// candidates for a query. Each candidate is then refined with the exact predicate, using
+/// [`Intersects`], [`Relate`] or [`DistanceWithin`], so only geometries which actually satisfy the
+/// predicate are returned.
+///
+/// Empty geometries can be added, but never match any query.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, Geometry, GeometryIndex};
+///
+/// let index: GeometryIndex<&str> = [
+///     ("park", Geometry::from(wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.))))),
+///     ("river", wkt!(LINESTRING(0. 12.,20. 12.)).into()),
+///     ("well", wkt!(POINT(15. 5.)).into()),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let path = wkt!(LINESTRING(5. 5.,5. 15.));
+/// let crossed: Vec<_> = index.intersecting(&path).map(|(id, _)| *id).collect();
+/// assert_eq!(crossed.len(), 2);
+/// assert!(crossed.contains(&"park") && crossed.contains(&"river"));
+///
+/// let house = wkt!(POINT(14. 6.));
+/// let nearest: Vec<_> = index
+///     .nearest_neighbors(&house, 2)
+///     .into_iter()
+///     .map(|(id, _, distance)| (*id, distance))
+///     .collect();
+/// assert_eq!(nearest[0].0, "well");
+/// assert_eq!(nearest[1], ("park", 4.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeometryIndex<Id, F: GeoFloat = f64> {
+    items: Vec<(Id, Geometry<F>)>,
+    tree: RTree<Entry<F>>,
+}
+
+/// The bounding rectangle of an item of a [`GeometryIndex`], and its position in the items.
+#[derive(Debug, Clone)]
+struct Entry<F: GeoFloat> {
+    index: usize,
+    envelope: AABB<Coord<F>>,
+}
+
+impl<F: GeoFloat> RTreeObject for Entry<F> {
+    type Envelope = AABB<Coord<F>>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+impl<F: GeoFloat> Entry<F> {
+    fn new(index: usize, geometry: &Geometry<F>) -> Option<Self> {
+        let rect = private::Facets::envelope(geometry)?;
+        Some(Entry {
+            index,
+            envelope: AABB::from_corners(rect.min(), rect.max()),
+        })
+    }
+}
+
+impl<Id, F: GeoFloat> GeometryIndex<Id, F> {
+    /// Bulk load an index from pairs of ids and geometries.
+    pub fn new<G: Into<Geometry<F>>>(items: impl IntoIterator<Item = (Id, G)>) -> Self {
+        let items: Vec<(Id, Geometry<F>)> = items
+            .into_iter()
+            .map(|(id, geometry)| (id, geometry.into()))
+            .collect();
+        let entries = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, geometry))| Entry::new(index, geometry))
+            .collect();
+        Self {
+            items,
+            tree: RTree::bulk_load(entries),
+        }
+    }
+
+    /// Add a geometry to the index.
+    pub fn insert(&mut self, id: Id, geometry: impl Into<Geometry<F>>) {
+        let geometry = geometry.into();
+        if let Some(entry) = Entry::new(self.items.len(), &geometry) {
+            self.tree.insert(entry);
+        }
+        self.items.push((id, geometry));
+    }
+
+    /// The number of geometries in the index, including empty ones.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over the ids and geometries in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&Id, &Geometry<F>)> {
+        self.items.iter().map(|(id, geometry)| (id, geometry))
+    }
+
+    /// The geometries which intersect `query`.
+    pub fn intersecting<'a, G>(
+        &'a self,
+        query: &'a G,
+    ) -> impl Iterator<Item = (&'a Id, &'a Geometry<F>)> + 'a
+    where
+        G: private::Facets<F>,
+        Geometry<F>: Intersects<G>,
+    {
+        self.candidates(query, F::zero())
+            .filter(move |(_, geometry)| geometry.intersects(query))
+    }
+
+    /// The geometries which contain `query`.
+    pub fn containing<'a, G>(
+        &'a self,
+        query: &'a G,
+    ) -> impl Iterator<Item = (&'a Id, &'a Geometry<F>)> + 'a
+    where
+        G: private::Facets<F> + Relate<F>,
+    {
+        self.candidates(query, F::zero())
+            .filter(move |(_, geometry)| geometry.relate(query).is_contains())
+    }
+
+    /// The geometries which lie within `query`.
+    pub fn within<'a, G>(
+        &'a self,
+        query: &'a G,
+    ) -> impl Iterator<Item = (&'a Id, &'a Geometry<F>)> + 'a
+    where
+        G: private::Facets<F> + Relate<F>,
+    {
+        let envelope = query
+            .envelope()
+            .map(|rect| AABB::from_corners(rect.min(), rect.max()));
+        envelope
+            .into_iter()
+            .flat_map(move |envelope| self.tree.locate_in_envelope(&envelope))
+            .map(move |entry| self.item(entry))
+            .filter(move |(_, geometry)| geometry.relate(query).is_within())
+    }
+
+    /// The geometries which are within `distance` of `query`.
+    pub fn within_distance<'a, G>(
+        &'a self,
+        query: &'a G,
+        distance: F,
+    ) -> impl Iterator<Item = (&'a Id, &'a Geometry<F>)> + 'a
+    where
+        G: private::Facets<F>,
+    {
+        self.candidates(query, distance)
+            .filter(move |(_, geometry)| geometry.is_within_distance(query, distance))
+    }
+
+    /// The `k` geometries nearest to `query`, closest first, with their distances.
+    ///
+    /// The distances are the true Euclidean distances between the geometries, not between their
+    /// bounding rectangles. Geometries at the same distance are returned in no particular order.
+    /// Geometries which have no distance to `query`, such as empty ones, are skipped.
+    pub fn nearest_neighbors<G>(&self, query: &G, k: usize) -> Vec<(&Id, &Geometry<F>, F)>
+    where
+        G: private::Facets<F>,
+    {
+        let mut nearest = vec![];
+        let Some(query_rect) = query.envelope() else {
+            return nearest;
+        };
+
+        // Best-first search: the distance to the bounding rectangle of a node or item is a lower
+        // bound for the distance to any geometry inside it, so when an item with its exact
+        // distance comes out of the queue, nothing left in the queue can be nearer.
+        let mut queue = BinaryHeap::new();
+        queue.push(Queued {
+            distance: F::zero(),
+            node: QueuedNode::Parent(self.tree.root()),
+        });
+        while let Some(Queued { distance, node }) = queue.pop() {
+            if nearest.len() == k {
+                break;
+            }
+            match node {
+                QueuedNode::Parent(parent) => {
+                    for child in parent.children() {
+                        let (envelope, node) = match child {
+                            RTreeNode::Parent(parent) => {
+                                (parent.envelope(), QueuedNode::Parent(parent))
+                            }
+                            RTreeNode::Leaf(entry) => {
+                                (entry.envelope, QueuedNode::Leaf(entry.index))
+                            }
+                        };
+                        let envelope = Rect::new(envelope.lower(), envelope.upper());
+                        queue.push(Queued {
+                            distance: rect_gap(query_rect, envelope),
+                            node,
+                        });
+                    }
+                }
+                QueuedNode::Leaf(index) => {
+                    let (_, geometry) = &self.items[index];
+                    if let Some(points) = nearest_points(geometry, query) {
+                        queue.push(Queued {
+                            distance: points.distance,
+                            node: QueuedNode::Item(index),
+                        });
+                    }
+                }
+                QueuedNode::Item(index) => {
+                    let (id, geometry) = &self.items[index];
+                    nearest.push((id, geometry, distance));
+                }
+            }
+        }
+        nearest
+    }
+
+    /// The geometries whose bounding rectangles are within `distance` of that of `query`.
+    fn candidates<'a, G>(
+        &'a self,
+        query: &G,
+        distance: F,
+    ) -> impl Iterator<Item = (&'a Id, &'a Geometry<F>)> + 'a
+    where
+        G: private::Facets<F>,
+    {
+        let envelope = query.envelope().map(|rect| {
+            let offset = Coord::from((distance, distance));
+            AABB::from_corners(rect.min() - offset, rect.max() + offset)
+        });
+        envelope
+            .into_iter()
+            .flat_map(move |envelope| self.tree.locate_in_envelope_intersecting(&envelope))
+            .map(move |entry| self.item(entry))
+    }
+
+    fn item(&self, entry: &Entry<F>) -> (&Id, &Geometry<F>) {
+        let (id, geometry) = &self.items[entry.index];
+        (id, geometry)
+    }
+}
+
+impl<Id, F: GeoFloat, G: Into<Geometry<F>>> FromIterator<(Id, G)> for GeometryIndex<Id, F> {
+    fn from_iter<I: IntoIterator<Item = (Id, G)>>(items: I) -> Self {
+        Self::new(items)
+    }
+}
+
+/// A node of the R*-tree, or an item, in the queue of a nearest neighbor search.
+struct Queued<'a, F: GeoFloat> {
+    /// A lower bound of the distance, or the exact distance for an `Item`
+    distance: F,
+    node: QueuedNode<'a, F>,
+}
+
+enum QueuedNode<'a, F: GeoFloat> {
+    Parent(&'a ParentNode<Entry<F>>),
+    Leaf(usize),
+    Item(usize),
+}
+
+impl<F: GeoFloat> Queued<'_, F> {
+    /// Items come out before nodes at the same distance, so the search can stop earlier.
+    fn rank(&self) -> u8 {
+        match self.node {
+            QueuedNode::Item(_) => 0,
+            QueuedNode::Leaf(_) => 1,
+            QueuedNode::Parent(_) => 2,
+        }
+    }
+}
+
+impl<F: GeoFloat> PartialEq for Queued<'_, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: GeoFloat> Eq for Queued<'_, F> {}
+
+impl<F: GeoFloat> PartialOrd for Queued<'_, F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: GeoFloat> Ord for Queued<'_, F> {
+    /// Reversed, so that the max-heap `BinaryHeap` pops the nearest first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.rank().cmp(&self.rank()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Distance, Euclidean, PreparedGeometry};
+
+    fn ids<'a>(found: impl Iterator<Item = (&'a usize, &'a Geometry)>) -> Vec<usize> {
+        let mut ids: Vec<usize> = found.map(|(id, _)| *id).collect();
+        ids.sort();
+        ids
+    }
+
+    /// A grid of unit squares, with id `10 * x + y`.
+    fn grid() -> GeometryIndex<usize> {
+        GeometryIndex::new((0..10).flat_map(|x| {
+            (0..10).map(move |y| {
+                let (x, y) = (x as f64 * 2., y as f64 * 2.);
+                let square = Rect::new((x, y), (x + 1., y + 1.));
+                (10 * (x as usize / 2) + y as usize / 2, square)
+            })
+        }))
+    }
+
+    #[test]
+    fn intersecting() {
+        let index = grid();
+        assert_eq!(index.len(), 100);
+        // along the diagonal, touching the corners of the squares in between
+        let line = wkt!(LINESTRING(0.5 0.5,4.5 4.5));
+        assert_eq!(ids(index.intersecting(&line)), vec![0, 11, 22]);
+        // the bounding rect overlaps squares which the triangle misses
+        let triangle = wkt!(POLYGON((0.5 0.5,4.5 0.5,0.5 4.5,0.5 0.5)));
+        assert_eq!(
+            ids(index.intersecting(&triangle)),
+            vec![0, 1, 2, 10, 11, 20]
+        );
+    }
+
+    #[test]
+    fn containing_and_within() {
+        let index = grid();
+        let point = wkt!(POINT(2.5 4.5));
+        assert_eq!(ids(index.containing(&point)), vec![12]);
+        let gap = wkt!(POINT(1.5 1.5));
+        assert!(ids(index.containing(&gap)).is_empty());
+
+        let area = wkt!(POLYGON((0. 0.,3. 0.,3. 3.,0. 3.,0. 0.)));
+        assert_eq!(ids(index.within(&area)), vec![0, 1, 10, 11]);
+        let prepared = PreparedGeometry::from(&area);
+        assert_eq!(ids(index.within(&prepared)), vec![0, 1, 10, 11]);
+    }
+
+    #[test]
+    fn within_distance() {
+        let index = grid();
+        let point = wkt!(POINT(1.5 1.5));
+        assert_eq!(ids(index.within_distance(&point, 0.5)), vec![]);
+        assert_eq!(ids(index.within_distance(&point, 0.75)), vec![0, 1, 10, 11]);
+    }
+
+    #[test]
+    fn nearest_neighbors() {
+        let index: GeometryIndex<usize> = [
+            (0, Geometry::from(wkt!(LINESTRING(0. 10.,10. 10.)))),
+            (1, wkt!(POINT(3. 3.)).into()),
+            (2, wkt!(POLYGON((5. 0.,9. 0.,9. 4.,5. 4.,5. 0.))).into()),
+            (3, wkt!(MULTIPOINT EMPTY).into()),
+        ]
+        .into_iter()
+        .collect();
+        // the point is nearest by envelope distance, but not by true distance
+        let query = wkt!(LINESTRING(4. 9.,4. 6.));
+        let nearest = index.nearest_neighbors(&query, 10);
+        let found: Vec<_> = nearest.iter().map(|(id, _, _)| **id).collect();
+        assert_eq!(found, vec![0, 2, 1]);
+        for (_, geometry, distance) in nearest {
+            assert_relative_eq!(
+                distance,
+                Euclidean.distance(geometry, &Geometry::from(query.clone()))
+            );
+        }
+        assert_eq!(index.nearest_neighbors(&query, 1).len(), 1);
+        assert!(index.nearest_neighbors(&query, 0).is_empty());
+    }
+
+    #[test]
+    fn nearest_neighbors_agree_with_brute_force() {
+        let index = grid();
+        let query = wkt!(LINESTRING(3.3 7.9,12.2 4.1));
+        let nearest = index.nearest_neighbors(&query, 7);
+        let mut distances: Vec<f64> = index
+            .iter()
+            .map(|(_, geometry)| Euclidean.distance(geometry, &Geometry::from(query.clone())))
+            .collect();
+        distances.sort_by(f64::total_cmp);
+        for ((_, _, distance), expected) in nearest.iter().zip(&distances) {
+            assert_relative_eq!(*distance, *expected, epsilon = 1e-12);
+        }
+        assert_eq!(nearest.len(), 7);
+    }
+
+    #[test]
+    fn nearest_neighbors_with_degenerate_items() {
+        let degenerate_ring = Polygon::new(LineString::new(vec![Coord { x: 1., y: 1. }]), vec![]);
+        let index: GeometryIndex<usize> = [
+            (0, Geometry::from(wkt!(POINT(5. 5.)))),
+            (1, degenerate_ring.into()),
+            (2, LineString::new(vec![Coord { x: 3., y: 3. }]).into()),
+            (3, wkt!(POLYGON((8. 8.,9. 8.,9. 9.,8. 9.,8. 8.))).into()),
+        ]
+        .into_iter()
+        .collect();
+        let query = wkt!(POINT(0. 0.));
+        let nearest = index.nearest_neighbors(&query, 10);
+        let found: Vec<_> = nearest.iter().map(|(id, _, _)| **id).collect();
+        assert_eq!(found, vec![1, 2, 0, 3]);
+        assert_relative_eq!(nearest[0].2, 2f64.sqrt());
+    }
+
+    #[test]
+    fn insert() {
+        let mut index = GeometryIndex::new(Vec::<(&str, Point)>::new());
+        assert!(index.is_empty());
+        index.insert("a", wkt!(POINT(1. 1.)));
+        index.insert("empty", wkt!(LINESTRING EMPTY));
+        index.insert("b", wkt!(POINT(2. 2.)));
+        assert_eq!(index.len(), 3);
+        let area = wkt!(POLYGON((0. 0.,1.5 0.,1.5 1.5,0. 1.5,0. 0.)));
+        let found: Vec<_> = index.intersecting(&area).map(|(id, _)| *id).collect();
+        assert_eq!(found, vec!["a"]);
+    }
+}
@@ -0,0 +1,573 @@
+use super::{Bearing, Destination, Distance, InterpolatePoint};
+use crate::algorithm::buffer::{BufferParts, BufferStyle, PieceBuilder, Side};
+use crate::bool_ops::{unary_difference, unary_union, BoolOpsNum, BooleanOps};
+use crate::coordinate_position::{CoordPos, CoordinatePosition};
+use crate::geometry::*;
+use crate::orient::{Direction, Orient};
+use crate::winding_order::Winding;
+use crate::{BoundingRect, GeoFloat, MapCoords};
+
+/// Buffer a geometry by a distance in a [metric space](super::metric_spaces), such as
+/// [`Haversine`](super::Haversine) or [`Geodesic`](super::Geodesic).
+///
+/// The geometry's coordinates are lon/lat degrees, the distance is in the units of the metric
+/// space (e.g. meters) and the result is a [`MultiPolygon`] in lon/lat degrees. See
+/// [`Buffer`](crate::Buffer) for buffering in the Euclidean plane, and for the meaning of the
+/// [`BufferStyle`] parameters.
+///
+/// Segments of the input are interpreted as the lines of the metric space (e.g. geodesics), and
+/// all points of the buffer boundary are computed with the metric space's [`Destination`], so that
+/// e.g. a buffered point is a true geodesic circle. Buffers which cross the antimeridian are split
+/// into polygons on either side of it, and buffers which contain a pole reach up to ±90° latitude
+/// along the whole antimeridian.
+///
+/// The buffer must not reach around the globe to the antipode of the center of the geometry.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, Contains, Geodesic, MetricBuffer};
+///
+/// let zurich = wkt!(POINT(8.54 47.37));
+/// let circle = Geodesic.buffer(&zurich, 5_000.0);
+/// assert!(circle.contains(&wkt!(POINT(8.57 47.39))));
+/// assert!(!circle.contains(&wkt!(POINT(8.7 47.4))));
+///
+/// // Close to the antimeridian, the buffer is split in two.
+/// let fiji = wkt!(POINT(179.99 -17.0));
+/// assert_eq!(Geodesic.buffer(&fiji, 5_000.0).0.len(), 2);
+/// ```
+pub trait MetricBuffer<F: BoolOpsNum + GeoFloat> {
+    /// Buffer the geometry by `distance`, with round caps and round joins.
+    fn buffer(&self, geometry: &(impl Clone + Into<Geometry<F>>), distance: F) -> MultiPolygon<F> {
+        self.buffer_with_style(geometry, BufferStyle::new(distance))
+    }
+
+    /// Buffer the geometry using the given [`BufferStyle`].
+    fn buffer_with_style(
+        &self,
+        geometry: &(impl Clone + Into<Geometry<F>>),
+        style: BufferStyle<F>,
+    ) -> MultiPolygon<F>;
+}
+
+impl<F, MetricSpace> MetricBuffer<F> for MetricSpace
+where
+    F: BoolOpsNum + GeoFloat,
+    MetricSpace:
+        Bearing<F> + Destination<F> + Distance<F, Point<F>, Point<F>> + InterpolatePoint<F>,
+{
+    fn buffer_with_style(
+        &self,
+        geometry: &(impl Clone + Into<Geometry<F>>),
+        style: BufferStyle<F>,
+    ) -> MultiPolygon<F> {
+        let mut parts = BufferParts::default();
+        parts.add_geometry(&geometry.clone().into());
+        let Some(center) = spherical_center(&parts) else {
+            return MultiPolygon::new(vec![]);
+        };
+        MetricBufferBuilder {
+            metric_space: self,
+            style: &style,
+            center,
+            max_segment_length: self.distance(
+                Point::new(F::zero(), F::zero()),
+                Point::new(F::zero(), F::one()),
+            ),
+        }
+        .buffer(parts)
+    }
+}
+
+/// Builds the buffer in an azimuthal equidistant projection centered on the geometry, in which
+/// the pieces of the buffer are merged.
+///
+/// The pieces themselves are computed in lon/lat, so that their accuracy doesn't depend on the
+/// distortion of the projection, which is only used for the topology of the result.
+struct MetricBufferBuilder<'a, F: GeoFloat, MetricSpace> {
+    metric_space: &'a MetricSpace,
+    style: &'a BufferStyle<F>,
+    center: Point<F>,
+    /// Lines of the metric space are approximated by segments of at most this length (1° of
+    /// latitude) in the projection.
+    max_segment_length: F,
+}
+
+impl<F, MetricSpace> MetricBufferBuilder<'_, F, MetricSpace>
+where
+    F: BoolOpsNum + GeoFloat,
+    MetricSpace:
+        Bearing<F> + Destination<F> + Distance<F, Point<F>, Point<F>> + InterpolatePoint<F>,
+{
+    fn buffer(&self, parts: BufferParts<F>) -> MultiPolygon<F> {
+        let distance = self.style.distance;
+        let polygons: Vec<Polygon<F>> = parts
+            .polygons
+            .iter()
+            .map(|polygon| {
+                let mut rings = polygon.rings().map(|ring| self.project_ring(&ring.0));
+                let exterior = rings.next().unwrap();
+                Polygon::new(exterior, rings.collect())
+            })
+            .collect();
+        let areal = if polygons.is_empty() {
+            MultiPolygon::new(vec![])
+        } else {
+            unary_union(&polygons).orient(Direction::Default)
+        };
+        if distance.is_zero() {
+            return self.unproject(areal);
+        }
+
+        let mut pieces = vec![];
+        if distance < F::zero() {
+            for polygon in &areal {
+                for ring in polygon.rings() {
+                    let ring: Vec<Coord<F>> =
+                        ring.coords().map(|c| self.unproject_coord(*c)).collect();
+                    self.add_path(&mut pieces, &ring, true);
+                }
+            }
+            if pieces.is_empty() {
+                return self.unproject(areal);
+            }
+            return self.unproject(unary_difference(&areal.0, &pieces));
+        }
+
+        for polygon in &parts.polygons {
+            for ring in polygon.rings() {
+                self.add_path(&mut pieces, &ring.0, true);
+            }
+        }
+        for line in &parts.lines {
+            let closed = line.len() > 2 && line.first() == line.last();
+            self.add_path(&mut pieces, line, closed);
+            if !closed {
+                let n = line.len();
+                self.add_cap(&mut pieces, line[1], line[0]);
+                self.add_cap(&mut pieces, line[n - 2], line[n - 1]);
+            }
+        }
+        for point in &parts.points {
+            let mut local = PieceBuilder::new(self.style);
+            local.add_point(Coord::zero());
+            self.add_local_pieces(&mut pieces, *point, local);
+        }
+        pieces.extend(areal);
+        if pieces.is_empty() {
+            return MultiPolygon::new(vec![]);
+        }
+        self.unproject(unary_union(&pieces))
+    }
+
+    /// Convert the projected buffer back to lon/lat.
+    fn unproject(&self, projected: MultiPolygon<F>) -> MultiPolygon<F> {
+        let mut polygons = vec![];
+        for polygon in projected {
+            let exterior = self.unproject_ring(polygon.exterior());
+            let holes: Vec<Polygon<F>> = polygon
+                .interiors()
+                .iter()
+                .flat_map(|ring| self.unproject_ring(ring))
+                .collect();
+            if holes.is_empty() {
+                polygons.extend(exterior);
+            } else {
+                polygons.extend(unary_difference(&exterior.0, &holes));
+            }
+        }
+        MultiPolygon::new(polygons).orient(Direction::Default)
+    }
+
+    /// The region enclosed by a projected ring, in lon/lat with longitudes in [-180, 180].
+    fn unproject_ring(&self, ring: &LineString<F>) -> MultiPolygon<F> {
+        let half_turn = F::from(180.0).unwrap();
+        let quarter_turn = F::from(90.0).unwrap();
+        let Some(polygon) = self.unwrap_ring(ring) else {
+            return MultiPolygon::new(vec![]);
+        };
+        match polygon.bounding_rect() {
+            None => return MultiPolygon::new(vec![]),
+            Some(rect) if rect.min().x >= -half_turn && rect.max().x <= half_turn => {
+                return MultiPolygon::new(vec![polygon]);
+            }
+            Some(_) => {}
+        }
+        // Cut the region at the antimeridian, and shift the parts back into range. Parts on either
+        // end of the range can be adjacent if the region encloses a pole.
+        let mut parts = vec![];
+        for turns in [-1.0, 0.0, 1.0] {
+            let offset = F::from(turns * 360.0).unwrap();
+            let window = Rect::new(
+                Coord {
+                    x: offset - half_turn,
+                    y: -quarter_turn,
+                },
+                Coord {
+                    x: offset + half_turn,
+                    y: quarter_turn,
+                },
+            );
+            for part in polygon.intersection(&window.to_polygon()) {
+                parts.push(part.map_coords(|c| Coord {
+                    x: c.x - offset,
+                    y: c.y,
+                }));
+            }
+        }
+        unary_union(&parts).map_coords(clamp_to_range)
+    }
+}
+
+impl<F, MetricSpace> MetricBufferBuilder<'_, F, MetricSpace>
+where
+    F: GeoFloat,
+    MetricSpace:
+        Bearing<F> + Destination<F> + Distance<F, Point<F>, Point<F>> + InterpolatePoint<F>,
+{
+    /// Add the segments and joins of the buffer of both sides of a path.
+    fn add_path(&self, pieces: &mut Vec<Polygon<F>>, coords: &[Coord<F>], closed: bool) {
+        let mut coords = coords.to_vec();
+        coords.dedup();
+        for window in coords.windows(2) {
+            self.add_segment(pieces, window[0], window[1]);
+        }
+        for window in coords.windows(3) {
+            self.add_join(pieces, window[0], window[1], window[2]);
+        }
+        let n = coords.len();
+        if closed && n > 2 {
+            self.add_join(pieces, coords[n - 2], coords[0], coords[1]);
+        }
+    }
+
+    /// Add the region within the buffer distance of a segment, by offsetting points along it
+    /// perpendicularly to either side.
+    fn add_segment(&self, pieces: &mut Vec<Polygon<F>>, start: Coord<F>, end: Coord<F>) {
+        let ms = self.metric_space;
+        let d = self.style.distance.abs();
+        let ninety = F::from(90.0).unwrap();
+        let points: Vec<Point<F>> = ms
+            .points_along_line(start.into(), end.into(), self.max_segment_length, true)
+            .collect();
+        let offsets: Vec<(Coord<F>, Coord<F>, Coord<F>)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, &point)| {
+                let bearing = match points.get(i + 1) {
+                    Some(&next) => ms.bearing(point, next),
+                    None => reverse_bearing(ms.bearing(point, points[i - 1])),
+                };
+                let left = ms.destination(point, bearing - ninety, d);
+                let right = ms.destination(point, bearing + ninety, d);
+                (self.project(left), self.project(point), self.project(right))
+            })
+            .collect();
+        // The points on the line are part of the pieces, so that they share their edges exactly
+        // with the joins and caps, despite the line being curved in the projection.
+        for window in offsets.windows(2) {
+            let ((left_1, mid_1, right_1), (left_2, mid_2, right_2)) = (window[0], window[1]);
+            push_piece(pieces, vec![mid_1, right_1, right_2, mid_2, left_2, left_1]);
+        }
+    }
+
+    fn add_join(
+        &self,
+        pieces: &mut Vec<Polygon<F>>,
+        prev: Coord<F>,
+        vertex: Coord<F>,
+        next: Coord<F>,
+    ) {
+        let ms = self.metric_space;
+        let vertex_point = Point::from(vertex);
+        let incoming = direction(reverse_bearing(ms.bearing(vertex_point, prev.into())));
+        let outgoing = direction(ms.bearing(vertex_point, next.into()));
+        let mut local = PieceBuilder::new(self.style);
+        local.add_join(-incoming, Coord::zero(), outgoing, Side::Both);
+        self.add_local_pieces(pieces, vertex, local);
+    }
+
+    fn add_cap(&self, pieces: &mut Vec<Polygon<F>>, prev: Coord<F>, end: Coord<F>) {
+        let incoming = direction(reverse_bearing(
+            self.metric_space.bearing(end.into(), prev.into()),
+        ));
+        let mut local = PieceBuilder::new(self.style);
+        local.add_cap(-incoming, Coord::zero());
+        self.add_local_pieces(pieces, end, local);
+    }
+
+    /// Add pieces built in the azimuthal equidistant projection centered on `origin`, in which
+    /// distances from the origin are exact.
+    fn add_local_pieces(
+        &self,
+        pieces: &mut Vec<Polygon<F>>,
+        origin: Coord<F>,
+        local: PieceBuilder<F>,
+    ) {
+        let origin = Point::from(origin);
+        for piece in local.pieces {
+            let ring = piece
+                .exterior()
+                .coords()
+                .map(|c| self.project(self.inverse(origin, *c)))
+                .collect();
+            push_piece(pieces, ring);
+        }
+    }
+
+    /// Project lon/lat onto the plane centered on the geometry.
+    fn project(&self, point: Point<F>) -> Coord<F> {
+        self.forward(self.center, point)
+    }
+
+    fn unproject_coord(&self, coord: Coord<F>) -> Coord<F> {
+        self.inverse(self.center, coord).0
+    }
+
+    /// The azimuthal equidistant projection centered on `center`.
+    fn forward(&self, center: Point<F>, point: Point<F>) -> Coord<F> {
+        let distance = self.metric_space.distance(center, point);
+        if distance.is_zero() {
+            return Coord::zero();
+        }
+        let bearing = self.metric_space.bearing(center, point).to_radians();
+        Coord {
+            x: distance * bearing.sin(),
+            y: distance * bearing.cos(),
+        }
+    }
+
+    fn inverse(&self, center: Point<F>, coord: Coord<F>) -> Point<F> {
+        let distance = coord.x.hypot(coord.y);
+        if distance.is_zero() {
+            return center;
+        }
+        let bearing = coord.x.atan2(coord.y).to_degrees();
+        self.metric_space.destination(center, bearing, distance)
+    }
+
+    /// Project a ring, approximating its segments, which are lines of the metric space, by
+    /// shorter ones.
+    fn project_ring(&self, ring: &[Coord<F>]) -> LineString<F> {
+        let mut coords: Vec<Coord<F>> = ring
+            .windows(2)
+            .flat_map(|w| {
+                self.metric_space
+                    .points_along_line(w[0].into(), w[1].into(), self.max_segment_length, true)
+                    .skip(1)
+            })
+            .map(|point| self.project(point))
+            .collect();
+        if let Some(&last) = coords.last() {
+            coords.insert(0, last);
+        }
+        LineString::new(coords)
+    }
+
+    /// The region enclosed by a projected ring, in lon/lat, with the longitudes unwrapped so
+    /// that it is continuous across the antimeridian. They are in the range [-540, 540].
+    fn unwrap_ring(&self, ring: &LineString<F>) -> Option<Polygon<F>> {
+        let full_turn = F::from(360.0).unwrap();
+        let half_turn = F::from(180.0).unwrap();
+        let quarter_turn = F::from(90.0).unwrap();
+
+        let mut coords: Vec<Coord<F>> = vec![];
+        for coord in ring {
+            let mut c = self.unproject_coord(*coord);
+            if let Some(prev) = coords.last() {
+                let mut delta = (c.x - prev.x) % full_turn;
+                if delta > half_turn {
+                    delta = delta - full_turn;
+                } else if delta < -half_turn {
+                    delta = delta + full_turn;
+                }
+                c.x = prev.x + delta;
+            }
+            coords.push(c);
+        }
+        let (Some(&first), Some(&last)) = (coords.first(), coords.last()) else {
+            return None;
+        };
+
+        // A ring which goes all the way around the globe encloses a pole: close it along the
+        // line of latitude of that pole.
+        if (last.x - first.x).abs() > half_turn {
+            let north_pole = self.project(Point::new(F::zero(), quarter_turn));
+            let contains_north_pole = Polygon::new(ring.clone(), vec![])
+                .coordinate_position(&north_pole)
+                == CoordPos::Inside;
+            let pole = if contains_north_pole {
+                quarter_turn
+            } else {
+                -quarter_turn
+            };
+            coords.push(Coord { x: last.x, y: pole });
+            coords.push(Coord {
+                x: first.x,
+                y: pole,
+            });
+        }
+
+        Some(Polygon::new(LineString::new(coords), vec![]))
+    }
+}
+
+/// The center of the geometry on the sphere, i.e. the normalized mean of the unit vectors of its
+/// coordinates.
+fn spherical_center<F: GeoFloat>(parts: &BufferParts<F>) -> Option<Point<F>> {
+    let coords = parts
+        .polygons
+        .iter()
+        .flat_map(|polygon| polygon.exterior().coords())
+        .chain(parts.lines.iter().flatten())
+        .chain(&parts.points);
+    let mut first = None;
+    let (mut x, mut y, mut z) = (F::zero(), F::zero(), F::zero());
+    for coord in coords {
+        first.get_or_insert(*coord);
+        let (lon, lat) = (coord.x.to_radians(), coord.y.to_radians());
+        x = x + lat.cos() * lon.cos();
+        y = y + lat.cos() * lon.sin();
+        z = z + lat.sin();
+    }
+    let first = first?;
+    let norm = (x * x + y * y + z * z).sqrt();
+    if norm <= F::epsilon() {
+        // e.g. points spread evenly around the globe
+        return Some(first.into());
+    }
+    let lat = (z / norm).asin().to_degrees();
+    let lon = y.atan2(x).to_degrees();
+    Some(Point::new(lon, lat))
+}
+
+/// Undo the rounding of the clipping at the antimeridian.
+fn clamp_to_range<F: GeoFloat>(coord: Coord<F>) -> Coord<F> {
+    let half_turn = F::from(180.0).unwrap();
+    let quarter_turn = F::from(90.0).unwrap();
+    Coord {
+        x: coord.x.max(-half_turn).min(half_turn),
+        y: coord.y.max(-quarter_turn).min(quarter_turn),
+    }
+}
+
+/// The bearing in the opposite direction, e.g. the bearing at which a line arrives at its end,
+/// given the bearing from its end back to its start.
+fn reverse_bearing<F: GeoFloat>(bearing: F) -> F {
+    (bearing + F::from(180.0).unwrap()) % F::from(360.0).unwrap()
+}
+
+/// The unit vector pointing in the direction of `bearing`, with x to the east and y to the north.
+fn direction<F: GeoFloat>(bearing: F) -> Coord<F> {
+    let (sin, cos) = bearing.to_radians().sin_cos();
+    Coord { x: sin, y: cos }
+}
+
+fn push_piece<F: GeoFloat>(pieces: &mut Vec<Polygon<F>>, coords: Vec<Coord<F>>) {
+    let mut ring = LineString::new(coords);
+    ring.close();
+    ring.make_ccw_winding();
+    pieces.push(Polygon::new(ring, vec![]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::LineCap;
+    use crate::{wkt, Contains, Geodesic, GeodesicArea, Haversine, Intersects};
+
+    #[test]
+    fn point_is_a_geodesic_circle() {
+        let center = wkt!(POINT(8.54 47.37));
+        let circle = Geodesic.buffer(&center, 5_000.0);
+        assert_eq!(circle.0.len(), 1);
+        assert_eq!(circle.0[0].exterior().0.len(), 33);
+        for coord in circle.0[0].exterior() {
+            let distance = Geodesic.distance(center, Point::from(*coord));
+            assert_relative_eq!(distance, 5_000.0, epsilon = 1e-3);
+        }
+        // the area of the inscribed 32-gon
+        let expected = 16.0 * 5_000.0_f64.powi(2) * (std::f64::consts::TAU / 32.0).sin();
+        assert_relative_eq!(
+            circle.geodesic_area_unsigned(),
+            expected,
+            max_relative = 1e-3
+        );
+
+        let square = Haversine
+            .buffer_with_style(&center, BufferStyle::new(5_000.0).line_cap(LineCap::Square));
+        assert_eq!(square.0[0].exterior().0.len(), 5);
+        assert!(Haversine.buffer(&center, -1.0).0.is_empty());
+    }
+
+    #[test]
+    fn line_string() {
+        let line_string = wkt!(LINESTRING(0. 0.,1. 0.,1. 1.));
+        let buffered = Haversine.buffer(&line_string, 1_000.0);
+        assert_eq!(buffered.0.len(), 1);
+        // 1 km is about 0.009°
+        assert!(buffered.contains(&wkt!(POINT(0.5 0.0085))));
+        assert!(!buffered.contains(&wkt!(POINT(0.5 0.0095))));
+        assert!(buffered.contains(&wkt!(POINT(1.0085 0.5))));
+        assert!(!buffered.contains(&wkt!(POINT(1.0095 0.5))));
+        assert!(buffered.contains(&wkt!(POINT(-0.0085 0.))));
+        assert!(!buffered.contains(&wkt!(POINT(-0.0095 0.))));
+    }
+
+    #[test]
+    fn polygon() {
+        let square = wkt!(POLYGON((10. 50.,11. 50.,11. 51.,10. 51.,10. 50.)));
+        let area = square.geodesic_area_unsigned();
+
+        let grown = Geodesic.buffer(&square, 1_000.0);
+        assert_eq!(grown.0.len(), 1);
+        assert!(grown.contains(&square));
+        let perimeter = 4.0 * 100_000.0;
+        assert!(grown.geodesic_area_unsigned() > area + perimeter * 900.0);
+
+        let shrunk = Geodesic.buffer(&square, -1_000.0);
+        assert_eq!(shrunk.0.len(), 1);
+        assert!(square.contains(&shrunk));
+        assert!(shrunk.geodesic_area_unsigned() < area - perimeter * 900.0);
+
+        assert!(Geodesic.buffer(&square, -100_000.0).0.is_empty());
+    }
+
+    #[test]
+    fn antimeridian() {
+        let line_string = wkt!(LINESTRING(179.5 10.,-179.5 10.));
+        let buffered = Haversine.buffer(&line_string, 10_000.0);
+        assert_eq!(buffered.0.len(), 2);
+        for coord in buffered.0.iter().flat_map(|polygon| polygon.exterior()) {
+            assert!((-180.0..=180.0).contains(&coord.x));
+        }
+        assert!(buffered.contains(&wkt!(POINT(179.9 10.))));
+        assert!(buffered.contains(&wkt!(POINT(-179.9 10.))));
+        assert!(!buffered.intersects(&wkt!(POINT(0. 10.))));
+    }
+
+    #[test]
+    fn pole() {
+        let point = wkt!(POINT(30. 89.95));
+        let buffered = Geodesic.buffer(&point, 20_000.0);
+        assert_eq!(buffered.0.len(), 1);
+        for lon in [-179.0, -90.0, 0.0, 90.0, 179.0] {
+            assert!(buffered.contains(&Point::new(lon, 89.99)));
+        }
+        assert!(!buffered.intersects(&wkt!(POINT(30. 89.7))));
+
+        // a ring of great circle arcs around the south pole
+        let ring = LineString::from(vec![(0., -80.), (120., -80.), (-120., -80.), (0., -80.)]);
+        let band = Haversine.buffer(&ring, 10_000.0);
+        assert_eq!(band.0.len(), 1);
+        assert!(band.contains(&Point::new(120., -80.)));
+        assert!(band.contains(&Haversine.point_at_ratio_between(
+            Point::new(0., -80.),
+            Point::new(120., -80.),
+            0.5
+        )));
+        assert!(!band.intersects(&Point::new(60., -89.)));
+        assert!(!band.intersects(&Point::new(60., -75.)));
+    }
+}
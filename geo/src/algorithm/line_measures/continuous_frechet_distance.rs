@@ -0,0 +1,288 @@
+use super::{Distance, Euclidean, FrechetDistance};
+use crate::{Coord, GeoFloat, LineString};
+
+/// Determine the similarity between two `LineStrings` using the continuous [Fréchet distance].
+///
+/// Unlike the discrete [`FrechetDistance`], which only pairs up vertices, the continuous distance
+/// considers every point along both lines, so it doesn't overestimate the distance between lines
+/// which are sampled sparsely or at different rates. It's never larger than the discrete
+/// distance.
+///
+/// The decision procedure, [`is_frechet_distance_within`], is based on the free space diagram of
+/// [Alt and Godau (1995)] and runs in `O(n * m)` time for lines of `n` and `m` vertices. The
+/// distance itself is found by bisecting on the decision procedure, to the precision of `F`.
+///
+/// Empty lines have a distance of zero.
+///
+/// # Examples
+///
+/// ```
+/// use approx::assert_relative_eq;
+/// use geo::line_measures::{ContinuousFrechetDistance, FrechetDistance};
+/// use geo::{wkt, Euclidean};
+///
+/// let line_string_a = wkt!(LINESTRING(0. 0.,10. 0.));
+/// let line_string_b = wkt!(LINESTRING(0. 1.,5. 1.,10. 1.));
+///
+/// // the vertex in the middle of `line_string_b` is 5 units from the nearest vertex of
+/// // `line_string_a`, but only 1 unit from the line
+/// let distance = Euclidean.continuous_frechet_distance(&line_string_a, &line_string_b);
+/// assert_relative_eq!(distance, 1.);
+/// assert_relative_eq!(Euclidean.frechet_distance(&line_string_a, &line_string_b), 26f64.sqrt());
+///
+/// assert!(Euclidean.is_frechet_distance_within(&line_string_a, &line_string_b, 1.));
+/// assert!(!Euclidean.is_frechet_distance_within(&line_string_a, &line_string_b, 0.9));
+/// ```
+///
+/// [Fréchet distance]: https://en.wikipedia.org/wiki/Fr%C3%A9chet_distance
+/// [`is_frechet_distance_within`]: Self::is_frechet_distance_within
+/// [Alt and Godau (1995)]: https://doi.org/10.1142/S0218195995000064
+pub trait ContinuousFrechetDistance<F: GeoFloat> {
+    /// Returns the continuous Fréchet distance between two `LineString`s.
+    fn continuous_frechet_distance(&self, ls_1: &LineString<F>, ls_2: &LineString<F>) -> F;
+
+    /// Returns `true` if the continuous Fréchet distance between two `LineString`s is no more
+    /// than `epsilon`.
+    ///
+    /// This is faster than comparing [`continuous_frechet_distance`] with `epsilon`.
+    ///
+    /// [`continuous_frechet_distance`]: Self::continuous_frechet_distance
+    fn is_frechet_distance_within(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        epsilon: F,
+    ) -> bool;
+}
+
+impl<F: GeoFloat> ContinuousFrechetDistance<F> for Euclidean {
+    fn continuous_frechet_distance(&self, ls_1: &LineString<F>, ls_2: &LineString<F>) -> F {
+        let (a, b) = (&ls_1.0, &ls_2.0);
+        let (Some(&a_last), Some(&b_last)) = (a.last(), b.last()) else {
+            return F::zero();
+        };
+        if a.len() == 1 || b.len() == 1 {
+            return point_distance(a, b);
+        }
+
+        // the lines' endpoints are always paired up, and the discrete distance is an upper bound
+        let mut low = self.distance(a[0], b[0]).max(self.distance(a_last, b_last));
+        if free_space_path_exists(a, b, low) {
+            return low;
+        }
+        let mut high = self.frechet_distance(ls_1, ls_2);
+        loop {
+            let middle = (low + high) / (F::one() + F::one());
+            if middle <= low || middle >= high {
+                return high;
+            }
+            if free_space_path_exists(a, b, middle) {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+    }
+
+    fn is_frechet_distance_within(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        epsilon: F,
+    ) -> bool {
+        let (a, b) = (&ls_1.0, &ls_2.0);
+        let (Some(&a_last), Some(&b_last)) = (a.last(), b.last()) else {
+            return F::zero() <= epsilon;
+        };
+        if a.len() == 1 || b.len() == 1 {
+            return point_distance(a, b) <= epsilon;
+        }
+        self.distance(a[0], b[0]) <= epsilon
+            && self.distance(a_last, b_last) <= epsilon
+            && free_space_path_exists(a, b, epsilon)
+    }
+}
+
+/// The Fréchet distance when one of the lines is a single point, which is the distance to the
+/// furthest vertex of the other.
+fn point_distance<F: GeoFloat>(a: &[Coord<F>], b: &[Coord<F>]) -> F {
+    let (point, others) = if a.len() == 1 { (a[0], b) } else { (b[0], a) };
+    others.iter().fold(F::zero(), |furthest, &other| {
+        furthest.max(Euclidean.distance(point, other))
+    })
+}
+
+/// The part of a segment of the free space diagram, as parameters between 0 and 1, which is
+/// reachable by a monotone path from the start of both lines.
+#[derive(Debug, Clone, Copy)]
+struct Interval<F> {
+    start: F,
+    end: F,
+}
+
+impl<F: GeoFloat> Interval<F> {
+    /// The parameters of the points of the segment from `start` to `end` which are within
+    /// `epsilon` of `point`.
+    fn free(point: Coord<F>, start: Coord<F>, end: Coord<F>, epsilon: F) -> Option<Self> {
+        let direction = end - start;
+        let offset = start - point;
+        let a = direction.x * direction.x + direction.y * direction.y;
+        let b = direction.x * offset.x + direction.y * offset.y;
+        let c = offset.x * offset.x + offset.y * offset.y - epsilon * epsilon;
+        if a == F::zero() {
+            return (c <= F::zero()).then_some(Interval {
+                start: F::zero(),
+                end: F::one(),
+            });
+        }
+        let discriminant = b * b - a * c;
+        if discriminant < F::zero() {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (low, high) = ((-b - root) / a, (-b + root) / a);
+        if high < F::zero() || low > F::one() {
+            return None;
+        }
+        Some(Interval {
+            start: low.max(F::zero()),
+            end: high.min(F::one()),
+        })
+    }
+
+    /// The part of this free interval which can be reached from a reachable point at `from`.
+    fn from(self, from: F) -> Option<Self> {
+        (from <= self.end).then_some(Interval {
+            start: self.start.max(from),
+            end: self.end,
+        })
+    }
+}
+
+/// Whether there is a monotone path through the free space diagram of `a` and `b` for
+/// `epsilon`, from the start of both lines to the end of both lines.
+///
+/// Cell `(i, j)` pairs segment `i` of `a` with segment `j` of `b`. Its left edge pairs vertex
+/// `i` of `a` with segment `j` of `b`, and its bottom edge pairs segment `i` of `a` with vertex
+/// `j` of `b`. The reachable part of the right and top edges of each cell follows from the
+/// reachable part of its left and bottom edges.
+fn free_space_path_exists<F: GeoFloat>(a: &[Coord<F>], b: &[Coord<F>], epsilon: F) -> bool {
+    let (n, m) = (a.len() - 1, b.len() - 1);
+    if Euclidean.distance(a[0], b[0]) > epsilon || Euclidean.distance(a[n], b[m]) > epsilon {
+        return false;
+    }
+    let left_free = |i: usize, j: usize| Interval::free(a[i], b[j], b[j + 1], epsilon);
+    let bottom_free = |i: usize, j: usize| Interval::free(b[j], a[i], a[i + 1], epsilon);
+
+    // the reachable parts of the left edges of the current column of cells, and of the bottom
+    // edges of the cells in the current row
+    let mut left: Vec<Option<Interval<F>>> = Vec::with_capacity(m);
+    let mut reachable = true;
+    for j in 0..m {
+        let free = left_free(0, j).filter(|free| reachable && free.start == F::zero());
+        reachable = free.is_some_and(|free| free.end == F::one());
+        left.push(free);
+    }
+    let mut bottom: Vec<Option<Interval<F>>> = Vec::with_capacity(n);
+    let mut reachable = true;
+    for i in 0..n {
+        let free = bottom_free(i, 0).filter(|free| reachable && free.start == F::zero());
+        reachable = free.is_some_and(|free| free.end == F::one());
+        bottom.push(free);
+    }
+
+    for (i, column_top) in bottom.iter_mut().enumerate() {
+        // the bottom edge of cell (i, 0) is the first of this column
+        let mut below = *column_top;
+        for (j, left) in left.iter_mut().enumerate() {
+            let right = match (*left, below) {
+                (_, Some(_)) => left_free(i + 1, j),
+                (Some(reached), None) => {
+                    left_free(i + 1, j).and_then(|free| free.from(reached.start))
+                }
+                (None, None) => None,
+            };
+            let top = match (*left, below) {
+                (Some(_), _) => bottom_free(i, j + 1),
+                (None, Some(reached)) => {
+                    bottom_free(i, j + 1).and_then(|free| free.from(reached.start))
+                }
+                (None, None) => None,
+            };
+            *left = right;
+            below = top;
+        }
+        *column_top = below;
+    }
+
+    // the end of both lines is the top right corner of the last cell
+    left[m - 1].is_some_and(|reached| reached.end == F::one())
+        || bottom[n - 1].is_some_and(|reached| reached.end == F::one())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wkt;
+
+    #[test]
+    fn sparse_sampling() {
+        let ls_a = wkt!(LINESTRING(0. 0.,10. 0.));
+        let ls_b = wkt!(LINESTRING(0. 1.,1. 1.,2. 1.,3. 1.,7. 1.,10. 1.));
+        for distance in [
+            Euclidean.continuous_frechet_distance(&ls_a, &ls_b),
+            Euclidean.continuous_frechet_distance(&ls_b, &ls_a),
+        ] {
+            assert_relative_eq!(distance, 1., epsilon = 1e-12);
+        }
+        assert_relative_eq!(Euclidean.frechet_distance(&ls_a, &ls_b), 10f64.sqrt());
+    }
+
+    #[test]
+    fn backtracking() {
+        // `ls_b` doubles back on itself, so a leash must stretch back to the turning point
+        let ls_a = wkt!(LINESTRING(0. 0.,10. 0.));
+        let ls_b = wkt!(LINESTRING(0. 0.,6. 0.,4. 0.,10. 0.));
+        assert_relative_eq!(
+            Euclidean.continuous_frechet_distance(&ls_a, &ls_b),
+            1.,
+            epsilon = 1e-12
+        );
+        assert!(Euclidean.is_frechet_distance_within(&ls_a, &ls_b, 1.));
+        assert!(!Euclidean.is_frechet_distance_within(&ls_a, &ls_b, 0.99));
+    }
+
+    #[test]
+    fn endpoints() {
+        let ls_a = wkt!(LINESTRING(0. 0.,5. 0.,10. 0.));
+        let ls_b = wkt!(LINESTRING(10. 0.,0. 0.));
+        // the starts of the lines are paired, as are their ends
+        assert_relative_eq!(Euclidean.continuous_frechet_distance(&ls_a, &ls_b), 10.);
+        assert!(!Euclidean.is_frechet_distance_within(&ls_a, &ls_b, 9.));
+    }
+
+    #[test]
+    fn never_more_than_discrete() {
+        let ls_a = wkt!(LINESTRING(1. 1.,2. 1.,2. 2.,3. 3.));
+        let ls_b = wkt!(LINESTRING(2. 2.,0. 1.,2. 4.,3. 4.));
+        let continuous = Euclidean.continuous_frechet_distance(&ls_a, &ls_b);
+        let discrete = Euclidean.frechet_distance(&ls_a, &ls_b);
+        assert!(continuous <= discrete);
+        assert!(Euclidean.is_frechet_distance_within(&ls_a, &ls_b, continuous));
+        assert!(!Euclidean.is_frechet_distance_within(&ls_a, &ls_b, continuous * 0.999));
+    }
+
+    #[test]
+    fn degenerate() {
+        let empty = LineString::<f64>::new(vec![]);
+        let point = wkt!(LINESTRING(0. 1.));
+        let ls = wkt!(LINESTRING(0. 0.,3. 0.,0. 3.));
+        assert_eq!(Euclidean.continuous_frechet_distance(&empty, &ls), 0.);
+        assert_relative_eq!(
+            Euclidean.continuous_frechet_distance(&point, &ls),
+            10f64.sqrt()
+        );
+        assert!(Euclidean.is_frechet_distance_within(&ls, &point, 10f64.sqrt()));
+        assert_relative_eq!(Euclidean.continuous_frechet_distance(&ls, &ls), 0.);
+    }
+}
@@ -0,0 +1,422 @@
+//! Extract the part of a linear geometry (1-D) between two positions along it.
+
+use super::{InterpolatePoint, Length};
+use geo_types::{Coord, CoordFloat, Line, LineString, MultiLineString, Point};
+
+/// Extract the part of a `LineString` or `MultiLineString` between two positions along it, like
+/// PostGIS `ST_LineSubstring`.
+///
+/// This is the inverse of [`InterpolateLine`](super::InterpolateLine): the endpoints of the
+/// substring are the points interpolated at the start and end positions, and every vertex of the
+/// line in between is kept.
+///
+/// If the start position is after the end position, the substring runs in the opposite direction
+/// to the line. If they are equal, the substring is a degenerate `LineString` of two equal
+/// points.
+pub trait LineSubstring<F: CoordFloat>: InterpolatePoint<F> + Length<F> + Sized {
+    /// Returns the part of the line between two ratios of its total length.
+    ///
+    /// # Params
+    ///
+    /// - `line`: A `LineString` or `MultiLineString` which implements `SubstringableLine`.
+    /// - `start`, `end`: ratios of the total line length. They will be bounded between 0..1.
+    ///
+    /// # Example
+    /// ```
+    /// # use approx::assert_relative_eq;
+    /// use geo::algorithm::{Haversine, Euclidean, LineSubstring};
+    /// use geo::wkt;
+    ///
+    /// let line_string = wkt!(LINESTRING(0. 0.,0. 10.,10. 10.));
+    /// let substring = Euclidean.line_substring_by_ratio(&line_string, 0.25, 0.75).unwrap();
+    /// assert_relative_eq!(substring, wkt!(LINESTRING(0. 5.,0. 10.,5. 10.)));
+    ///
+    /// let substring = Haversine.line_substring_by_ratio(&line_string, 0.25, 0.5).unwrap();
+    /// assert_relative_eq!(substring, wkt!(LINESTRING(0. 4.961924877405399,0. 9.923849754810798)), epsilon=1e-14);
+    /// ```
+    fn line_substring_by_ratio<L: SubstringableLine<F>>(
+        &self,
+        line: &L,
+        start: F,
+        end: F,
+    ) -> L::Output {
+        line.substring_by_ratio(self, start, end)
+    }
+
+    /// Returns the part of the line between two distances from its start.
+    ///
+    /// # Params
+    ///
+    /// - `line`: A `LineString` or `MultiLineString` which implements `SubstringableLine`.
+    /// - `start`, `end`: How far down the line. The units of distance depend on the metric space.
+    ///   Distances will be clamped so that the substring will not be outside of `line`.
+    ///
+    /// # Example
+    /// ```
+    /// # use approx::assert_relative_eq;
+    /// use geo::algorithm::{Haversine, Euclidean, LineSubstring};
+    /// use geo::wkt;
+    ///
+    /// let line_string = wkt!(LINESTRING(0. 0.,0. 10.,10. 10.));
+    ///
+    /// // For Euclidean calculations, distance is in the same units as your points
+    /// let substring = Euclidean.line_substring_by_distance(&line_string, 9.5, 10.5).unwrap();
+    /// assert_relative_eq!(substring, wkt!(LINESTRING(0. 9.5,0. 10.,0.5 10.)));
+    ///
+    /// // For Haversine calculations, distance is in meters
+    /// let substring = Haversine.line_substring_by_distance(&line_string, 0.0, 100_000.0).unwrap();
+    /// assert_relative_eq!(substring, wkt!(LINESTRING(0. 0.,0. 0.899320363724538)), epsilon=1e-14);
+    /// ```
+    fn line_substring_by_distance<L: SubstringableLine<F>>(
+        &self,
+        line: &L,
+        start: F,
+        end: F,
+    ) -> L::Output {
+        line.substring_by_distance(self, start, end)
+    }
+}
+
+impl<F, MetricSpace> LineSubstring<F> for MetricSpace
+where
+    F: CoordFloat,
+    MetricSpace: InterpolatePoint<F> + Length<F> + Sized,
+{
+}
+
+/// A linear geometry (1-D) from which the part between two positions along it can be extracted.
+///
+/// It's typically more convenient to use the [`LineSubstring`] trait instead of this trait
+/// directly.
+pub trait SubstringableLine<F: CoordFloat> {
+    type Output;
+
+    /// Returns the part of the line between two ratios of its total length, which will be
+    /// bounded between 0..1.
+    ///
+    /// # Example
+    /// ```
+    /// # use approx::assert_relative_eq;
+    /// use geo::algorithm::line_measures::{Euclidean, SubstringableLine};
+    /// use geo::wkt;
+    ///
+    /// let line_string = wkt!(LINESTRING(0. 0.,0. 10.,10. 10.));
+    /// let substring = line_string.substring_by_ratio(&Euclidean, 0.75, 0.25).unwrap();
+    /// assert_relative_eq!(substring, wkt!(LINESTRING(5. 10.,0. 10.,0. 5.)));
+    /// ```
+    fn substring_by_ratio<MetricSpace: InterpolatePoint<F> + Length<F>>(
+        &self,
+        metric_space: &MetricSpace,
+        start: F,
+        end: F,
+    ) -> Self::Output;
+
+    /// Returns the part of the line between two distances from its start, which will be clamped
+    /// so that the substring will not be outside of the line. The units of distance depend on the
+    /// metric space.
+    ///
+    /// # Example
+    /// ```
+    /// # use approx::assert_relative_eq;
+    /// use geo::algorithm::line_measures::{Euclidean, SubstringableLine};
+    /// use geo::wkt;
+    ///
+    /// let line_string = wkt!(LINESTRING(0. 0.,0. 10.,10. 10.));
+    /// let substring = line_string.substring_by_distance(&Euclidean, 5., 20.).unwrap();
+    /// assert_relative_eq!(substring, wkt!(LINESTRING(0. 5.,0. 10.,10. 10.)));
+    /// ```
+    fn substring_by_distance<MetricSpace: InterpolatePoint<F> + Length<F>>(
+        &self,
+        metric_space: &MetricSpace,
+        start: F,
+        end: F,
+    ) -> Self::Output;
+}
+
+impl<F: CoordFloat> SubstringableLine<F> for LineString<F> {
+    type Output = Option<LineString<F>>;
+
+    fn substring_by_ratio<MetricSpace: InterpolatePoint<F> + Length<F>>(
+        &self,
+        metric_space: &MetricSpace,
+        start: F,
+        end: F,
+    ) -> Self::Output {
+        let length = metric_space.length(self);
+        self.substring_by_distance(metric_space, start * length, end * length)
+    }
+
+    fn substring_by_distance<MetricSpace: InterpolatePoint<F> + Length<F>>(
+        &self,
+        metric_space: &MetricSpace,
+        start: F,
+        end: F,
+    ) -> Self::Output {
+        if start <= end {
+            substring(metric_space, self, start, end)
+        } else {
+            let mut reversed = substring(metric_space, self, end, start)?;
+            reversed.0.reverse();
+            Some(reversed)
+        }
+    }
+}
+
+impl<F: CoordFloat> SubstringableLine<F> for MultiLineString<F> {
+    type Output = Option<MultiLineString<F>>;
+
+    fn substring_by_ratio<MetricSpace: InterpolatePoint<F> + Length<F>>(
+        &self,
+        metric_space: &MetricSpace,
+        start: F,
+        end: F,
+    ) -> Self::Output {
+        let length = metric_space.length(self);
+        self.substring_by_distance(metric_space, start * length, end * length)
+    }
+
+    /// The parts of the `MultiLineString` are measured one after the other, as if they were
+    /// joined end to end. The output has the substring of each part which overlaps the range.
+    fn substring_by_distance<MetricSpace: InterpolatePoint<F> + Length<F>>(
+        &self,
+        metric_space: &MetricSpace,
+        start: F,
+        end: F,
+    ) -> Self::Output {
+        let (from, to) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        let mut ranges = Vec::with_capacity(self.0.len());
+        let mut offset = F::zero();
+        for line_string in self {
+            let length = metric_space.length(line_string);
+            ranges.push((offset, offset + length));
+            offset = offset + length;
+        }
+
+        let mut parts: Vec<LineString<F>> = self
+            .iter()
+            .zip(&ranges)
+            .filter(|(_, &(part_start, part_end))| from < part_end && to > part_start)
+            .filter_map(|(line_string, &(part_start, _))| {
+                substring(
+                    metric_space,
+                    line_string,
+                    from - part_start,
+                    to - part_start,
+                )
+            })
+            .collect();
+        if parts.is_empty() {
+            // the range is a single position, or lies beyond the end
+            let index = ranges
+                .iter()
+                .position(|&(_, part_end)| from <= part_end)
+                .unwrap_or(ranges.len().checked_sub(1)?);
+            let part_start = ranges[index].0;
+            parts.extend(substring(
+                metric_space,
+                &self.0[index],
+                from - part_start,
+                to - part_start,
+            ));
+        }
+        if parts.is_empty() {
+            return None;
+        }
+
+        if start > end {
+            parts.reverse();
+            for part in &mut parts {
+                part.0.reverse();
+            }
+        }
+        Some(MultiLineString::new(parts))
+    }
+}
+
+/// The part of `line_string` between `from` and `to`, which must be in order. Returns `None` if
+/// the `line_string` is empty.
+fn substring<F: CoordFloat>(
+    metric_space: &(impl InterpolatePoint<F> + Length<F>),
+    line_string: &LineString<F>,
+    from: F,
+    to: F,
+) -> Option<LineString<F>> {
+    let mut coords: Vec<Coord<F>> = vec![];
+    let mut offset = F::zero();
+    for segment in line_string.lines() {
+        let length = metric_space.length(&segment);
+        let segment_end = offset + length;
+        if coords.is_empty() && from < segment_end {
+            coords.push(point_on_segment(metric_space, segment, from - offset, length).0);
+        }
+        if !coords.is_empty() {
+            if to <= segment_end {
+                coords.push(point_on_segment(metric_space, segment, to - offset, length).0);
+                return Some(LineString::new(coords));
+            }
+            coords.push(segment.end);
+        }
+        offset = segment_end;
+    }
+
+    // the range is beyond the end of the line, or the line has a single coordinate
+    if coords.is_empty() {
+        let last = *line_string.0.last()?;
+        coords = vec![last, last];
+    }
+    Some(LineString::new(coords))
+}
+
+/// The point `distance` along a `segment` of the given `length`, clamped to its ends.
+fn point_on_segment<F: CoordFloat>(
+    metric_space: &impl InterpolatePoint<F>,
+    segment: Line<F>,
+    distance: F,
+    length: F,
+) -> Point<F> {
+    if distance <= F::zero() {
+        segment.start_point()
+    } else if distance >= length {
+        segment.end_point()
+    } else {
+        metric_space.point_at_distance_between(segment.start_point(), segment.end_point(), distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::line_measures::{Euclidean, Geodesic, Haversine, Rhumb};
+    use crate::{wkt, InterpolateLine};
+
+    #[test]
+    fn line_string_by_ratio() {
+        let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.));
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_ratio(&line_string, 0.1, 0.9)
+                .unwrap(),
+            wkt!(LINESTRING(2. 0.,10. 0.,10. 8.))
+        );
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_ratio(&line_string, 0.1, 0.3)
+                .unwrap(),
+            wkt!(LINESTRING(2. 0.,6. 0.))
+        );
+        // clamped to the ends of the line
+        assert_eq!(
+            Euclidean
+                .line_substring_by_ratio(&line_string, -1., 2.)
+                .unwrap(),
+            line_string
+        );
+        // a vertex at the start of the range isn't repeated
+        assert_eq!(
+            Euclidean
+                .line_substring_by_ratio(&line_string, 0.5, 1.)
+                .unwrap(),
+            wkt!(LINESTRING(10. 0.,10. 10.))
+        );
+    }
+
+    #[test]
+    fn line_string_reversed_and_degenerate() {
+        let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.));
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_distance(&line_string, 15., 5.)
+                .unwrap(),
+            wkt!(LINESTRING(10. 5.,10. 0.,5. 0.))
+        );
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_distance(&line_string, 5., 5.)
+                .unwrap(),
+            wkt!(LINESTRING(5. 0.,5. 0.))
+        );
+        assert_eq!(
+            Euclidean
+                .line_substring_by_distance(&line_string, 30., 40.)
+                .unwrap(),
+            wkt!(LINESTRING(10. 10.,10. 10.))
+        );
+        assert!(Euclidean
+            .line_substring_by_ratio(&LineString::<f64>::new(vec![]), 0., 1.)
+            .is_none());
+        assert_eq!(
+            Euclidean
+                .line_substring_by_ratio(&wkt!(LINESTRING(1. 1.)), 0., 1.)
+                .unwrap(),
+            wkt!(LINESTRING(1. 1.,1. 1.))
+        );
+    }
+
+    #[test]
+    fn endpoints_match_interpolation() {
+        let line_string = wkt!(LINESTRING(10. 10.,20. 30.,40. 35.,45. 60.));
+        fn check(metric_space: &(impl InterpolatePoint<f64> + Length<f64>), line: &LineString) {
+            let substring = metric_space
+                .line_substring_by_ratio(line, 0.2, 0.7)
+                .unwrap();
+            let start = metric_space.point_at_ratio_from_start(line, 0.2).unwrap();
+            let end = metric_space.point_at_ratio_from_start(line, 0.7).unwrap();
+            assert_relative_eq!(substring.points().next().unwrap(), start, epsilon = 1e-9);
+            assert_relative_eq!(substring.points().next_back().unwrap(), end, epsilon = 1e-9);
+            assert_relative_eq!(
+                metric_space.length(&substring),
+                0.5 * metric_space.length(line),
+                max_relative = 1e-9
+            );
+        }
+        check(&Euclidean, &line_string);
+        check(&Haversine, &line_string);
+        check(&Geodesic, &line_string);
+        check(&Rhumb, &line_string);
+    }
+
+    #[test]
+    fn multi_line_string() {
+        let multi_line_string =
+            wkt!(MULTILINESTRING((0. 0.,10. 0.),(20. 0.,20. 10.),(30. 0.,40. 0.)));
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_distance(&multi_line_string, 5., 15.)
+                .unwrap(),
+            wkt!(MULTILINESTRING((5. 0.,10. 0.),(20. 0.,20. 5.)))
+        );
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_ratio(&multi_line_string, 0.5, 0.6)
+                .unwrap(),
+            wkt!(MULTILINESTRING((20. 5.,20. 8.)))
+        );
+        // a range ending where a part starts doesn't include that part
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_distance(&multi_line_string, 5., 10.)
+                .unwrap(),
+            wkt!(MULTILINESTRING((5. 0.,10. 0.)))
+        );
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_distance(&multi_line_string, 25., 10.)
+                .unwrap(),
+            wkt!(MULTILINESTRING((35. 0.,30. 0.),(20. 10.,20. 0.)))
+        );
+        assert_relative_eq!(
+            Euclidean
+                .line_substring_by_distance(&multi_line_string, 10., 10.)
+                .unwrap(),
+            wkt!(MULTILINESTRING((10. 0.,10. 0.)))
+        );
+        assert!(Euclidean
+            .line_substring_by_ratio(&MultiLineString::<f64>::new(vec![]), 0., 1.)
+            .is_none());
+    }
+}
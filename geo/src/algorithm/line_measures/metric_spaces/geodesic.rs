@@ -1,5 +1,6 @@
 use super::super::{Bearing, Destination, Distance, InterpolatePoint};
-use crate::Point;
+use super::point_distance::{point_distance_impl, PointSegmentDistance};
+use crate::geometry::*;
 use geographiclib_rs::{DirectGeodesic, InverseGeodesic};
 use std::sync::LazyLock;
 
@@ -85,6 +86,15 @@ pub struct GeodesicMeasure<F = fn() -> geographiclib_rs::Geodesic> {
 ///     132675.5018588206, // meters
 ///     Geodesic.distance(start, finish)
 /// );
+///
+/// // The distance from a point to a line or polygon is to its closest point.
+/// let road = wkt!(LINESTRING(23.319941 42.698334,24.742168 42.136097));
+/// let gps_fix = wkt!(POINT(24.0 42.5));
+/// assert_relative_eq!(
+///     6572.309513371585, // meters
+///     Geodesic.distance(&gps_fix, &road),
+///     epsilon = 1e-6
+/// );
 /// ```
 ///
 /// [geodesic lines]: https://en.wikipedia.org/wiki/Geodesics_on_an_ellipsoid
@@ -386,6 +396,54 @@ where
     }
 }
 
+/// The distance to the closest point of the segment along its [geodesic line], found with the
+/// iterative method of [Baselga and Martínez-Llario (2018)], which converges in a few steps.
+///
+/// [geodesic line]: https://en.wikipedia.org/wiki/Geodesics_on_an_ellipsoid
+/// [Baselga and Martínez-Llario (2018)]: https://doi.org/10.1007/s11200-017-1020-z
+impl<F> PointSegmentDistance<f64> for GeodesicMeasure<F>
+where
+    F: FnOnce() -> geographiclib_rs::Geodesic,
+{
+    fn point_segment_distance(&self, point: Point<f64>, segment: Line<f64>) -> f64 {
+        const MAX_ITERATIONS: usize = 32;
+        const TOLERANCE_METERS: f64 = 1e-6;
+
+        let (start, end) = (segment.start_point(), segment.end_point());
+        let to_ends = self.distance(point, start).min(self.distance(point, end));
+        let (length, azimuth, _, _): (f64, f64, f64, f64) =
+            self.geoid.inverse(start.y(), start.x(), end.y(), end.x());
+        if length == 0.0 {
+            return to_ends;
+        }
+
+        // the spherical along-track distance from the current point on the segment, using the
+        // equatorial radius, is a good estimate of how far the closest point is
+        let radius = self.geoid.a;
+        let mut along = 0.0;
+        let (mut closest, mut line_azimuth) = (start, azimuth);
+        for _ in 0..MAX_ITERATIONS {
+            let (distance, point_azimuth, _, _): (f64, f64, f64, f64) =
+                self.geoid
+                    .inverse(closest.y(), closest.x(), point.y(), point.x());
+            let angle = (point_azimuth - line_azimuth).to_radians();
+            let arc = distance / radius;
+            let step = radius * (arc.sin() * angle.cos()).atan2(arc.cos());
+            let next = (along + step).clamp(0.0, length);
+            if (next - along).abs() < TOLERANCE_METERS {
+                break;
+            }
+            along = next;
+            let (lat, lon, next_azimuth) = self.geoid.direct(start.y(), start.x(), azimuth, along);
+            closest = Point::new(lon, lat);
+            line_azimuth = next_azimuth;
+        }
+        self.distance(point, closest).min(to_ends)
+    }
+}
+
+point_distance_impl!([F: FnOnce() -> geographiclib_rs::Geodesic] GeodesicMeasure<F>, f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -535,4 +593,56 @@ mod tests {
 
         assert_relative_eq!(70684.36315529353, mars_geoid.distance(start, finish));
     }
+
+    mod distance_to_geometries {
+        use super::*;
+        use crate::wkt;
+
+        #[test]
+        fn point_to_line() {
+            let line = wkt!(LINESTRING(0. 0.,1. 0.));
+            // meridians meet the equator at right angles
+            let point = wkt!(POINT(0.5 1.));
+            let expected = Geodesic.distance(point, wkt!(POINT(0.5 0.)));
+            assert_relative_eq!(Geodesic.distance(&point, &line), expected, epsilon = 1e-6);
+            assert_relative_eq!(Geodesic.distance(&line, &point), expected, epsilon = 1e-6);
+
+            // beyond the end of the line
+            let point = wkt!(POINT(2. 1.));
+            let expected = Geodesic.distance(point, wkt!(POINT(1. 0.)));
+            assert_relative_eq!(Geodesic.distance(&point, &line), expected);
+        }
+
+        #[test]
+        fn point_to_line_matches_sampling() {
+            let line = Line::new(point!(x: 10., y: 50.), point!(x: 20., y: 55.));
+            for point in [point!(x: 15., y: 56.), point!(x: 12., y: 48.)] {
+                let sampled = (0..=10_000)
+                    .map(|i| {
+                        let along = Geodesic.point_at_ratio_between(
+                            line.start_point(),
+                            line.end_point(),
+                            i as f64 / 10_000.,
+                        );
+                        Geodesic.distance(point, along)
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                let distance = Geodesic.distance(&point, &line);
+                assert!(distance <= sampled);
+                assert_relative_eq!(distance, sampled, epsilon = 0.05);
+            }
+        }
+
+        #[test]
+        fn point_to_polygon() {
+            let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)));
+            assert_eq!(Geodesic.distance(&point!(x: 2., y: 2.), &polygon), 0.);
+
+            let multi_polygon = MultiPolygon::new(vec![polygon]);
+            let outside = point!(x: 11., y: 5.);
+            let geometry = Geometry::MultiPolygon(multi_polygon);
+            let expected = Geodesic.distance(&outside, &wkt!(LINESTRING(10. 0.,10. 10.)));
+            assert_relative_eq!(Geodesic.distance(&geometry, &outside), expected);
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use num_traits::FromPrimitive;
 
 use super::super::{Bearing, Destination, Distance, InterpolatePoint};
+use super::point_distance::{point_distance_impl, PointSegmentDistance};
+use crate::geometry::*;
 use crate::utils::normalize_longitude;
-use crate::{CoordFloat, Point};
+use crate::{Closest, CoordFloat, GeoFloat, HaversineClosestPoint};
 
 /// Use the [`Haversine`] constant (an instance of `HaversineMeasure`) rather than building your own
 /// customized [`HaversineMeasure`] for standard spherical Earth measurements.
@@ -89,6 +91,14 @@ impl Default for HaversineMeasure {
 ///     132433.09929460194,
 ///     Haversine.distance(start, finish)
 /// );
+///
+/// // The distance from a point to a line or polygon is to its closest point.
+/// let road = wkt!(LINESTRING(23.319941 42.698334,24.742168 42.136097));
+/// let gps_fix = wkt!(POINT(24.0 42.5));
+/// assert_relative_eq!(
+///     6574.641481220621,
+///     Haversine.distance(&gps_fix, &road)
+/// );
 /// ```
 ///
 /// # References
@@ -477,9 +487,28 @@ impl<T: CoordFloat + FromPrimitive> HaversineIntermediateFillCalculation<T> {
     }
 }
 
+/// The distance to the closest point of the segment along a [great circle], found with
+/// [`HaversineClosestPoint`].
+///
+/// [great circle]: https://en.wikipedia.org/wiki/Great_circle
+impl<F: GeoFloat + FromPrimitive> PointSegmentDistance<F> for HaversineMeasure {
+    fn point_segment_distance(&self, point: Point<F>, segment: Line<F>) -> F {
+        match segment.haversine_closest_point(&point) {
+            Closest::Intersection(_) => F::zero(),
+            Closest::SinglePoint(closest) => self.distance(point, closest),
+            Closest::Indeterminate => self
+                .distance(point, segment.start_point())
+                .min(self.distance(point, segment.end_point())),
+        }
+    }
+}
+
+point_distance_impl!([F: GeoFloat + FromPrimitive] HaversineMeasure, F);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::coord;
 
     mod bearing {
         use super::*;
@@ -608,4 +637,73 @@ mod tests {
             assert_relative_eq!(route[0], Point::new(17.882467331860965, 24.435542998803793));
         }
     }
+
+    mod distance_to_geometries {
+        use super::*;
+        use crate::wkt;
+
+        #[test]
+        fn point_to_line() {
+            let line = wkt!(LINESTRING(0. 0.,1. 0.));
+            // the closest point of the equator is due south
+            let point = wkt!(POINT(0.5 1.));
+            let expected = Haversine.distance(point, wkt!(POINT(0.5 0.)));
+            assert_relative_eq!(Haversine.distance(&point, &line), expected, epsilon = 1e-6);
+            assert_relative_eq!(Haversine.distance(&line, &point), expected, epsilon = 1e-6);
+
+            // beyond the end of the line
+            let point = wkt!(POINT(2. 1.));
+            let expected = Haversine.distance(point, wkt!(POINT(1. 0.)));
+            assert_relative_eq!(Haversine.distance(&point, &line), expected);
+
+            let line = Line::new(coord! { x: 0., y: 0. }, coord! { x: 1., y: 0. });
+            assert_relative_eq!(Haversine.distance(&point, &line), expected);
+            assert_eq!(Haversine.distance(&wkt!(POINT(0.5 0.)), &line), 0.);
+        }
+
+        #[test]
+        fn point_to_polygon() {
+            let polygon =
+                wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.),(4. 4.,6. 4.,6. 6.,4. 6.,4. 4.)));
+            assert_eq!(Haversine.distance(&wkt!(POINT(2. 2.)), &polygon), 0.);
+
+            // the edges of the hole are great circles, which bulge towards the pole
+            let in_hole = wkt!(POINT(5. 4.5));
+            let hole = &polygon.interiors()[0];
+            assert_relative_eq!(
+                Haversine.distance(&in_hole, &polygon),
+                Haversine.distance(&in_hole, hole)
+            );
+            assert!(
+                Haversine.distance(&in_hole, &polygon)
+                    < Haversine.distance(in_hole, wkt!(POINT(5. 4.)))
+            );
+
+            // the edge along the equator is a great circle
+            let outside = Point::new(5., -1.);
+            let expected = Haversine.distance(outside, wkt!(POINT(5. 0.)));
+            assert_relative_eq!(
+                Haversine.distance(&polygon, &outside),
+                expected,
+                epsilon = 1e-6
+            );
+            let geometry = Geometry::MultiPolygon(MultiPolygon::new(vec![polygon]));
+            assert_relative_eq!(
+                Haversine.distance(&outside, &geometry),
+                expected,
+                epsilon = 1e-6
+            );
+        }
+
+        #[test]
+        fn custom_radius() {
+            let line_string = wkt!(LINESTRING(0. 0.,1. 1.,2. 0.));
+            let point = wkt!(POINT(1. 0.));
+            let unit_sphere = HaversineMeasure::new(1.0);
+            assert_relative_eq!(
+                unit_sphere.distance(&point, &line_string) * Haversine.radius(),
+                Haversine.distance(&point, &line_string)
+            );
+        }
+    }
 }
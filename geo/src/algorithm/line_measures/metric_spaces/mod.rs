@@ -9,3 +9,5 @@ pub use haversine::{Haversine, HaversineMeasure};
 
 mod rhumb;
 pub use rhumb::Rhumb;
+
+mod point_distance;
@@ -0,0 +1,112 @@
+use crate::geometry::*;
+use crate::{GeoFloat, Intersects};
+use num_traits::Bounded;
+
+/// The distance from a point to the nearest point of a line segment, which the distance from a
+/// point to any linear or areal geometry is built upon, see [`point_distance_impl`].
+pub(super) trait PointSegmentDistance<F: GeoFloat> {
+    fn point_segment_distance(&self, point: Point<F>, segment: Line<F>) -> F;
+}
+
+pub(super) fn point_line_string_distance<F: GeoFloat>(
+    metric_space: &impl PointSegmentDistance<F>,
+    point: Point<F>,
+    line_string: &LineString<F>,
+) -> F {
+    line_string
+        .lines()
+        .fold(Bounded::max_value(), |accum: F, segment| {
+            accum.min(metric_space.point_segment_distance(point, segment))
+        })
+}
+
+/// Zero if the polygon contains the point, otherwise the distance to the nearest ring.
+///
+/// Containment is tested on the lon/lat coordinates, as if the edges of the polygon were straight
+/// lines in that plane.
+pub(super) fn point_polygon_distance<F: GeoFloat>(
+    metric_space: &impl PointSegmentDistance<F>,
+    point: Point<F>,
+    polygon: &Polygon<F>,
+) -> F {
+    if polygon.exterior().0.is_empty() || polygon.intersects(&point) {
+        return F::zero();
+    }
+    polygon
+        .interiors()
+        .iter()
+        .chain([polygon.exterior()])
+        .fold(Bounded::max_value(), |accum: F, ring| {
+            accum.min(point_line_string_distance(metric_space, point, ring))
+        })
+}
+
+/// Implements `Distance` between a `Point` and every other geometry type, in both directions,
+/// for a metric space which implements [`PointSegmentDistance`].
+macro_rules! point_distance_impl {
+    ([$($generics:tt)*] $metric_space:ty, $f:ty) => {
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &Line<$f>, |metric_space, point, line| {
+            metric_space.point_segment_distance(point, *line)
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &LineString<$f>, |metric_space, point, line_string| {
+            $crate::algorithm::line_measures::metric_spaces::point_distance::point_line_string_distance(metric_space, point, line_string)
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &Polygon<$f>, |metric_space, point, polygon| {
+            $crate::algorithm::line_measures::metric_spaces::point_distance::point_polygon_distance(metric_space, point, polygon)
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &Rect<$f>, |metric_space, point, rect| {
+            $crate::algorithm::line_measures::metric_spaces::point_distance::point_polygon_distance(metric_space, point, &rect.to_polygon())
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &Triangle<$f>, |metric_space, point, triangle| {
+            $crate::algorithm::line_measures::metric_spaces::point_distance::point_polygon_distance(metric_space, point, &triangle.to_polygon())
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &MultiPoint<$f>, |metric_space, point, multi_point| {
+            multi_point.iter().fold(<$f as num_traits::Bounded>::max_value(), |accum: $f, member| {
+                accum.min(metric_space.distance(point, *member))
+            })
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &MultiLineString<$f>, |metric_space, point, multi_line_string| {
+            multi_line_string.iter().fold(<$f as num_traits::Bounded>::max_value(), |accum: $f, member| {
+                accum.min(metric_space.distance(&point, member))
+            })
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &MultiPolygon<$f>, |metric_space, point, multi_polygon| {
+            multi_polygon.iter().fold(<$f as num_traits::Bounded>::max_value(), |accum: $f, member| {
+                accum.min(metric_space.distance(&point, member))
+            })
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &GeometryCollection<$f>, |metric_space, point, collection| {
+            collection.iter().fold(<$f as num_traits::Bounded>::max_value(), |accum: $f, member| {
+                accum.min(metric_space.distance(&point, member))
+            })
+        });
+        point_distance_impl!(@symmetric [$($generics)*] $metric_space, $f, &Geometry<$f>, |metric_space, point, geometry| {
+            match geometry {
+                Geometry::Point(other) => metric_space.distance(point, *other),
+                Geometry::Line(line) => metric_space.distance(&point, line),
+                Geometry::LineString(line_string) => metric_space.distance(&point, line_string),
+                Geometry::Polygon(polygon) => metric_space.distance(&point, polygon),
+                Geometry::MultiPoint(multi_point) => metric_space.distance(&point, multi_point),
+                Geometry::MultiLineString(multi_line_string) => metric_space.distance(&point, multi_line_string),
+                Geometry::MultiPolygon(multi_polygon) => metric_space.distance(&point, multi_polygon),
+                Geometry::GeometryCollection(collection) => metric_space.distance(&point, collection),
+                Geometry::Rect(rect) => metric_space.distance(&point, rect),
+                Geometry::Triangle(triangle) => metric_space.distance(&point, triangle),
+            }
+        });
+    };
+    (@symmetric [$($generics:tt)*] $metric_space:ty, $f:ty, $geometry:ty, |$self:ident, $point:ident, $other:ident| $body:block) => {
+        impl<$($generics)*> Distance<$f, &Point<$f>, $geometry> for $metric_space {
+            fn distance(&self, point: &Point<$f>, $other: $geometry) -> $f {
+                let ($self, $point) = (self, *point);
+                $body
+            }
+        }
+        impl<$($generics)*> Distance<$f, $geometry, &Point<$f>> for $metric_space {
+            fn distance(&self, geometry: $geometry, point: &Point<$f>) -> $f {
+                self.distance(point, geometry)
+            }
+        }
+    };
+}
+pub(super) use point_distance_impl;
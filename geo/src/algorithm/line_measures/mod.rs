@@ -34,14 +34,26 @@ pub use interpolate_point::InterpolatePoint;
 mod interpolate_line;
 pub use interpolate_line::{InterpolatableLine, InterpolateLine};
 
+mod line_substring;
+pub use line_substring::{LineSubstring, SubstringableLine};
+
 mod length;
 pub use length::{Length, LengthMeasurable};
 
 mod densify;
 pub use densify::{Densifiable, Densify};
 
+mod buffer;
+pub use buffer::MetricBuffer;
+
 mod frechet_distance;
 pub use frechet_distance::FrechetDistance;
 
+mod continuous_frechet_distance;
+pub use continuous_frechet_distance::ContinuousFrechetDistance;
+
+mod trajectory_similarity;
+pub use trajectory_similarity::{DtwDistance, EdrDistance, LcssDistance};
+
 pub mod metric_spaces;
 pub use metric_spaces::{Euclidean, Geodesic, GeodesicMeasure, Haversine, HaversineMeasure, Rhumb};
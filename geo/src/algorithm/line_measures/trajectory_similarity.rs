@@ -0,0 +1,391 @@
+//! Similarity measures for trajectories, which align the vertices of one line with those of
+//! another.
+
+use geo_types::{CoordFloat, LineString, Point};
+
+use super::Distance;
+
+/// Determine the similarity between two `LineStrings` using [Dynamic Time Warping] (DTW).
+///
+/// DTW is the smallest sum of the distances between aligned vertices, over all alignments which
+/// pair up the first and last vertices of the lines and never go backwards along either line.
+/// Unlike the [`FrechetDistance`](super::FrechetDistance), which is the largest of these
+/// distances, it grows with the number of vertices.
+///
+/// [Dynamic Time Warping]: https://en.wikipedia.org/wiki/Dynamic_time_warping
+pub trait DtwDistance<F: CoordFloat>: Distance<F, Point<F>, Point<F>> {
+    /// Returns the DTW distance between two `LineString`s, or zero if either is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::line_measures::DtwDistance;
+    /// use geo::{wkt, Euclidean};
+    ///
+    /// let line_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.));
+    /// let line_2 = wkt!(LINESTRING(0. 1.,3. 1.));
+    ///
+    /// // both vertices of `line_2` are aligned with two vertices of `line_1`
+    /// let distance = Euclidean.dtw_distance(&line_1, &line_2);
+    /// assert_eq!(distance, 2. * 2f64.sqrt() + 2.);
+    /// ```
+    fn dtw_distance(&self, ls_1: &LineString<F>, ls_2: &LineString<F>) -> F {
+        self.dtw_distance_with_band(ls_1, ls_2, usize::MAX)
+    }
+
+    /// Returns the DTW distance between two `LineString`s, only aligning vertices within a
+    /// [Sakoe-Chiba band] of `band_width`, or zero if either is empty.
+    ///
+    /// Vertices are only aligned if their indices differ by no more than `band_width`, which
+    /// takes `O(n * band_width)` rather than `O(n * m)` time for lines of `n` and `m` vertices.
+    /// The band is widened to the difference between `n` and `m` if necessary, so that the last
+    /// vertices can be aligned.
+    ///
+    /// [Sakoe-Chiba band]: https://doi.org/10.1109/TASSP.1978.1163055
+    fn dtw_distance_with_band(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        band_width: usize,
+    ) -> F;
+}
+
+impl<F, MetricSpace> DtwDistance<F> for MetricSpace
+where
+    F: CoordFloat,
+    MetricSpace: Distance<F, Point<F>, Point<F>>,
+{
+    fn dtw_distance_with_band(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        band_width: usize,
+    ) -> F {
+        let (n, m) = (ls_1.0.len(), ls_2.0.len());
+        if n == 0 || m == 0 {
+            return F::zero();
+        }
+
+        // row `i` holds the cost of aligning the first `i` vertices of `ls_1` with the first `j`
+        // vertices of `ls_2`, which is infinite outside of the band
+        let mut previous = vec![F::infinity(); m + 1];
+        let mut current = vec![F::infinity(); m + 1];
+        previous[0] = F::zero();
+        for (i, a) in ls_1.points().enumerate() {
+            let (start, end) = band(i + 1, n, m, band_width);
+            current[start - 1] = F::infinity();
+            for j in start..=end {
+                let cost = self.distance(a, Point(ls_2.0[j - 1]));
+                current[j] = cost + previous[j - 1].min(previous[j]).min(current[j - 1]);
+            }
+            if end < m {
+                current[end + 1] = F::infinity();
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+        previous[m]
+    }
+}
+
+/// Determine the similarity between two `LineStrings` using the [Longest Common Subsequence]
+/// (LCSS) of their vertices.
+///
+/// Vertices match if they are no more than `epsilon` apart. The LCSS distance is one minus the
+/// number of vertices in the longest sequence of matches, which never goes backwards along
+/// either line, as a fraction of the number of vertices of the shorter line. It is between 0 for
+/// lines whose vertices all match and 1 for lines with no matching vertices, and isn't affected by
+/// outlying vertices.
+///
+/// [Longest Common Subsequence]: https://doi.org/10.1109/ICDE.2002.994784
+pub trait LcssDistance<F: CoordFloat>: Distance<F, Point<F>, Point<F>> {
+    /// Returns the LCSS distance between two `LineString`s, or zero if either is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::line_measures::LcssDistance;
+    /// use geo::{wkt, Euclidean};
+    ///
+    /// let line_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.));
+    /// // the third vertex is an outlier
+    /// let line_2 = wkt!(LINESTRING(0. 0.1,1. 0.1,2. 5.,3. 0.1));
+    ///
+    /// assert_eq!(Euclidean.lcss_distance(&line_1, &line_2, 0.5), 0.25);
+    /// ```
+    fn lcss_distance(&self, ls_1: &LineString<F>, ls_2: &LineString<F>, epsilon: F) -> F {
+        self.lcss_distance_with_band(ls_1, ls_2, epsilon, usize::MAX)
+    }
+
+    /// Returns the LCSS distance between two `LineString`s, only matching vertices within a
+    /// Sakoe-Chiba band of `band_width`, or zero if either is empty.
+    ///
+    /// See [`DtwDistance::dtw_distance_with_band`] for how the band is applied.
+    fn lcss_distance_with_band(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        epsilon: F,
+        band_width: usize,
+    ) -> F;
+}
+
+impl<F, MetricSpace> LcssDistance<F> for MetricSpace
+where
+    F: CoordFloat,
+    MetricSpace: Distance<F, Point<F>, Point<F>>,
+{
+    fn lcss_distance_with_band(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        epsilon: F,
+        band_width: usize,
+    ) -> F {
+        let (n, m) = (ls_1.0.len(), ls_2.0.len());
+        if n == 0 || m == 0 {
+            return F::zero();
+        }
+
+        // row `i` holds the length of the longest common subsequence of the first `i` vertices
+        // of `ls_1` and the first `j` vertices of `ls_2`. Outside of the band, it's the same as at
+        // the nearest cell of the band in the previous row or the same row.
+        let mut previous = vec![0; m + 1];
+        let mut current = vec![0; m + 1];
+        for (i, a) in ls_1.points().enumerate() {
+            let (start, end) = band(i + 1, n, m, band_width);
+            current[start - 1] = previous[start - 1];
+            for j in start..=end {
+                current[j] = if self.distance(a, Point(ls_2.0[j - 1])) <= epsilon {
+                    previous[j - 1] + 1
+                } else {
+                    previous[j].max(current[j - 1])
+                };
+            }
+            if end < m {
+                current[end + 1] = current[end];
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+        let common = F::from(previous[m]).unwrap();
+        F::one() - common / F::from(n.min(m)).unwrap()
+    }
+}
+
+/// Determine the similarity between two `LineStrings` using the [Edit Distance on Real
+/// sequences] (EDR) of their vertices.
+///
+/// Vertices match if they are no more than `epsilon` apart. The EDR distance is the smallest
+/// number of vertices which must be inserted, deleted or replaced to turn one line into the
+/// other, where matching vertices needn't be replaced. Like [`LcssDistance`], it isn't affected by
+/// outlying vertices, but it also penalizes gaps between matches.
+///
+/// [Edit Distance on Real sequences]: https://doi.org/10.1145/1066157.1066213
+pub trait EdrDistance<F: CoordFloat>: Distance<F, Point<F>, Point<F>> {
+    /// Returns the EDR distance between two `LineString`s. If either is empty, it's the number
+    /// of vertices of the other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::line_measures::EdrDistance;
+    /// use geo::{wkt, Euclidean};
+    ///
+    /// let line_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.));
+    /// // the third vertex is replaced, and the last is deleted
+    /// let line_2 = wkt!(LINESTRING(0. 0.1,1. 0.1,2. 5.));
+    ///
+    /// assert_eq!(Euclidean.edr_distance(&line_1, &line_2, 0.5), 2);
+    /// ```
+    fn edr_distance(&self, ls_1: &LineString<F>, ls_2: &LineString<F>, epsilon: F) -> usize {
+        self.edr_distance_with_band(ls_1, ls_2, epsilon, usize::MAX)
+    }
+
+    /// Returns the EDR distance between two `LineString`s, only aligning vertices within a
+    /// Sakoe-Chiba band of `band_width`. If either is empty, it's the number of vertices of the
+    /// other.
+    ///
+    /// See [`DtwDistance::dtw_distance_with_band`] for how the band is applied.
+    fn edr_distance_with_band(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        epsilon: F,
+        band_width: usize,
+    ) -> usize;
+}
+
+impl<F, MetricSpace> EdrDistance<F> for MetricSpace
+where
+    F: CoordFloat,
+    MetricSpace: Distance<F, Point<F>, Point<F>>,
+{
+    fn edr_distance_with_band(
+        &self,
+        ls_1: &LineString<F>,
+        ls_2: &LineString<F>,
+        epsilon: F,
+        band_width: usize,
+    ) -> usize {
+        let (n, m) = (ls_1.0.len(), ls_2.0.len());
+        if n == 0 || m == 0 {
+            return n.max(m);
+        }
+
+        // row `i` holds the edit distance between the first `i` vertices of `ls_1` and the first
+        // `j` vertices of `ls_2`, which is unbounded outside of the band
+        let width = band_width.max(n.abs_diff(m));
+        let mut previous: Vec<usize> = (0..=m)
+            .map(|j| if j <= width { j } else { usize::MAX })
+            .collect();
+        let mut current = vec![usize::MAX; m + 1];
+        for (i, a) in ls_1.points().enumerate() {
+            let (start, end) = band(i + 1, n, m, band_width);
+            current[start - 1] = if start == 1 && i < width {
+                i + 1
+            } else {
+                usize::MAX
+            };
+            for j in start..=end {
+                let replace = if self.distance(a, Point(ls_2.0[j - 1])) <= epsilon {
+                    0
+                } else {
+                    1
+                };
+                current[j] = previous[j - 1]
+                    .saturating_add(replace)
+                    .min(previous[j].saturating_add(1))
+                    .min(current[j - 1].saturating_add(1));
+            }
+            if end < m {
+                current[end + 1] = usize::MAX;
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+        previous[m]
+    }
+}
+
+/// The first and last (1-based) vertex of the second line which may be aligned with vertex `i`
+/// of the first, widening the band so that vertex `n` can be aligned with vertex `m`.
+fn band(i: usize, n: usize, m: usize, band_width: usize) -> (usize, usize) {
+    let width = band_width.max(n.abs_diff(m));
+    (
+        i.saturating_sub(width).max(1),
+        i.saturating_add(width).min(m),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{wkt, Euclidean, Haversine};
+
+    #[test]
+    fn dtw() {
+        let ls_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.));
+        assert_eq!(Euclidean.dtw_distance(&ls_1, &ls_1), 0.);
+
+        // a repeated vertex costs nothing
+        let ls_2 = wkt!(LINESTRING(0. 0.,1. 0.,1. 0.,2. 0.));
+        assert_eq!(Euclidean.dtw_distance(&ls_1, &ls_2), 0.);
+        assert_eq!(Euclidean.dtw_distance(&ls_2, &ls_1), 0.);
+
+        let ls_3 = wkt!(LINESTRING(0. 1.,2. 1.));
+        assert_eq!(Euclidean.dtw_distance(&ls_1, &ls_3), 1. + 2f64.sqrt() + 1.);
+
+        assert_eq!(Euclidean.dtw_distance(&ls_1, &LineString::new(vec![])), 0.);
+    }
+
+    #[test]
+    fn dtw_band() {
+        // `ls_2` is `ls_1` delayed by two vertices
+        let ls_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.,3. 0.,3. 0.));
+        let ls_2 = wkt!(LINESTRING(0. 0.,0. 0.,0. 0.,1. 0.,2. 0.,3. 0.));
+        assert_eq!(Euclidean.dtw_distance(&ls_1, &ls_2), 0.);
+        assert_eq!(Euclidean.dtw_distance_with_band(&ls_1, &ls_2, 2), 0.);
+        // the delayed vertices can't be aligned within a narrower band
+        assert_eq!(Euclidean.dtw_distance_with_band(&ls_1, &ls_2, 1), 3.);
+        assert_eq!(Euclidean.dtw_distance_with_band(&ls_1, &ls_2, 0), 6.);
+
+        // the band is widened to align the ends of lines of different lengths
+        let ls_3 = wkt!(LINESTRING(0. 0.,3. 0.));
+        assert_eq!(Euclidean.dtw_distance_with_band(&ls_1, &ls_3, 0), 2.);
+        assert_eq!(Euclidean.dtw_distance_with_band(&ls_3, &ls_1, 0), 2.);
+    }
+
+    #[test]
+    fn lcss() {
+        let ls_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.));
+        let ls_2 = wkt!(LINESTRING(0. 0.1,1. 0.1,2. 5.,3. 0.1));
+        assert_eq!(Euclidean.lcss_distance(&ls_1, &ls_2, 0.5), 0.25);
+        assert_eq!(Euclidean.lcss_distance(&ls_1, &ls_2, 0.05), 1.);
+        assert_eq!(Euclidean.lcss_distance(&ls_1, &ls_2, 10.), 0.);
+
+        // the shorter line is a subsequence of the longer
+        let ls_3 = wkt!(LINESTRING(1. 0.,3. 0.));
+        assert_eq!(Euclidean.lcss_distance(&ls_1, &ls_3, 0.5), 0.);
+        assert_eq!(Euclidean.lcss_distance(&ls_3, &ls_1, 0.5), 0.);
+    }
+
+    #[test]
+    fn lcss_band() {
+        let ls_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.,4. 0.));
+        let ls_2 = wkt!(LINESTRING(9. 9.,9. 9.,0. 0.,1. 0.,2. 0.));
+        assert_relative_eq!(Euclidean.lcss_distance(&ls_1, &ls_2, 0.5), 0.4);
+        assert_relative_eq!(Euclidean.lcss_distance_with_band(&ls_1, &ls_2, 0.5, 2), 0.4);
+        assert_relative_eq!(Euclidean.lcss_distance_with_band(&ls_1, &ls_2, 0.5, 1), 1.);
+    }
+
+    #[test]
+    fn edr() {
+        let ls_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.));
+        let ls_2 = wkt!(LINESTRING(0. 0.1,1. 0.1,2. 5.));
+        assert_eq!(Euclidean.edr_distance(&ls_1, &ls_2, 0.5), 2);
+        assert_eq!(Euclidean.edr_distance(&ls_2, &ls_1, 0.5), 2);
+        assert_eq!(Euclidean.edr_distance(&ls_1, &ls_1, 0.), 0);
+        assert_eq!(
+            Euclidean.edr_distance(&ls_1, &LineString::new(vec![]), 0.5),
+            4
+        );
+
+        // a gap is penalized
+        let ls_3 = wkt!(LINESTRING(0. 0.,3. 0.));
+        assert_eq!(Euclidean.edr_distance(&ls_1, &ls_3, 0.5), 2);
+    }
+
+    #[test]
+    fn edr_band() {
+        let ls_1 = wkt!(LINESTRING(0. 0.,1. 0.,2. 0.,3. 0.,4. 0.));
+        let ls_2 = wkt!(LINESTRING(9. 9.,9. 9.,0. 0.,1. 0.,2. 0.));
+        assert_eq!(Euclidean.edr_distance(&ls_1, &ls_2, 0.5), 4);
+        assert_eq!(Euclidean.edr_distance_with_band(&ls_1, &ls_2, 0.5, 2), 4);
+        // without deletions and insertions, every vertex is replaced
+        assert_eq!(Euclidean.edr_distance_with_band(&ls_1, &ls_2, 0.5, 0), 5);
+    }
+
+    #[test]
+    fn band_matches_unconstrained() {
+        let ls_1: LineString = (0..200)
+            .map(|i| (i as f64 * 0.01, (i as f64 * 0.1).sin()))
+            .collect();
+        let ls_2: LineString = (0..150)
+            .map(|i| (i as f64 * 0.013, (i as f64 * 0.13).sin() + 0.01))
+            .collect();
+        assert_eq!(
+            Haversine.dtw_distance(&ls_1, &ls_2),
+            Haversine.dtw_distance_with_band(&ls_1, &ls_2, 200)
+        );
+        assert!(
+            Haversine.dtw_distance(&ls_1, &ls_2)
+                <= Haversine.dtw_distance_with_band(&ls_1, &ls_2, 10)
+        );
+        assert_eq!(
+            Haversine.lcss_distance(&ls_1, &ls_2, 1_000.),
+            Haversine.lcss_distance_with_band(&ls_1, &ls_2, 1_000., 200)
+        );
+        assert_eq!(
+            Haversine.edr_distance(&ls_1, &ls_2, 1_000.),
+            Haversine.edr_distance_with_band(&ls_1, &ls_2, 1_000., 200)
+        );
+    }
+}
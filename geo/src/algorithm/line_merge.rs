@@ -0,0 +1,573 @@
+use crate::relate::geomgraph::node_map::{NodeFactory, NodeMap};
+use crate::{Coord, GeoFloat, LineString, MultiLineString};
+
+/// Join the parts of a `MultiLineString` which meet end to end into the longest possible
+/// `LineString`s.
+///
+/// Two parts are only joined where no other part meets them, i.e. at a node of degree 2. Parts
+/// which form a ring of such nodes are joined into a closed `LineString`.
+///
+/// Parts with fewer than two distinct coordinates are dropped, and repeated coordinates are
+/// removed from the output.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, LineMerge};
+///
+/// let lines = wkt!(MULTILINESTRING(
+///     (0. 0.,1. 0.),
+///     (2. 0.,1. 0.),
+///     (2. 0.,3. 0.),
+///     (3. 0.,4. 1.),
+///     (3. 0.,4. 2.)
+/// ));
+///
+/// assert_eq!(
+///     lines.line_merge(),
+///     wkt!(MULTILINESTRING(
+///         (0. 0.,1. 0.,2. 0.,3. 0.),
+///         (3. 0.,4. 1.),
+///         (3. 0.,4. 2.)
+///     ))
+/// );
+/// ```
+pub trait LineMerge<T: GeoFloat> {
+    /// Merge the parts, reversing them where needed.
+    fn line_merge(&self) -> MultiLineString<T> {
+        self.line_merge_with_options(LineMergeOptions::default())
+    }
+
+    /// Merge the parts, see [`LineMergeOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::line_merge::{LineMerge, LineMergeOptions};
+    /// use geo::wkt;
+    ///
+    /// let lines = wkt!(MULTILINESTRING((0. 0.,1. 0.),(2. 0.,1. 0.)));
+    ///
+    /// // the lines both end at (1 0), so they can't be joined without reversing one
+    /// let directed = LineMergeOptions::new().directed(true);
+    /// assert_eq!(lines.line_merge_with_options(directed), lines);
+    ///
+    /// let lines = wkt!(MULTILINESTRING(
+    ///     (2. 2.,3. 3.,4. 2.),
+    ///     (0. 2.,1. 1.,2. 2.),
+    ///     (4. 2.,3. 1.,2. 2.)
+    /// ));
+    ///
+    /// // the loop through (4 2) is output after the line leading to it
+    /// let sequenced = LineMergeOptions::new().sequenced(true);
+    /// assert_eq!(
+    ///     lines.line_merge_with_options(sequenced),
+    ///     wkt!(MULTILINESTRING(
+    ///         (0. 2.,1. 1.,2. 2.),
+    ///         (2. 2.,3. 3.,4. 2.,3. 1.,2. 2.)
+    ///     ))
+    /// );
+    /// ```
+    fn line_merge_with_options(&self, options: LineMergeOptions) -> MultiLineString<T>;
+}
+
+/// Parameters for [`LineMerge::line_merge_with_options`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineMergeOptions {
+    /// Never reverse parts: only join a part ending at a node to a part starting there.
+    pub directed: bool,
+    /// Order the output like the JTS `LineSequencer`, so that each `LineString` starts where the
+    /// previous one ends, wherever the topology allows it. Connected lines are kept together, and
+    /// unless `directed` is set, lines are reversed where needed.
+    pub sequenced: bool,
+}
+
+impl LineMergeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = directed;
+        self
+    }
+
+    pub fn sequenced(mut self, sequenced: bool) -> Self {
+        self.sequenced = sequenced;
+        self
+    }
+}
+
+impl<T: GeoFloat> LineMerge<T> for MultiLineString<T> {
+    fn line_merge_with_options(&self, options: LineMergeOptions) -> MultiLineString<T> {
+        let lines = self.iter().map(|line_string| line_string.0.clone());
+        let graph = MergeGraph::new(lines, options.directed);
+        let merged = graph.merge().map(|chain| graph.coords(&chain));
+        if !options.sequenced {
+            return merged.map(LineString::new).collect();
+        }
+
+        let graph = MergeGraph::new(merged, options.directed);
+        graph
+            .sequence()
+            .into_iter()
+            .map(|step| LineString::new(graph.coords(&[step])))
+            .collect()
+    }
+}
+
+/// A line traversed either along or against its direction.
+///
+/// At a node, the lines meeting there are represented by the steps which leave the node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Step {
+    line: usize,
+    forward: bool,
+}
+
+impl Step {
+    fn new(line: usize, forward: bool) -> Self {
+        Self { line, forward }
+    }
+
+    fn reversed(self) -> Self {
+        Self {
+            line: self.line,
+            forward: !self.forward,
+        }
+    }
+}
+
+/// The steps leaving each node.
+#[derive(Debug, Clone, PartialEq)]
+struct MergeNodeFactory;
+
+impl<F: GeoFloat> NodeFactory<F> for MergeNodeFactory {
+    type Node = Vec<Step>;
+    fn create_node(_coordinate: Coord<F>) -> Self::Node {
+        vec![]
+    }
+}
+
+/// A graph with a node at the ends of each line.
+struct MergeGraph<T: GeoFloat> {
+    lines: Vec<Vec<Coord<T>>>,
+    /// The start and end node of each line.
+    line_nodes: Vec<[usize; 2]>,
+    /// The steps leaving each node.
+    nodes: Vec<Vec<Step>>,
+    directed: bool,
+}
+
+impl<T: GeoFloat> MergeGraph<T> {
+    fn new(lines: impl IntoIterator<Item = Vec<Coord<T>>>, directed: bool) -> Self {
+        let lines: Vec<Vec<Coord<T>>> = lines
+            .into_iter()
+            .map(|mut coords| {
+                coords.dedup();
+                coords
+            })
+            .filter(|coords| coords.len() >= 2)
+            .collect();
+
+        let mut node_map = NodeMap::<T, MergeNodeFactory>::new();
+        for (line, coords) in lines.iter().enumerate() {
+            node_map
+                .insert_node_with_coordinate(coords[0])
+                .push(Step::new(line, true));
+            node_map
+                .insert_node_with_coordinate(coords[coords.len() - 1])
+                .push(Step::new(line, false));
+        }
+
+        let nodes: Vec<Vec<Step>> = node_map.into_iter().collect();
+        let mut line_nodes = vec![[0, 0]; lines.len()];
+        for (node, steps) in nodes.iter().enumerate() {
+            for step in steps {
+                line_nodes[step.line][usize::from(!step.forward)] = node;
+            }
+        }
+
+        Self {
+            lines,
+            line_nodes,
+            nodes,
+            directed,
+        }
+    }
+
+    fn origin(&self, step: Step) -> usize {
+        self.line_nodes[step.line][usize::from(!step.forward)]
+    }
+
+    fn destination(&self, step: Step) -> usize {
+        self.origin(step.reversed())
+    }
+
+    /// Whether exactly two lines meet at `node`, so that a line arriving there can only continue
+    /// along the other one.
+    fn is_pass_through(&self, node: usize) -> bool {
+        let steps = &self.nodes[node];
+        if self.directed {
+            steps.len() == 2 && steps[0].forward != steps[1].forward
+        } else {
+            steps.len() == 2
+        }
+    }
+
+    /// The chains of steps joined by pass-through nodes.
+    fn merge(&self) -> impl Iterator<Item = Vec<Step>> + '_ {
+        let mut used = vec![false; self.lines.len()];
+        let mut chains = vec![];
+
+        // Chains which end at other nodes, started in input order.
+        for line in 0..self.lines.len() {
+            if used[line] {
+                continue;
+            }
+            let forward = Step::new(line, true);
+            if !self.is_pass_through(self.origin(forward)) {
+                chains.push(self.walk(forward, &mut used));
+            } else if !self.directed && !self.is_pass_through(self.destination(forward)) {
+                chains.push(self.walk(forward.reversed(), &mut used));
+            }
+        }
+
+        // The remaining lines form rings of pass-through nodes.
+        for line in 0..self.lines.len() {
+            if !used[line] {
+                chains.push(self.walk(Step::new(line, true), &mut used));
+            }
+        }
+
+        chains.into_iter().map(|chain| {
+            // like JTS, keep the orientation of most of the lines
+            let reversed = chain.iter().filter(|step| !step.forward).count();
+            if 2 * reversed > chain.len() {
+                chain.into_iter().rev().map(Step::reversed).collect()
+            } else {
+                chain
+            }
+        })
+    }
+
+    /// Follow the lines from `start` through pass-through nodes.
+    fn walk(&self, start: Step, used: &mut [bool]) -> Vec<Step> {
+        let mut chain = vec![];
+        let mut step = start;
+        loop {
+            used[step.line] = true;
+            chain.push(step);
+            let node = self.destination(step);
+            if !self.is_pass_through(node) {
+                break;
+            }
+            let arrival = step.reversed();
+            let Some(&next) = self.nodes[node].iter().find(|&&s| s != arrival) else {
+                break;
+            };
+            if used[next.line] {
+                break;
+            }
+            step = next;
+        }
+        chain
+    }
+
+    /// Order and orient the lines so that consecutive lines are connected wherever possible.
+    ///
+    /// The lines of each connected component are covered by the fewest possible trails: we
+    /// connect the nodes with an odd degree (or unbalanced in and out degrees, for directed
+    /// lines) with extra edges, find an Eulerian circuit, and then cut it at the extra edges.
+    fn sequence(&self) -> Vec<Step> {
+        let components = self.components();
+        let mut sequence = Vec::with_capacity(self.lines.len());
+        for lines in components {
+            sequence.extend(self.sequence_component(&lines));
+        }
+        sequence
+    }
+
+    /// The lines of each connected component, with components ordered by their first line.
+    fn components(&self) -> Vec<Vec<usize>> {
+        let mut component_of_node: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut components: Vec<Vec<usize>> = vec![];
+        for line in 0..self.lines.len() {
+            if component_of_node[self.line_nodes[line][0]].is_some() {
+                continue;
+            }
+            let component = components.len();
+            let mut lines = vec![];
+            let mut stack = vec![self.line_nodes[line][0]];
+            component_of_node[stack[0]] = Some(component);
+            while let Some(node) = stack.pop() {
+                for &step in &self.nodes[node] {
+                    if step.forward {
+                        lines.push(step.line);
+                    }
+                    let next = self.destination(step);
+                    if component_of_node[next].is_none() {
+                        component_of_node[next] = Some(component);
+                        stack.push(next);
+                    }
+                }
+            }
+            lines.sort_unstable();
+            components.push(lines);
+        }
+        components
+    }
+
+    fn sequence_component(&self, lines: &[usize]) -> Vec<Step> {
+        // Edges are the lines of the component, followed by the extra edges.
+        let mut edges: Vec<[usize; 2]> = lines.iter().map(|&l| self.line_nodes[l]).collect();
+
+        let mut balance = std::collections::BTreeMap::<usize, isize>::new();
+        for &[start, end] in &edges {
+            *balance.entry(start).or_default() += 1;
+            *balance.entry(end).or_default() += if self.directed { -1 } else { 1 };
+        }
+        if self.directed {
+            // connect nodes with more lines arriving to nodes with more lines leaving
+            let mut sinks = vec![];
+            let mut sources = vec![];
+            for (&node, &balance) in &balance {
+                let list = if balance < 0 {
+                    &mut sinks
+                } else {
+                    &mut sources
+                };
+                list.extend(std::iter::repeat(node).take(balance.unsigned_abs()));
+            }
+            edges.extend(sinks.into_iter().zip(sources).map(|(a, b)| [a, b]));
+        } else {
+            let odd: Vec<usize> = balance
+                .iter()
+                .filter(|(_, balance)| *balance % 2 != 0)
+                .map(|(&node, _)| node)
+                .collect();
+            edges.extend(odd.chunks_exact(2).map(|pair| [pair[0], pair[1]]));
+        }
+
+        let mut adjacent = std::collections::BTreeMap::<usize, Vec<usize>>::new();
+        for (edge, &[start, end]) in edges.iter().enumerate() {
+            adjacent.entry(start).or_default().push(edge);
+            if !self.directed && start != end {
+                adjacent.entry(end).or_default().push(edge);
+            }
+        }
+
+        // Hierholzer's algorithm, starting from the first line of the component
+        let mut used = vec![false; edges.len()];
+        let mut stack: Vec<(usize, Option<(usize, bool)>)> = vec![(edges[0][0], None)];
+        let mut circuit = vec![];
+        while let Some(&(node, _)) = stack.last() {
+            let next = adjacent.get_mut(&node).and_then(|edges| {
+                while let Some(edge) = edges.pop() {
+                    if !used[edge] {
+                        return Some(edge);
+                    }
+                }
+                None
+            });
+            match next {
+                Some(edge) => {
+                    used[edge] = true;
+                    let forward = edges[edge][0] == node;
+                    let next_node = edges[edge][usize::from(forward)];
+                    stack.push((next_node, Some((edge, forward))));
+                }
+                None => {
+                    let (_, edge) = stack.pop().expect("stack is not empty");
+                    circuit.extend(edge);
+                }
+            }
+        }
+        circuit.reverse();
+
+        // Start the circuit after an extra edge, so that cutting it at the extra edges doesn't
+        // split a trail in two.
+        if let Some(first_extra) = circuit.iter().position(|&(e, _)| e >= lines.len()) {
+            circuit.rotate_left(first_extra + 1);
+        }
+        circuit
+            .into_iter()
+            .filter(|&(edge, _)| edge < lines.len())
+            .map(|(edge, forward)| Step::new(lines[edge], forward))
+            .collect()
+    }
+
+    fn coords(&self, chain: &[Step]) -> Vec<Coord<T>> {
+        let mut coords = vec![];
+        for step in chain {
+            let line = &self.lines[step.line];
+            let skip = usize::from(!coords.is_empty());
+            if step.forward {
+                coords.extend(line.iter().skip(skip));
+            } else {
+                coords.extend(line.iter().rev().skip(skip));
+            }
+        }
+        coords
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt;
+
+    fn directed() -> LineMergeOptions {
+        LineMergeOptions::new().directed(true)
+    }
+
+    fn sequenced() -> LineMergeOptions {
+        LineMergeOptions::new().sequenced(true)
+    }
+
+    #[test]
+    fn empty() {
+        let lines = MultiLineString::<f64>::new(vec![]);
+        assert_eq!(lines.line_merge(), lines);
+        assert_eq!(lines.line_merge_with_options(sequenced()), lines);
+    }
+
+    #[test]
+    fn reverses_lines() {
+        let lines = wkt!(MULTILINESTRING((0. 0.,1. 1.),(2. 2.,1. 1.),(2. 2.,3. 3.)));
+        assert_eq!(
+            lines.line_merge(),
+            wkt!(MULTILINESTRING((0. 0.,1. 1.,2. 2.,3. 3.)))
+        );
+        assert_eq!(
+            lines.line_merge_with_options(directed()),
+            wkt!(MULTILINESTRING((0. 0.,1. 1.),(2. 2.,1. 1.),(2. 2.,3. 3.)))
+        );
+    }
+
+    #[test]
+    fn keeps_majority_orientation() {
+        let lines = wkt!(MULTILINESTRING((3. 3.,2. 2.),(1. 1.,2. 2.),(1. 1.,0. 0.)));
+        assert_eq!(
+            lines.line_merge(),
+            wkt!(MULTILINESTRING((3. 3.,2. 2.,1. 1.,0. 0.)))
+        );
+    }
+
+    #[test]
+    fn stops_at_junctions() {
+        // JTS LineMergerTest.test1
+        let lines = wkt!(MULTILINESTRING(
+            (120. 120.,180. 140.),
+            (200. 180.,180. 140.),
+            (200. 180.,240. 180.)
+        ));
+        assert_eq!(
+            lines.line_merge(),
+            wkt!(MULTILINESTRING((120. 120.,180. 140.,200. 180.,240. 180.)))
+        );
+
+        let lines = wkt!(MULTILINESTRING(
+            (0. 0.,10. 0.),
+            (10. 0.,20. 0.),
+            (10. 0.,10. 10.),
+            (10. 10.,10. 20.)
+        ));
+        assert_eq!(
+            lines.line_merge(),
+            wkt!(MULTILINESTRING((0. 0.,10. 0.),(10. 0.,20. 0.),(10. 0.,10. 10.,10. 20.)))
+        );
+    }
+
+    #[test]
+    fn rings() {
+        let lines = wkt!(MULTILINESTRING(
+            (0. 0.,10. 0.),
+            (10. 10.,10. 0.),
+            (10. 10.,0. 0.),
+            (20. 0.,30. 0.,20. 10.,20. 0.)
+        ));
+        assert_eq!(
+            lines.line_merge(),
+            wkt!(MULTILINESTRING(
+                (0. 0.,10. 0.,10. 10.,0. 0.),
+                (20. 0.,30. 0.,20. 10.,20. 0.)
+            ))
+        );
+    }
+
+    #[test]
+    fn drops_degenerate_lines() {
+        let lines = wkt!(MULTILINESTRING((0. 0.,0. 0.),(0. 0.,1. 0.,1. 0.,2. 0.)));
+        assert_eq!(
+            lines.line_merge(),
+            wkt!(MULTILINESTRING((0. 0.,1. 0.,2. 0.)))
+        );
+    }
+
+    #[test]
+    fn directed_ring() {
+        let lines = wkt!(MULTILINESTRING((10. 0.,0. 0.),(0. 0.,10. 10.),(10. 10.,10. 0.)));
+        assert_eq!(
+            lines.line_merge_with_options(directed()),
+            wkt!(MULTILINESTRING((10. 0.,0. 0.,10. 10.,10. 0.)))
+        );
+    }
+
+    #[test]
+    fn sequence_single_path() {
+        // JTS LineSequencerTest.testSimple, shuffled
+        let lines = wkt!(MULTILINESTRING(
+            (20. 0.,30. 0.),
+            (0. 0.,10. 0.),
+            (10. 0.,20. 0.),
+            (10. 0.,10. 10.),
+            (10. 10.,0. 0.)
+        ));
+        let sequenced = lines.line_merge_with_options(sequenced());
+        // the triangle is merged into a ring, and the other lines into a single line
+        assert_eq!(sequenced.0.len(), 2);
+        assert_connected(&sequenced, 1);
+    }
+
+    #[test]
+    fn sequence_with_branches() {
+        // a star with four arms can be walked with two trails
+        let lines = wkt!(MULTILINESTRING(
+            (1. 1.,2. 1.),
+            (1. 1.,1. 2.),
+            (1. 1.,0. 1.),
+            (1. 1.,1. 0.),
+            (5. 5.,6. 6.)
+        ));
+        let sequenced = lines.line_merge_with_options(sequenced());
+        assert_eq!(sequenced.0.len(), 5);
+        assert_connected(&sequenced, 3);
+    }
+
+    #[test]
+    fn sequence_directed() {
+        let lines = wkt!(MULTILINESTRING(
+            (1. 0.,2. 0.),
+            (2. 0.,0. 0.),
+            (0. 0.,1. 0.),
+            (1. 0.,1. 1.)
+        ));
+        let sequenced =
+            lines.line_merge_with_options(LineMergeOptions::new().directed(true).sequenced(true));
+        assert_eq!(
+            sequenced,
+            wkt!(MULTILINESTRING((1. 0.,2. 0.,0. 0.,1. 0.),(1. 0.,1. 1.)))
+        );
+    }
+
+    /// Assert that the lines form `trails` sequences of connected lines.
+    fn assert_connected(lines: &MultiLineString<f64>, trails: usize) {
+        let breaks = lines
+            .0
+            .windows(2)
+            .filter(|pair| pair[0].0.last() != pair[1].0.first())
+            .count();
+        assert_eq!(breaks + 1, trails, "{lines:?}");
+    }
+}
@@ -0,0 +1,365 @@
+use crate::bool_ops::{even_odd_area, unary_union, BoolOpsNum, BooleanOps};
+use crate::geometry::*;
+use crate::GeoFloat;
+
+/// How [`MakeValid`] rebuilds the area of an invalid `Polygon` or `MultiPolygon`.
+///
+/// The strategies are named after those of GEOS and PostGIS' `ST_MakeValid`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MakeValidStrategy {
+    /// All rings are treated alike: a point is part of the result if it is enclosed by an odd
+    /// number of them. Holes outside of their exterior become polygons of their own, and the
+    /// parts where polygons of a `MultiPolygon` overlap are removed.
+    #[default]
+    Linework,
+    /// The area of each polygon is the area of its exterior, minus the areas of its interiors,
+    /// and the result is the union of these. Holes outside of their exterior are dropped, and
+    /// polygons of a `MultiPolygon` which overlap are merged.
+    Structure,
+}
+
+/// Repair an invalid geometry, so that it passes [`Validation::is_valid`].
+///
+/// Like the `GeometryFixer` of JTS, this preserves the dimension of the input: areal geometries
+/// are repaired into a `MultiPolygon`, dropping any parts which collapse to lines or points.
+/// Likewise, lines with fewer than two distinct points are dropped. Non-finite coordinates can't
+/// be repaired, and are removed. Valid geometries are returned mostly unchanged, though
+/// `Polygon`s are still turned into a `MultiPolygon`, whose rings may start at a different
+/// vertex.
+///
+/// Self-intersecting rings are split at the intersections, so that a bow-tie turns into two
+/// triangles, and unclosed rings are closed. See [`MakeValidStrategy`] for how the rings are
+/// then combined.
+///
+/// [`Validation::is_valid`]: crate::Validation::is_valid
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, MakeValid, Validation};
+///
+/// let bow_tie = wkt!(POLYGON((0. 0.,2. 2.,2. 0.,0. 2.,0. 0.)));
+/// assert!(!bow_tie.is_valid());
+///
+/// let fixed = bow_tie.make_valid();
+/// assert!(fixed.is_valid());
+/// assert_eq!(fixed.0.len(), 2);
+/// ```
+///
+/// The strategies differ in how they treat holes outside of their exterior:
+///
+/// ```
+/// use geo::{wkt, MakeValid, Validation};
+/// use geo::MakeValidStrategy;
+///
+/// let polygon = wkt!(POLYGON(
+///     (0. 0.,2. 0.,2. 2.,0. 2.,0. 0.),
+///     (3. 0.,4. 0.,4. 1.,3. 1.,3. 0.)
+/// ));
+///
+/// let linework = polygon.make_valid_with_strategy(MakeValidStrategy::Linework);
+/// assert_eq!(linework.0.len(), 2);
+///
+/// let structure = polygon.make_valid_with_strategy(MakeValidStrategy::Structure);
+/// assert_eq!(structure.0.len(), 1);
+/// ```
+pub trait MakeValid {
+    type Output;
+
+    /// Repair the geometry with the default [`MakeValidStrategy`].
+    fn make_valid(&self) -> Self::Output {
+        self.make_valid_with_strategy(MakeValidStrategy::default())
+    }
+
+    /// Repair the geometry, rebuilding areas with the given strategy.
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output;
+}
+
+fn is_finite<T: GeoFloat>(coord: &Coord<T>) -> bool {
+    coord.x.is_finite() && coord.y.is_finite()
+}
+
+/// The finite coordinates of a line, if there are at least two distinct ones.
+fn valid_line_string<T: GeoFloat>(line_string: &LineString<T>) -> Option<LineString<T>> {
+    let coords: Vec<Coord<T>> = line_string.coords().copied().filter(is_finite).collect();
+    let first = *coords.first()?;
+    coords
+        .iter()
+        .any(|&coord| coord != first)
+        .then(|| LineString::new(coords))
+}
+
+/// The finite coordinates of a ring.
+fn finite_ring<T: GeoFloat>(ring: &LineString<T>) -> LineString<T> {
+    LineString::new(ring.coords().copied().filter(is_finite).collect())
+}
+
+fn make_valid_polygons<'a, T: BoolOpsNum + GeoFloat + 'a>(
+    polygons: impl IntoIterator<Item = &'a Polygon<T>>,
+    strategy: MakeValidStrategy,
+) -> MultiPolygon<T> {
+    match strategy {
+        MakeValidStrategy::Linework => {
+            let rings: Vec<LineString<T>> = polygons
+                .into_iter()
+                .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+                .map(finite_ring)
+                .collect();
+            even_odd_area(&rings)
+        }
+        MakeValidStrategy::Structure => {
+            let areas: Vec<MultiPolygon<T>> = polygons
+                .into_iter()
+                .map(|polygon| {
+                    let exterior = even_odd_area([&finite_ring(polygon.exterior())]);
+                    let interiors: Vec<MultiPolygon<T>> = polygon
+                        .interiors()
+                        .iter()
+                        .map(|interior| even_odd_area([&finite_ring(interior)]))
+                        .collect();
+                    if interiors.is_empty() {
+                        exterior
+                    } else {
+                        exterior.difference(&unary_union(&interiors))
+                    }
+                })
+                .collect();
+            match areas.len() {
+                0 => MultiPolygon::new(vec![]),
+                1 => areas.into_iter().next().unwrap(),
+                _ => unary_union(&areas),
+            }
+        }
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for Point<T> {
+    type Output = Option<Point<T>>;
+
+    fn make_valid_with_strategy(&self, _strategy: MakeValidStrategy) -> Self::Output {
+        is_finite(&self.0).then_some(*self)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for MultiPoint<T> {
+    type Output = MultiPoint<T>;
+
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        self.iter()
+            .filter_map(|point| point.make_valid_with_strategy(strategy))
+            .collect()
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for Line<T> {
+    type Output = Option<Line<T>>;
+
+    fn make_valid_with_strategy(&self, _strategy: MakeValidStrategy) -> Self::Output {
+        (is_finite(&self.start) && is_finite(&self.end) && self.start != self.end).then_some(*self)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for LineString<T> {
+    type Output = LineString<T>;
+
+    /// A line which collapses to a point is replaced by an empty `LineString`.
+    fn make_valid_with_strategy(&self, _strategy: MakeValidStrategy) -> Self::Output {
+        valid_line_string(self).unwrap_or_else(|| LineString::new(vec![]))
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for MultiLineString<T> {
+    type Output = MultiLineString<T>;
+
+    fn make_valid_with_strategy(&self, _strategy: MakeValidStrategy) -> Self::Output {
+        self.iter().filter_map(valid_line_string).collect()
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for Polygon<T> {
+    type Output = MultiPolygon<T>;
+
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        make_valid_polygons([self], strategy)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for MultiPolygon<T> {
+    type Output = MultiPolygon<T>;
+
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        make_valid_polygons(self, strategy)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for Rect<T> {
+    type Output = MultiPolygon<T>;
+
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        self.to_polygon().make_valid_with_strategy(strategy)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for Triangle<T> {
+    type Output = MultiPolygon<T>;
+
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        self.to_polygon().make_valid_with_strategy(strategy)
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for GeometryCollection<T> {
+    type Output = GeometryCollection<T>;
+
+    /// Members which are entirely removed are dropped from the collection.
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        self.iter()
+            .map(|geometry| geometry.make_valid_with_strategy(strategy))
+            .filter(|geometry| match geometry {
+                Geometry::MultiPoint(g) => !g.0.is_empty(),
+                Geometry::LineString(g) => !g.0.is_empty(),
+                Geometry::MultiLineString(g) => !g.0.is_empty(),
+                Geometry::MultiPolygon(g) => !g.0.is_empty(),
+                Geometry::GeometryCollection(g) => !g.0.is_empty(),
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+impl<T: BoolOpsNum + GeoFloat> MakeValid for Geometry<T> {
+    type Output = Geometry<T>;
+
+    /// Areal geometries are repaired into a `MultiPolygon`. A `Point` or `Line` which can't be
+    /// repaired is replaced by an empty `MultiPoint` or `MultiLineString` respectively.
+    fn make_valid_with_strategy(&self, strategy: MakeValidStrategy) -> Self::Output {
+        match self {
+            Geometry::Point(g) => match g.make_valid_with_strategy(strategy) {
+                Some(point) => Geometry::Point(point),
+                None => Geometry::MultiPoint(MultiPoint::new(vec![])),
+            },
+            Geometry::Line(g) => match g.make_valid_with_strategy(strategy) {
+                Some(line) => Geometry::Line(line),
+                None => Geometry::MultiLineString(MultiLineString::new(vec![])),
+            },
+            Geometry::LineString(g) => Geometry::LineString(g.make_valid_with_strategy(strategy)),
+            Geometry::Polygon(g) => Geometry::MultiPolygon(g.make_valid_with_strategy(strategy)),
+            Geometry::MultiPoint(g) => Geometry::MultiPoint(g.make_valid_with_strategy(strategy)),
+            Geometry::MultiLineString(g) => {
+                Geometry::MultiLineString(g.make_valid_with_strategy(strategy))
+            }
+            Geometry::MultiPolygon(g) => {
+                Geometry::MultiPolygon(g.make_valid_with_strategy(strategy))
+            }
+            Geometry::GeometryCollection(g) => {
+                Geometry::GeometryCollection(g.make_valid_with_strategy(strategy))
+            }
+            Geometry::Rect(g) => Geometry::MultiPolygon(g.make_valid_with_strategy(strategy)),
+            Geometry::Triangle(g) => Geometry::MultiPolygon(g.make_valid_with_strategy(strategy)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Area, Relate, Validation};
+
+    fn assert_fixed(polygon: &Polygon<f64>, strategy: MakeValidStrategy, expected: MultiPolygon) {
+        let fixed = polygon.make_valid_with_strategy(strategy);
+        assert!(fixed.is_valid(), "{:?}", fixed.validation_errors());
+        assert!(
+            fixed.relate(&expected).is_equal_topo(),
+            "expected {expected:?}, got {fixed:?}"
+        );
+    }
+
+    #[test]
+    fn bow_tie() {
+        let bow_tie = wkt!(POLYGON((0. 0.,2. 2.,2. 0.,0. 2.,0. 0.)));
+        let expected = wkt!(MULTIPOLYGON(((0. 0.,1. 1.,0. 2.,0. 0.)),((1. 1.,2. 0.,2. 2.,1. 1.))));
+        assert_fixed(&bow_tie, MakeValidStrategy::Linework, expected.clone());
+        assert_fixed(&bow_tie, MakeValidStrategy::Structure, expected);
+    }
+
+    #[test]
+    fn too_few_points() {
+        let polygon = wkt!(POLYGON((0. 0.,1. 1.,0. 0.)));
+        assert!(polygon.make_valid().0.is_empty());
+
+        // a collapsed hole is dropped
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.),(1. 1.,2. 2.,1. 1.)));
+        let expected = wkt!(MULTIPOLYGON(((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.))));
+        assert_fixed(&polygon, MakeValidStrategy::Linework, expected.clone());
+        assert_fixed(&polygon, MakeValidStrategy::Structure, expected);
+    }
+
+    #[test]
+    fn unclosed_ring() {
+        let polygon = Polygon::new(LineString::from(vec![(0., 0.), (2., 0.), (2., 2.)]), vec![]);
+        let expected = wkt!(MULTIPOLYGON(((0. 0.,2. 0.,2. 2.,0. 0.))));
+        assert_fixed(&polygon, MakeValidStrategy::Linework, expected);
+    }
+
+    #[test]
+    fn overlapping_holes() {
+        let polygon = wkt!(POLYGON(
+            (0. 0.,6. 0.,6. 6.,0. 6.,0. 0.),
+            (1. 1.,4. 1.,4. 4.,1. 4.,1. 1.),
+            (2. 2.,5. 2.,5. 5.,2. 5.,2. 2.)
+        ));
+
+        // the overlap of the holes is enclosed by three rings
+        let fixed = polygon.make_valid_with_strategy(MakeValidStrategy::Linework);
+        assert!(fixed.is_valid());
+        assert_eq!(fixed.unsigned_area(), 36. - 18. + 2. * 4.);
+
+        let fixed = polygon.make_valid_with_strategy(MakeValidStrategy::Structure);
+        assert!(fixed.is_valid());
+        assert_eq!(fixed.unsigned_area(), 36. - 14.);
+    }
+
+    #[test]
+    fn overlapping_polygons() {
+        let multi_polygon = wkt!(MULTIPOLYGON(
+            ((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)),
+            ((1. 1.,3. 1.,3. 3.,1. 3.,1. 1.))
+        ));
+        assert!(!multi_polygon.is_valid());
+
+        let linework = multi_polygon.make_valid_with_strategy(MakeValidStrategy::Linework);
+        assert!(linework.is_valid());
+        assert_eq!(linework.unsigned_area(), 6.);
+
+        let structure = multi_polygon.make_valid_with_strategy(MakeValidStrategy::Structure);
+        assert!(structure.is_valid());
+        assert_eq!(structure.unsigned_area(), 7.);
+    }
+
+    #[test]
+    fn lines_and_points() {
+        assert_eq!(wkt!(POINT(1. 2.)).make_valid(), Some(wkt!(POINT(1. 2.))));
+        assert_eq!(Point::new(f64::NAN, 2.).make_valid(), None);
+
+        let line_string = LineString::from(vec![(0., 0.), (f64::INFINITY, 1.), (1., 1.)]);
+        assert_eq!(line_string.make_valid(), wkt!(LINESTRING(0. 0.,1. 1.)));
+
+        let multi_line_string = wkt!(MULTILINESTRING((0. 0.,0. 0.),(0. 0.,1. 1.)));
+        assert_eq!(
+            multi_line_string.make_valid(),
+            wkt!(MULTILINESTRING((0. 0.,1. 1.)))
+        );
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let collection = wkt!(GEOMETRYCOLLECTION(
+            POINT(1. 1.),
+            LINESTRING(0. 0.,0. 0.),
+            POLYGON((0. 0.,2. 2.,2. 0.,0. 2.,0. 0.))
+        ));
+        let fixed = collection.make_valid();
+        assert!(fixed.is_valid());
+        assert_eq!(fixed.0.len(), 2);
+    }
+}
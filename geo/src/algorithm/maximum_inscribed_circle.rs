@@ -0,0 +1,260 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::geometry::*;
+use crate::{coord, BoundingRect, Centroid, Contains, Distance, Euclidean, GeoFloat};
+
+/// A circle inscribed in a polygon, see [`MaximumInscribedCircle`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InscribedCircle<T: GeoFloat> {
+    /// The center of the circle, which is the point inside the polygon furthest from its boundary
+    pub center: Point<T>,
+    /// The radius of the circle: the distance from the center to the boundary of the polygon
+    pub radius: T,
+}
+
+/// Find the largest circle which fits inside a polygon.
+///
+/// The center of the circle is the _pole of inaccessibility_ of the polygon: the interior point
+/// which is furthest from its boundary. Unlike [`InteriorPoint`](crate::InteriorPoint), it lies
+/// in a visually central position, which makes it a good anchor for a label.
+///
+/// The circle is found by subdividing the bounding rectangle of the polygon into ever smaller
+/// cells, like the [polylabel](https://github.com/mapbox/polylabel) algorithm. The returned
+/// radius is within `tolerance` of the true maximum, which is approached quickly, but not
+/// necessarily the returned center.
+///
+/// Returns `None` for an empty geometry, or if `tolerance` isn't a positive, finite number.
+///
+/// # Examples
+///
+/// ```
+/// use approx::assert_relative_eq;
+/// use geo::{wkt, MaximumInscribedCircle};
+///
+/// // a square with a thin spike
+/// let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 6.,-20. 5.,0. 4.,0. 0.)));
+///
+/// let circle = polygon.maximum_inscribed_circle(0.01).unwrap();
+/// assert_relative_eq!(circle.radius, 5., epsilon = 0.01);
+/// assert_relative_eq!(circle.center, wkt!(POINT(5. 5.)), epsilon = 0.1);
+/// ```
+pub trait MaximumInscribedCircle<T: GeoFloat> {
+    fn maximum_inscribed_circle(&self, tolerance: T) -> Option<InscribedCircle<T>>;
+}
+
+impl<T: GeoFloat> MaximumInscribedCircle<T> for Polygon<T> {
+    fn maximum_inscribed_circle(&self, tolerance: T) -> Option<InscribedCircle<T>> {
+        let rings = std::iter::once(self.exterior()).chain(self.interiors());
+        maximum_inscribed_circle(self, rings.collect(), tolerance)
+    }
+}
+
+impl<T: GeoFloat> MaximumInscribedCircle<T> for MultiPolygon<T> {
+    fn maximum_inscribed_circle(&self, tolerance: T) -> Option<InscribedCircle<T>> {
+        let rings = self
+            .iter()
+            .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()));
+        maximum_inscribed_circle(self, rings.collect(), tolerance)
+    }
+}
+
+/// A square cell of the search, with the signed distance from its center to the boundary.
+#[derive(Clone, Copy)]
+struct Cell<T: GeoFloat> {
+    center: Coord<T>,
+    half_size: T,
+    distance: T,
+    /// The largest distance to the boundary of any point in the cell
+    max_distance: T,
+}
+
+impl<T: GeoFloat> Cell<T> {
+    fn new<G>(center: Coord<T>, half_size: T, geometry: &G, rings: &[&LineString<T>]) -> Self
+    where
+        G: Contains<Coord<T>>,
+    {
+        let distance = signed_distance(center, geometry, rings);
+        Cell {
+            center,
+            half_size,
+            distance,
+            max_distance: distance + half_size * T::from(2).unwrap().sqrt(),
+        }
+    }
+}
+
+impl<T: GeoFloat> PartialEq for Cell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_distance == other.max_distance
+    }
+}
+
+impl<T: GeoFloat> Eq for Cell<T> {}
+
+impl<T: GeoFloat> PartialOrd for Cell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: GeoFloat> Ord for Cell<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.max_distance.total_cmp(&other.max_distance)
+    }
+}
+
+/// The distance from `coord` to the boundary, which is negative outside of the geometry.
+fn signed_distance<T: GeoFloat, G: Contains<Coord<T>>>(
+    coord: Coord<T>,
+    geometry: &G,
+    rings: &[&LineString<T>],
+) -> T {
+    let distance = rings
+        .iter()
+        .map(|ring| Euclidean.distance(&Point(coord), *ring))
+        .fold(T::infinity(), T::min);
+    if geometry.contains(&coord) {
+        distance
+    } else {
+        -distance
+    }
+}
+
+fn maximum_inscribed_circle<T, G>(
+    geometry: &G,
+    rings: Vec<&LineString<T>>,
+    tolerance: T,
+) -> Option<InscribedCircle<T>>
+where
+    T: GeoFloat,
+    G: BoundingRect<T, Output = Option<Rect<T>>> + Centroid<Output = Option<Point<T>>>,
+    G: Contains<Coord<T>>,
+{
+    if !(tolerance > T::zero() && tolerance.is_finite()) {
+        return None;
+    }
+    let bounds = geometry.bounding_rect()?;
+    let two = T::from(2).unwrap();
+
+    let cell_size = bounds.width().min(bounds.height());
+    if cell_size == T::zero() {
+        // a collapsed polygon has no interior to fit a circle in
+        return Some(InscribedCircle {
+            center: bounds.center().into(),
+            radius: T::zero(),
+        });
+    }
+    let half_size = cell_size / two;
+
+    // cover the bounding rectangle with square cells
+    let mut queue = BinaryHeap::new();
+    let mut x = bounds.min().x;
+    while x < bounds.max().x {
+        let mut y = bounds.min().y;
+        while y < bounds.max().y {
+            let center = coord! { x: x + half_size, y: y + half_size };
+            queue.push(Cell::new(center, half_size, geometry, &rings));
+            y = y + cell_size;
+        }
+        x = x + cell_size;
+    }
+
+    // the centroid and the center of the bounds are good first guesses
+    let mut best = Cell::new(bounds.center(), T::zero(), geometry, &rings);
+    if let Some(centroid) = geometry.centroid() {
+        let cell = Cell::new(centroid.0, T::zero(), geometry, &rings);
+        if cell.distance > best.distance {
+            best = cell;
+        }
+    }
+
+    while let Some(cell) = queue.pop() {
+        if cell.distance > best.distance {
+            best = cell;
+        }
+        // the cells are visited by decreasing potential, so none of the others can do better
+        if cell.max_distance - best.distance <= tolerance {
+            break;
+        }
+        let half_size = cell.half_size / two;
+        for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let center = coord! {
+                x: cell.center.x + half_size * T::from(dx).unwrap(),
+                y: cell.center.y + half_size * T::from(dy).unwrap(),
+            };
+            queue.push(Cell::new(center, half_size, geometry, &rings));
+        }
+    }
+
+    Some(InscribedCircle {
+        center: best.center.into(),
+        radius: best.distance.max(T::zero()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt;
+
+    #[test]
+    fn square() {
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+        let circle = polygon.maximum_inscribed_circle(1e-6).unwrap();
+        assert_eq!(circle.center, wkt!(POINT(2. 2.)));
+        assert_eq!(circle.radius, 2.);
+    }
+
+    #[test]
+    fn tolerance_is_respected() {
+        // an L shape, whose widest point is in the corner, touching both outer sides and the
+        // inner corner
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 2.,2. 2.,2. 10.,0. 10.,0. 0.)));
+        let radius = 4. - 2. * 2f64.sqrt();
+        for tolerance in [1., 0.1, 0.001] {
+            let circle = polygon.maximum_inscribed_circle(tolerance).unwrap();
+            assert!(polygon.contains(&circle.center));
+            assert!(circle.radius <= radius + 1e-12);
+            assert!(circle.radius >= radius - tolerance);
+        }
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let polygon = wkt!(POLYGON(
+            (0. 0.,10. 0.,10. 10.,0. 10.,0. 0.),
+            (1. 1.,9. 1.,9. 7.,1. 7.,1. 1.)
+        ));
+        let circle = polygon.maximum_inscribed_circle(1e-3).unwrap();
+        assert_relative_eq!(circle.radius, 1.5, epsilon = 1e-3);
+        assert_relative_eq!(circle.center.y(), 8.5, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn multi_polygon() {
+        let multi_polygon = wkt!(MULTIPOLYGON(
+            ((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)),
+            ((10. 0.,16. 0.,16. 6.,10. 6.,10. 0.))
+        ));
+        let circle = multi_polygon.maximum_inscribed_circle(1e-3).unwrap();
+        assert_relative_eq!(circle.radius, 3., epsilon = 1e-3);
+        assert_relative_eq!(circle.center, wkt!(POINT(13. 3.)), epsilon = 1e-2);
+    }
+
+    #[test]
+    fn degenerate() {
+        assert!(wkt!(POLYGON EMPTY).maximum_inscribed_circle(1.0).is_none());
+        let collapsed = wkt!(POLYGON((0. 0.,4. 0.,2. 0.,0. 0.)));
+        let circle = collapsed.maximum_inscribed_circle(1.0).unwrap();
+        assert_eq!(circle.radius, 0.);
+    }
+
+    #[test]
+    fn invalid_tolerance() {
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+        for tolerance in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(polygon.maximum_inscribed_circle(tolerance).is_none());
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::geometry::*;
+use crate::{coord, ConvexHull, CoordsIter, GeoFloat};
+
+/// The smallest circle enclosing a geometry, see [`MinimumBoundingCircle`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundingCircle<T: GeoFloat> {
+    /// The center of the circle
+    pub center: Point<T>,
+    /// The radius of the circle, which is half the diameter of the geometry
+    pub radius: T,
+    /// The vertices of the geometry which lie on the circle and determine it: one for a geometry
+    /// with a single distinct vertex, two on opposite sides of the circle, or three which form
+    /// an acute triangle
+    pub defining_points: Vec<Point<T>>,
+}
+
+/// Calculate the smallest circle which encloses all vertices of a geometry.
+///
+/// The circle is found with Welzl's algorithm, in the iterative form of de Berg et al., on the
+/// vertices of the [`ConvexHull`]. Its diameter is a measure of the size of the geometry which
+/// doesn't depend on its orientation.
+///
+/// Returns `None` for an empty geometry.
+///
+/// # Examples
+///
+/// ```
+/// use approx::assert_relative_eq;
+/// use geo::{wkt, MinimumBoundingCircle};
+///
+/// let polygon = wkt!(POLYGON((0. 0.,6. 0.,5. 2.,3. 4.,0. 0.)));
+///
+/// let circle = polygon.minimum_bounding_circle().unwrap();
+/// assert_relative_eq!(circle.center, wkt!(POINT(3. 0.875)));
+/// assert_relative_eq!(circle.radius, 3.125);
+/// assert_eq!(circle.defining_points.len(), 3);
+/// ```
+pub trait MinimumBoundingCircle<T: GeoFloat> {
+    fn minimum_bounding_circle(&self) -> Option<BoundingCircle<T>>;
+}
+
+impl<T, G> MinimumBoundingCircle<T> for G
+where
+    T: GeoFloat,
+    G: CoordsIter<Scalar = T>,
+{
+    fn minimum_bounding_circle(&self) -> Option<BoundingCircle<T>> {
+        let hull = self.convex_hull();
+        let mut points: Vec<Coord<T>> = hull.exterior().0.clone();
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        shuffle(&mut points);
+        let circle = minimum_circle(&points)?;
+        Some(BoundingCircle {
+            center: circle.center.into(),
+            radius: circle.radius,
+            defining_points: circle.points.into_iter().map(Point::from).collect(),
+        })
+    }
+}
+
+/// A circle through up to three points.
+struct Circle<T: GeoFloat> {
+    center: Coord<T>,
+    radius: T,
+    points: Vec<Coord<T>>,
+}
+
+impl<T: GeoFloat> Circle<T> {
+    fn from_diameter(a: Coord<T>, b: Coord<T>) -> Self {
+        let center = (a + b) / (T::one() + T::one());
+        Circle {
+            center,
+            radius: distance(center, a).max(distance(center, b)),
+            points: vec![a, b],
+        }
+    }
+
+    /// The circle through three points, or `None` if they are collinear.
+    fn from_triangle(a: Coord<T>, b: Coord<T>, c: Coord<T>) -> Option<Self> {
+        let (ab, ac) = (b - a, c - a);
+        let d = (ab.x * ac.y - ab.y * ac.x) * (T::one() + T::one());
+        if d == T::zero() {
+            return None;
+        }
+        let (ab_2, ac_2) = (ab.x * ab.x + ab.y * ab.y, ac.x * ac.x + ac.y * ac.y);
+        let center = a + coord! {
+            x: (ac.y * ab_2 - ab.y * ac_2) / d,
+            y: (ab.x * ac_2 - ac.x * ab_2) / d,
+        };
+        let radius = distance(center, a)
+            .max(distance(center, b))
+            .max(distance(center, c));
+        Some(Circle {
+            center,
+            radius,
+            points: vec![a, b, c],
+        })
+    }
+
+    fn contains(&self, coord: Coord<T>) -> bool {
+        // allow for rounding errors in the center and radius
+        let tolerance = T::epsilon() * T::from(64).unwrap();
+        distance(self.center, coord) <= self.radius * (T::one() + tolerance)
+    }
+}
+
+fn distance<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> T {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// Welzl's algorithm runs in expected linear time if the points are in random order. The points
+/// of a convex hull are in the worst possible order, so they are permuted with a fixed seed.
+fn shuffle<T>(items: &mut [T]) {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    for i in (1..items.len()).rev() {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        items.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+fn minimum_circle<T: GeoFloat>(points: &[Coord<T>]) -> Option<Circle<T>> {
+    let (&first, rest) = points.split_first()?;
+    let mut circle = Circle {
+        center: first,
+        radius: T::zero(),
+        points: vec![first],
+    };
+    for (i, &p) in rest.iter().enumerate() {
+        if !circle.contains(p) {
+            circle = circle_with_point(&points[..=i], p);
+        }
+    }
+    Some(circle)
+}
+
+/// The smallest circle enclosing `points` with `p` on its boundary.
+fn circle_with_point<T: GeoFloat>(points: &[Coord<T>], p: Coord<T>) -> Circle<T> {
+    let mut circle = Circle::from_diameter(points[0], p);
+    for (i, &q) in points.iter().enumerate().skip(1) {
+        if !circle.contains(q) {
+            circle = circle_with_points(&points[..i], p, q);
+        }
+    }
+    circle
+}
+
+/// The smallest circle enclosing `points` with `p` and `q` on its boundary.
+fn circle_with_points<T: GeoFloat>(points: &[Coord<T>], p: Coord<T>, q: Coord<T>) -> Circle<T> {
+    let mut circle = Circle::from_diameter(p, q);
+    for &r in points {
+        if !circle.contains(r) {
+            // r can only be collinear with p and q due to rounding errors
+            circle = Circle::from_triangle(p, q, r).unwrap_or_else(|| {
+                let pairs = [(p, q), (p, r), (q, r)];
+                let (a, b) = pairs
+                    .into_iter()
+                    .max_by(|(a, b), (c, d)| distance(*a, *b).total_cmp(&distance(*c, *d)))
+                    .unwrap();
+                Circle::from_diameter(a, b)
+            });
+        }
+    }
+    circle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Distance, Euclidean};
+
+    #[test]
+    fn empty_and_single_point() {
+        assert!(MultiPoint::<f64>::new(vec![])
+            .minimum_bounding_circle()
+            .is_none());
+
+        let circle = wkt!(MULTIPOINT(1. 2.,1. 2.))
+            .minimum_bounding_circle()
+            .unwrap();
+        assert_eq!(circle.center, wkt!(POINT(1. 2.)));
+        assert_eq!(circle.radius, 0.);
+        assert_eq!(circle.defining_points, vec![wkt!(POINT(1. 2.))]);
+    }
+
+    #[test]
+    fn diameter() {
+        let line_string = wkt!(LINESTRING(0. 0.,1. 1.,4. 0.,2. 1.));
+        let circle = line_string.minimum_bounding_circle().unwrap();
+        assert_eq!(circle.center, wkt!(POINT(2. 0.)));
+        assert_eq!(circle.radius, 2.);
+        assert_eq!(circle.defining_points.len(), 2);
+    }
+
+    #[test]
+    fn encloses_all_points() {
+        // points on a spiral, whose hull has many vertices
+        let points: MultiPoint = (0..500)
+            .map(|i| {
+                let angle = i as f64 * 0.1;
+                Point::new(angle.cos() * (1. + angle), angle.sin() * (1. + angle))
+            })
+            .collect();
+        let circle = points.minimum_bounding_circle().unwrap();
+        for point in &points {
+            assert!(Euclidean.distance(circle.center, *point) <= circle.radius * (1. + 1e-9));
+        }
+        for point in &circle.defining_points {
+            assert_relative_eq!(Euclidean.distance(circle.center, *point), circle.radius);
+        }
+        assert!(circle.defining_points.len() >= 2);
+    }
+}
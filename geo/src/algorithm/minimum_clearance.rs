@@ -0,0 +1,308 @@
+use rstar::primitives::GeomWithData;
+use rstar::RTree;
+
+use crate::geometry::*;
+use crate::{CoordsIter, GeoFloat, LinesIter};
+
+/// Calculate the minimum clearance of a geometry.
+///
+/// The minimum clearance is the smallest distance by which a vertex of the geometry could be
+/// moved so that the geometry becomes invalid or topologically different, e.g. so that a polygon
+/// self-intersects or two of its rings touch. It is the smallest distance between two distinct
+/// vertices, or between a vertex and a segment which it isn't an endpoint of.
+///
+/// A geometry with a small clearance relative to its size is fragile: snapping it to a grid or
+/// reducing its precision may well make it invalid, see [`Validation`](crate::Validation).
+///
+/// Repeated vertices count as one vertex. The members of a collection are measured together,
+/// so its clearance may be the distance between two of them.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{coord, wkt, Line, MinimumClearance};
+///
+/// // a spike which almost touches the opposite side of the polygon
+/// let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,5. 0.1,0. 10.,0. 0.)));
+///
+/// assert_eq!(polygon.minimum_clearance(), Some(0.1));
+/// assert_eq!(polygon.minimum_clearance_line(), Some(Line::new(coord! { x: 5., y: 0.1 }, coord! { x: 5., y: 0. })));
+/// ```
+pub trait MinimumClearance<T: GeoFloat> {
+    /// The minimum clearance distance, or `None` if the geometry has fewer than two distinct
+    /// vertices.
+    fn minimum_clearance(&self) -> Option<T> {
+        self.minimum_clearance_line()
+            .map(|line| line.dx().hypot(line.dy()))
+    }
+
+    /// A line which achieves the minimum clearance, from a vertex to the nearest point of another
+    /// vertex or segment, or `None` if the geometry has fewer than two distinct vertices.
+    ///
+    /// If several lines achieve the minimum clearance, a line between two vertices is preferred
+    /// over a line to a segment, and otherwise the first in the order of the vertices and
+    /// segments.
+    fn minimum_clearance_line(&self) -> Option<Line<T>>;
+}
+
+impl<T: GeoFloat, G: private::Facets<T>> MinimumClearance<T> for G {
+    fn minimum_clearance_line(&self) -> Option<Line<T>> {
+        let mut vertices = vec![];
+        let mut segments = vec![];
+        self.facets(&mut vertices, &mut segments);
+        minimum_clearance_line(&vertices, &segments)
+    }
+}
+
+mod private {
+    use super::*;
+
+    /// The vertices and segments of a geometry.
+    pub trait Facets<T: GeoFloat> {
+        fn facets(&self, vertices: &mut Vec<Coord<T>>, segments: &mut Vec<Line<T>>);
+    }
+}
+
+macro_rules! impl_facets_for_points {
+    ($($type:ident),*) => {
+        $(
+            impl<T: GeoFloat> private::Facets<T> for $type<T> {
+                fn facets(&self, vertices: &mut Vec<Coord<T>>, _segments: &mut Vec<Line<T>>) {
+                    vertices.extend(self.coords_iter());
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_facets_for_lines {
+    ($($type:ident),*) => {
+        $(
+            impl<T: GeoFloat> private::Facets<T> for $type<T> {
+                fn facets(&self, vertices: &mut Vec<Coord<T>>, segments: &mut Vec<Line<T>>) {
+                    vertices.extend(self.coords_iter());
+                    segments.extend(self.lines_iter());
+                }
+            }
+        )*
+    };
+}
+
+impl_facets_for_points!(Point, MultiPoint);
+impl_facets_for_lines!(
+    Line,
+    LineString,
+    MultiLineString,
+    Polygon,
+    MultiPolygon,
+    Rect,
+    Triangle
+);
+
+impl<T: GeoFloat> private::Facets<T> for GeometryCollection<T> {
+    fn facets(&self, vertices: &mut Vec<Coord<T>>, segments: &mut Vec<Line<T>>) {
+        for geometry in self {
+            geometry.facets(vertices, segments);
+        }
+    }
+}
+
+impl<T: GeoFloat> private::Facets<T> for Geometry<T> {
+    fn facets(&self, vertices: &mut Vec<Coord<T>>, segments: &mut Vec<Line<T>>) {
+        match self {
+            Geometry::Point(g) => g.facets(vertices, segments),
+            Geometry::Line(g) => g.facets(vertices, segments),
+            Geometry::LineString(g) => g.facets(vertices, segments),
+            Geometry::Polygon(g) => g.facets(vertices, segments),
+            Geometry::MultiPoint(g) => g.facets(vertices, segments),
+            Geometry::MultiLineString(g) => g.facets(vertices, segments),
+            Geometry::MultiPolygon(g) => g.facets(vertices, segments),
+            Geometry::GeometryCollection(g) => g.facets(vertices, segments),
+            Geometry::Rect(g) => g.facets(vertices, segments),
+            Geometry::Triangle(g) => g.facets(vertices, segments),
+        }
+    }
+}
+
+/// A candidate for the minimum clearance line.
+///
+/// Candidates at the same distance are ordered like a brute force search over the pairs of
+/// vertices, followed by the pairs of vertex and segment, would find them.
+struct Candidate<T: GeoFloat> {
+    distance: T,
+    /// 0 for a pair of vertices, 1 for a vertex and a segment
+    kind: u8,
+    indices: (usize, usize),
+    line: Line<T>,
+}
+
+impl<T: GeoFloat> Candidate<T> {
+    fn is_better_than(&self, other: &Option<Candidate<T>>) -> bool {
+        match other {
+            None => true,
+            Some(other) => {
+                self.distance < other.distance
+                    || (self.distance == other.distance
+                        && (self.kind, self.indices) < (other.kind, other.indices))
+            }
+        }
+    }
+
+    /// Whether an index query can stop at the squared `distance_2`, without missing a better
+    /// candidate than `best`.
+    fn is_beyond(best: &Option<Candidate<T>>, distance_2: T) -> bool {
+        best.as_ref().is_some_and(|best| {
+            // the index computes distances differently, so leave some slack to not miss ties
+            let best_2 = best.distance * best.distance;
+            let slack = T::epsilon() * T::from(16).unwrap() * best_2;
+            distance_2 > best_2 + slack
+        })
+    }
+}
+
+fn minimum_clearance_line<T: GeoFloat>(
+    vertices: &[Coord<T>],
+    segments: &[Line<T>],
+) -> Option<Line<T>> {
+    let mut best: Option<Candidate<T>> = None;
+
+    let vertex_tree = RTree::bulk_load(
+        vertices
+            .iter()
+            .enumerate()
+            .map(|(index, vertex)| GeomWithData::new(Point(*vertex), index))
+            .collect(),
+    );
+    for (index, vertex) in vertices.iter().enumerate() {
+        for (other, distance_2) in
+            vertex_tree.nearest_neighbor_iter_with_distance_2(&Point(*vertex))
+        {
+            if Candidate::is_beyond(&best, distance_2) {
+                break;
+            }
+            let other_vertex = other.geom().0;
+            if other_vertex == *vertex {
+                continue;
+            }
+            let (first, second) = if index < other.data {
+                (*vertex, other_vertex)
+            } else {
+                (other_vertex, *vertex)
+            };
+            let candidate = Candidate {
+                distance: (first - second).x.hypot((first - second).y),
+                kind: 0,
+                indices: (index.min(other.data), index.max(other.data)),
+                line: Line::new(first, second),
+            };
+            if candidate.is_better_than(&best) {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    let segment_tree = RTree::bulk_load(
+        segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| GeomWithData::new(*segment, index))
+            .collect(),
+    );
+    for (index, vertex) in vertices.iter().enumerate() {
+        for (segment, distance_2) in
+            segment_tree.nearest_neighbor_iter_with_distance_2(&Point(*vertex))
+        {
+            if Candidate::is_beyond(&best, distance_2) {
+                break;
+            }
+            let line = *segment.geom();
+            if line.start == *vertex || line.end == *vertex {
+                continue;
+            }
+            let nearest = nearest_point_on_segment(*vertex, line);
+            let candidate = Candidate {
+                distance: (*vertex - nearest).x.hypot((*vertex - nearest).y),
+                kind: 1,
+                indices: (index, segment.data),
+                line: Line::new(*vertex, nearest),
+            };
+            if candidate.is_better_than(&best) {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    best.map(|candidate| candidate.line)
+}
+
+fn nearest_point_on_segment<T: GeoFloat>(coord: Coord<T>, segment: Line<T>) -> Coord<T> {
+    let delta = segment.delta();
+    let length_2 = delta.x * delta.x + delta.y * delta.y;
+    if length_2 == T::zero() {
+        return segment.start;
+    }
+    let offset = coord - segment.start;
+    let fraction = (offset.x * delta.x + offset.y * delta.y) / length_2;
+    if fraction <= T::zero() {
+        segment.start
+    } else if fraction >= T::one() {
+        segment.end
+    } else {
+        segment.start + delta * fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coord, wkt};
+
+    #[test]
+    fn points() {
+        assert_eq!(wkt!(POINT(1. 1.)).minimum_clearance(), None);
+        assert_eq!(wkt!(MULTIPOINT(1. 1.,1. 1.)).minimum_clearance(), None);
+        assert_eq!(
+            wkt!(MULTIPOINT(100. 100.,10. 100.,30. 100.)).minimum_clearance_line(),
+            Some(Line::new(
+                coord! { x: 10., y: 100. },
+                coord! { x: 30., y: 100. }
+            ))
+        );
+    }
+
+    #[test]
+    fn vertex_to_segment() {
+        let line_string = wkt!(LINESTRING(100. 100.,200. 100.,200. 200.,150. 150.));
+        assert_eq!(line_string.minimum_clearance(), Some(50.));
+        assert_eq!(
+            line_string.minimum_clearance_line(),
+            Some(Line::new(
+                coord! { x: 150., y: 150. },
+                coord! { x: 150., y: 100. }
+            ))
+        );
+    }
+
+    #[test]
+    fn repeated_vertices() {
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 0.,4. 3.,0. 0.)));
+        assert_relative_eq!(polygon.minimum_clearance().unwrap(), 2.4);
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let collection = wkt!(GEOMETRYCOLLECTION(
+            POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)),
+            POINT(2. 5.)
+        ));
+        assert_eq!(
+            Geometry::GeometryCollection(collection).minimum_clearance_line(),
+            Some(Line::new(coord! { x: 2., y: 5. }, coord! { x: 2., y: 4. }))
+        );
+    }
+
+    #[test]
+    fn jts_test_suite() {
+        jts_test_runner::assert_jts_tests_succeed("*MinimumClearance*.xml");
+    }
+}
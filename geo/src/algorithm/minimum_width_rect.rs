@@ -0,0 +1,152 @@
+use crate::geometry::*;
+use crate::{coord, ConvexHull, CoordsIter, GeoFloat};
+
+/// The narrowest rectangle enclosing a geometry, see [`MinimumWidthRect`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidthRect<T: GeoFloat> {
+    /// The rectangle, which may be rotated. It has zero area if the geometry is a point or its
+    /// vertices are collinear
+    pub rect: Polygon<T>,
+    /// The width of the geometry: the length of the shorter side of the rectangle
+    pub width: T,
+}
+
+/// Calculate the narrowest rectangle which encloses all vertices of a geometry, in any
+/// orientation.
+///
+/// The width of the rectangle is the minimum width of the geometry: the smallest distance
+/// between two parallel lines which enclose it. Unlike the rectangle of
+/// [`MinimumRotatedRect`](crate::MinimumRotatedRect), which has the smallest area, one of
+/// its sides always lies on an edge of the [`ConvexHull`]. The rectangle is found by rotating
+/// calipers around the hull, in linear time.
+///
+/// Returns `None` for an empty geometry.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, MinimumWidthRect};
+///
+/// let triangle = wkt!(POLYGON((0. 0.,4. 0.,4. 3.,0. 0.)));
+///
+/// let width_rect = triangle.minimum_width_rect().unwrap();
+/// assert_eq!(width_rect.width, 2.4);
+/// ```
+pub trait MinimumWidthRect<T: GeoFloat> {
+    fn minimum_width_rect(&self) -> Option<WidthRect<T>>;
+}
+
+impl<T, G> MinimumWidthRect<T> for G
+where
+    T: GeoFloat,
+    G: CoordsIter<Scalar = T>,
+{
+    fn minimum_width_rect(&self) -> Option<WidthRect<T>> {
+        let hull = self.convex_hull();
+        let mut vertices: Vec<Coord<T>> = hull.exterior().0.clone();
+        vertices.dedup();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+
+        match vertices.len() {
+            0 => None,
+            1 => Some(WidthRect {
+                rect: Polygon::new(LineString::new(vec![vertices[0]; 2]), vec![]),
+                width: T::zero(),
+            }),
+            2 => Some(WidthRect {
+                rect: Polygon::new(vertices.into(), vec![]),
+                width: T::zero(),
+            }),
+            n => {
+                // the distance of vertex `k` from the line through edge `i`, which is positive as
+                // the hull is counter-clockwise
+                let height = |i: usize, k: usize| {
+                    let (start, end) = (vertices[i], vertices[(i + 1) % n]);
+                    let (edge, offset) = (end - start, vertices[k] - start);
+                    (edge.x * offset.y - edge.y * offset.x) / edge.x.hypot(edge.y)
+                };
+
+                let mut best_edge = 0;
+                let mut width = T::infinity();
+                let mut antipode = 1;
+                for edge in 0..n {
+                    while height(edge, (antipode + 1) % n) >= height(edge, antipode)
+                        && (antipode + 1) % n != edge
+                    {
+                        antipode = (antipode + 1) % n;
+                    }
+                    let edge_width = height(edge, antipode);
+                    if edge_width < width {
+                        width = edge_width;
+                        best_edge = edge;
+                    }
+                }
+
+                let origin = vertices[best_edge];
+                let direction = vertices[(best_edge + 1) % n] - origin;
+                let direction = direction / direction.x.hypot(direction.y);
+                let normal = coord! { x: -direction.y, y: direction.x };
+                let (min, max) = vertices
+                    .iter()
+                    .map(|&vertex| {
+                        let offset = vertex - origin;
+                        offset.x * direction.x + offset.y * direction.y
+                    })
+                    .fold((T::infinity(), T::neg_infinity()), |(min, max), along| {
+                        (min.min(along), max.max(along))
+                    });
+                let rect = Polygon::new(
+                    LineString::new(vec![
+                        origin + direction * min,
+                        origin + direction * max,
+                        origin + direction * max + normal * width,
+                        origin + direction * min + normal * width,
+                        origin + direction * min,
+                    ]),
+                    vec![],
+                );
+                Some(WidthRect { rect, width })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Area, Distance, Euclidean, Relate};
+
+    #[test]
+    fn rectangle() {
+        let rect = wkt!(POLYGON((0. 0.,5. 0.,5. 2.,0. 2.,0. 0.)));
+        let width_rect = rect.minimum_width_rect().unwrap();
+        assert_eq!(width_rect.width, 2.);
+        assert!(width_rect.rect.relate(&rect).is_equal_topo());
+    }
+
+    #[test]
+    fn rotated() {
+        // the narrowest direction is perpendicular to the hypotenuse
+        let triangle = wkt!(POLYGON((0. 0.,4. 0.,4. 3.,0. 0.)));
+        let width_rect = triangle.minimum_width_rect().unwrap();
+        assert_relative_eq!(width_rect.width, 2.4);
+        assert_relative_eq!(width_rect.rect.unsigned_area(), 2.4 * 5.);
+        for vertex in triangle.exterior().points() {
+            assert_relative_eq!(Euclidean.distance(&vertex, &width_rect.rect), 0.);
+        }
+    }
+
+    #[test]
+    fn degenerate() {
+        assert!(LineString::<f64>::new(vec![])
+            .minimum_width_rect()
+            .is_none());
+
+        let collinear = wkt!(MULTIPOINT(0. 0.,1. 1.,3. 3.));
+        let width_rect = collinear.minimum_width_rect().unwrap();
+        assert_eq!(width_rect.width, 0.);
+        assert_eq!(width_rect.rect.unsigned_area(), 0.);
+    }
+}
@@ -8,7 +8,15 @@ pub use area::Area;
 
 /// Boolean Operations such as the union, xor, or difference of two geometries.
 pub mod bool_ops;
-pub use bool_ops::{unary_union, BooleanOps, OpType};
+pub use bool_ops::{unary_union, BooleanOps, OpType, Overlay};
+
+/// Buffer a `Geometry` by a distance, with configurable caps and joins.
+pub mod buffer;
+pub use buffer::Buffer;
+
+/// Calculate the topological boundary of a `Geometry`.
+pub mod boundary;
+pub use boundary::Boundary;
 
 /// Calculate the bounding rectangle of a `Geometry`.
 pub mod bounding_rect;
@@ -18,6 +26,14 @@ pub use bounding_rect::BoundingRect;
 pub mod minimum_rotated_rect;
 pub use minimum_rotated_rect::MinimumRotatedRect;
 
+/// Calculate the smallest circle enclosing a `Geometry`.
+pub mod minimum_bounding_circle;
+pub use minimum_bounding_circle::MinimumBoundingCircle;
+
+/// Calculate the narrowest rectangle enclosing a `Geometry`.
+pub mod minimum_width_rect;
+pub use minimum_width_rect::MinimumWidthRect;
+
 /// Calculate the centroid of a `Geometry`.
 pub mod centroid;
 pub use centroid::Centroid;
@@ -62,6 +78,10 @@ pub use cross_track_distance::CrossTrackDistance;
 pub mod coordinate_position;
 pub use coordinate_position::CoordinatePosition;
 
+/// Simplify a coverage of polygons without opening gaps or overlaps between them.
+pub mod coverage_simplify;
+pub use coverage_simplify::CoverageSimplify;
+
 /// Iterate over geometry coordinates.
 pub mod coords_iter;
 pub use coords_iter::CoordsIter;
@@ -75,6 +95,10 @@ pub use densify_haversine::DensifyHaversine;
 pub mod dimensions;
 pub use dimensions::HasDimensions;
 
+/// Test whether two geometries are within a distance of each other.
+pub mod distance_within;
+pub use distance_within::DistanceWithin;
+
 /// Calculate the minimum Euclidean distance between two `Geometries`.
 pub mod euclidean_distance;
 #[allow(deprecated)]
@@ -94,6 +118,10 @@ pub mod frechet_distance;
 #[allow(deprecated)]
 pub use frechet_distance::FrechetDistance;
 
+/// A spatial index over geometries, with predicate and nearest neighbor queries.
+pub mod geometry_index;
+pub use geometry_index::GeometryIndex;
+
 /// Calculate the bearing to another `Point` on a geodesic.
 pub mod geodesic_bearing;
 pub use geodesic_bearing::GeodesicBearing;
@@ -184,6 +212,10 @@ pub use line_intersection::LineIntersection;
 pub mod line_locate_point;
 pub use line_locate_point::LineLocatePoint;
 
+/// Join the parts of a `MultiLineString` which meet end to end.
+pub mod line_merge;
+pub use line_merge::LineMerge;
+
 /// Iterate over the lines in a geometry.
 pub mod lines_iter;
 pub use lines_iter::LinesIter;
@@ -193,22 +225,47 @@ pub use line_measures::metric_spaces::{
     Euclidean, Geodesic, GeodesicMeasure, Haversine, HaversineMeasure, Rhumb,
 };
 pub use line_measures::{
-    Bearing, Densify, Destination, Distance, InterpolatableLine, InterpolateLine, InterpolatePoint,
-    Length,
+    Bearing, ContinuousFrechetDistance, Densify, Destination, Distance, DtwDistance, EdrDistance,
+    InterpolatableLine, InterpolateLine, InterpolatePoint, LcssDistance, Length, LineSubstring,
+    MetricBuffer, SubstringableLine,
 };
 
 /// Split a LineString into n segments
 pub mod linestring_segment;
 pub use linestring_segment::{LineStringSegmentize, LineStringSegmentizeHaversine};
 
+/// Repair invalid geometries.
+pub mod make_valid;
+pub use make_valid::{MakeValid, MakeValidStrategy};
+
 /// Apply a function to all `Coord`s of a `Geometry`.
 pub mod map_coords;
 pub use map_coords::{MapCoords, MapCoordsInPlace};
 
+/// Find the largest circle which fits inside a polygon.
+pub mod maximum_inscribed_circle;
+pub use maximum_inscribed_circle::MaximumInscribedCircle;
+
+/// Calculate the minimum clearance of a geometry.
+pub mod minimum_clearance;
+pub use minimum_clearance::MinimumClearance;
+
+/// Find the nearest points between two geometries.
+pub mod nearest_points;
+pub use nearest_points::{nearest_points, NearestPoints};
+
+/// Offset a line to one side by a distance.
+pub mod offset_curve;
+pub use offset_curve::OffsetCurve;
+
 /// Orient a `Polygon`'s exterior and interior rings.
 pub mod orient;
 pub use orient::Orient;
 
+/// Assemble polygons from noded linework.
+pub mod polygonize;
+pub use polygonize::{polygonize, Polygonized};
+
 /// Coordinate projections and transformations using the current stable version of [PROJ](http://proj.org).
 #[cfg(feature = "use-proj")]
 pub mod proj;
@@ -233,6 +290,10 @@ pub use scale::Scale;
 pub mod skew;
 pub use skew::Skew;
 
+/// Split a geometry into pieces with a blade.
+pub mod split;
+pub use split::Split;
+
 /// Composable affine operations such as rotate, scale, skew, and translate
 pub mod affine_ops;
 pub use affine_ops::{AffineOps, AffineTransform};
@@ -245,6 +306,10 @@ pub use simplify::{Simplify, SimplifyIdx};
 pub mod simplify_vw;
 pub use simplify_vw::{SimplifyVw, SimplifyVwIdx, SimplifyVwPreserve};
 
+/// Find the pairs of geometries from two slices which satisfy a predicate.
+pub mod spatial_join;
+pub use spatial_join::{spatial_join, JoinPredicate};
+
 /// Stitch together triangles with adjacent sides. Alternative to unioning triangles via BooleanOps.
 #[allow(dead_code)]
 pub(crate) mod stitch;
@@ -298,6 +363,12 @@ pub use vincenty_distance::VincentyDistance;
 pub mod vincenty_length;
 pub use vincenty_length::VincentyLength;
 
+/// Compute the Voronoi diagram of the vertices of a geometry.
+#[cfg(feature = "spade")]
+pub mod voronoi_diagram;
+#[cfg(feature = "spade")]
+pub use voronoi_diagram::VoronoiDiagram;
+
 /// Calculate and work with the winding order of `Linestring`s.
 pub mod winding_order;
 pub use winding_order::Winding;
@@ -324,4 +395,4 @@ pub mod rhumb;
 pub use rhumb::{RhumbBearing, RhumbDestination, RhumbDistance, RhumbIntermediate, RhumbLength};
 
 pub mod validation;
-pub use validation::Validation;
+pub use validation::{CoverageValidation, Validation};
@@ -0,0 +1,318 @@
+use std::borrow::Cow;
+
+use rstar::{RTree, AABB};
+
+use super::distance_within::{expanded_envelope, private, BRUTE_FORCE_PAIRS};
+use crate::geometry::*;
+use crate::line_intersection::{line_intersection, LineIntersection};
+use crate::{BoundingRect, GeoFloat, Intersects};
+
+/// The pair of points, one on each of two geometries, which are nearest to each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearestPoints<F: GeoFloat> {
+    /// The point on the first geometry
+    pub a: Coord<F>,
+    /// The point on the second geometry
+    pub b: Coord<F>,
+    /// The Euclidean distance between `a` and `b`
+    pub distance: F,
+}
+
+impl<F: GeoFloat> NearestPoints<F> {
+    /// The line from the point on the first geometry to the point on the second, e.g. to draw a
+    /// connector between them.
+    pub fn line(&self) -> Line<F> {
+        Line::new(self.a, self.b)
+    }
+}
+
+/// Find the points on two geometries which are nearest to each other, and the distance between
+/// them.
+///
+/// If the geometries intersect, both points are the same point of their intersection. If several
+/// pairs of points are at the minimum distance, any one of them may be returned.
+///
+/// For geometries with many segments, the segments of the larger geometry are loaded into an
+/// R-tree, so that each segment of the other geometry is only compared with the segments near it.
+///
+/// Returns `None` if either geometry is empty. A polygon ring or line string of a single
+/// coordinate counts as a point.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{coord, nearest_points, wkt, Line};
+///
+/// let building = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)));
+/// let road = wkt!(LINESTRING(20. -5.,20. 15.));
+///
+/// let nearest = nearest_points(&building, &road).unwrap();
+/// assert_eq!(nearest.distance, 10.0);
+/// assert_eq!(nearest.a.x, 10.0);
+/// assert_eq!(nearest.b.x, 20.0);
+///
+/// // a connector from the building to the road
+/// let connector: Line = nearest.line();
+/// assert_eq!(connector.dx(), 10.0);
+/// assert_eq!(connector.dy(), 0.0);
+/// ```
+pub fn nearest_points<F, A, B>(a: &A, b: &B) -> Option<NearestPoints<F>>
+where
+    F: GeoFloat,
+    A: private::Facets<F>,
+    B: private::Facets<F>,
+{
+    let mut a_facets = private::Collected::new(true);
+    a.facets(&mut a_facets);
+    let mut b_facets = private::Collected::new(true);
+    b.facets(&mut b_facets);
+    if a_facets.representatives.is_empty() || b_facets.representatives.is_empty() {
+        return None;
+    }
+
+    // A geometry which lies inside a polygon of the other has no nearest boundary to measure to.
+    if let Some(inside) = first_inside(&b_facets.representatives, &a_facets.polygons)
+        .or_else(|| first_inside(&a_facets.representatives, &b_facets.polygons))
+    {
+        return Some(NearestPoints {
+            a: inside,
+            b: inside,
+            distance: F::zero(),
+        });
+    }
+
+    let a_elements: Vec<_> = a_facets.elements().collect();
+    let b_elements: Vec<_> = b_facets.elements().collect();
+    if a_elements.is_empty() || b_elements.is_empty() {
+        return None;
+    }
+    Some(nearest_elements(&a_elements, &b_elements))
+}
+
+fn first_inside<F: GeoFloat>(
+    coords: &[Coord<F>],
+    polygons: &[Cow<Polygon<F>>],
+) -> Option<Coord<F>> {
+    polygons.iter().find_map(|polygon| {
+        let rect = polygon.bounding_rect()?;
+        coords
+            .iter()
+            .find(|coord| rect.intersects(*coord) && polygon.intersects(*coord))
+            .copied()
+    })
+}
+
+/// The nearest points between any segment of `a` and any segment of `b`, both non-empty.
+fn nearest_elements<F: GeoFloat>(a: &[Line<F>], b: &[Line<F>]) -> NearestPoints<F> {
+    let mut best = segment_nearest_points(&a[0], &b[0]);
+    if a.len() * b.len() <= BRUTE_FORCE_PAIRS {
+        for segment_a in a {
+            for segment_b in b {
+                let candidate = segment_nearest_points(segment_a, segment_b);
+                if candidate.distance < best.distance {
+                    best = candidate;
+                }
+            }
+        }
+        return best;
+    }
+
+    // index the larger geometry, and remember to swap the points back if that is `a`
+    let swapped = a.len() > b.len();
+    let (queries, indexed) = if swapped { (b, a) } else { (a, b) };
+    let tree = RTree::bulk_load(indexed.to_vec());
+    if swapped {
+        best = swap(best);
+    }
+    for query in queries {
+        if best.distance == F::zero() {
+            break;
+        }
+        let rect = expanded_envelope(query, best.distance);
+        let envelope = AABB::from_corners(rect.min().into(), rect.max().into());
+        for candidate in tree.locate_in_envelope_intersecting(&envelope) {
+            let candidate = segment_nearest_points(query, candidate);
+            if candidate.distance < best.distance {
+                best = candidate;
+            }
+        }
+    }
+    if swapped {
+        swap(best)
+    } else {
+        best
+    }
+}
+
+fn swap<F: GeoFloat>(nearest: NearestPoints<F>) -> NearestPoints<F> {
+    NearestPoints {
+        a: nearest.b,
+        b: nearest.a,
+        distance: nearest.distance,
+    }
+}
+
+/// The nearest points between two segments, either of which may be degenerate.
+fn segment_nearest_points<F: GeoFloat>(a: &Line<F>, b: &Line<F>) -> NearestPoints<F> {
+    if let Some(intersection) = line_intersection(*a, *b) {
+        let point = match intersection {
+            LineIntersection::SinglePoint { intersection, .. } => intersection,
+            LineIntersection::Collinear { intersection } => intersection.start,
+        };
+        return NearestPoints {
+            a: point,
+            b: point,
+            distance: F::zero(),
+        };
+    }
+    [
+        (a.start, project(a.start, b)),
+        (a.end, project(a.end, b)),
+        (project(b.start, a), b.start),
+        (project(b.end, a), b.end),
+    ]
+    .into_iter()
+    .map(|(on_a, on_b)| NearestPoints {
+        a: on_a,
+        b: on_b,
+        distance: (on_a - on_b).x.hypot((on_a - on_b).y),
+    })
+    .reduce(|best, candidate| {
+        if candidate.distance < best.distance {
+            candidate
+        } else {
+            best
+        }
+    })
+    .expect("four candidates")
+}
+
+/// The point on `segment` nearest to `coord`.
+fn project<F: GeoFloat>(coord: Coord<F>, segment: &Line<F>) -> Coord<F> {
+    let delta = segment.delta();
+    let length_2 = delta.x * delta.x + delta.y * delta.y;
+    if length_2 == F::zero() {
+        return segment.start;
+    }
+    let offset = coord - segment.start;
+    let t = ((offset.x * delta.x + offset.y * delta.y) / length_2)
+        .max(F::zero())
+        .min(F::one());
+    segment.start + delta * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coord, wkt, Distance, Euclidean, PreparedGeometry};
+
+    /// Check that the points lie on the geometries at the distance between the geometries.
+    fn check(a: &Geometry, b: &Geometry) -> NearestPoints<f64> {
+        let nearest = nearest_points(a, b).unwrap();
+        let distance = Euclidean.distance(a, b);
+        assert_relative_eq!(nearest.distance, distance, epsilon = 1e-9);
+        assert_relative_eq!(
+            Euclidean.distance(&Point(nearest.a), &Point(nearest.b)),
+            distance,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(Euclidean.distance(&Point(nearest.a), a), 0., epsilon = 1e-9);
+        assert_relative_eq!(Euclidean.distance(&Point(nearest.b), b), 0., epsilon = 1e-9);
+
+        let reversed = nearest_points(b, a).unwrap();
+        assert_relative_eq!(reversed.distance, distance, epsilon = 1e-9);
+        nearest
+    }
+
+    #[test]
+    fn point_to_line_string() {
+        let point = wkt!(POINT(5. 5.)).into();
+        let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.)).into();
+        let nearest = check(&point, &line_string);
+        assert_eq!(nearest.a, coord! { x: 5., y: 5. });
+        assert_eq!(nearest.distance, 5.);
+    }
+
+    #[test]
+    fn disjoint_polygons() {
+        let a = wkt!(POLYGON((100. 320.,60. 120.,240. 180.,200. 260.,100. 320.))).into();
+        let b = wkt!(POLYGON((420. 320.,280. 260.,400. 100.,420. 320.))).into();
+        let nearest = check(&a, &b);
+        assert_relative_eq!(nearest.distance, 71.55417527999327);
+    }
+
+    #[test]
+    fn crossing_lines() {
+        let a = wkt!(LINESTRING(0. 0.,10. 10.)).into();
+        let b = wkt!(LINESTRING(0. 10.,10. 0.)).into();
+        let nearest = check(&a, &b);
+        assert_eq!(nearest.a, coord! { x: 5., y: 5. });
+        assert_eq!(nearest.a, nearest.b);
+    }
+
+    #[test]
+    fn contained() {
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.))).into();
+        let line_string = wkt!(LINESTRING(2. 2.,3. 3.)).into();
+        let nearest = check(&polygon, &line_string);
+        assert_eq!(nearest.distance, 0.);
+        assert_eq!(nearest.a, coord! { x: 2., y: 2. });
+    }
+
+    #[test]
+    fn many_segments() {
+        // a zigzag and a circle, with enough segments to use an index
+        let zigzag = Geometry::LineString(
+            (0..200)
+                .map(|i| coord! { x: i as f64, y: (i % 2) as f64 })
+                .collect(),
+        );
+        let circle = Geometry::Polygon(Polygon::new(
+            (0..=100)
+                .map(|i| {
+                    let angle = i as f64 / 100. * std::f64::consts::TAU;
+                    coord! { x: 50. + 10. * angle.cos(), y: 15. + 10. * angle.sin() }
+                })
+                .collect(),
+            vec![],
+        ));
+        let nearest = check(&zigzag, &circle);
+        assert_relative_eq!(nearest.distance, 4., epsilon = 0.1);
+        check(&circle, &zigzag);
+    }
+
+    #[test]
+    fn prepared() {
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)));
+        let prepared = PreparedGeometry::from(&polygon);
+        let point = wkt!(POINT(13. 14.));
+        let nearest = nearest_points(&prepared, &point).unwrap();
+        assert_eq!(nearest.a, coord! { x: 10., y: 10. });
+        assert_eq!(nearest.distance, 5.);
+    }
+
+    #[test]
+    fn degenerate() {
+        let point = wkt!(POINT(4. 5.));
+        let ring = Polygon::new(LineString::new(vec![coord! { x: 1., y: 1. }]), vec![]);
+        let nearest = nearest_points(&ring, &point).unwrap();
+        assert_eq!(nearest.a, coord! { x: 1., y: 1. });
+        assert_eq!(nearest.distance, 5.);
+
+        let line_string = LineString::new(vec![coord! { x: 1., y: 1. }]);
+        let nearest = nearest_points(&point, &line_string).unwrap();
+        assert_eq!(nearest.b, coord! { x: 1., y: 1. });
+        assert_eq!(nearest.distance, 5.);
+
+        let nearest = nearest_points(&ring, &line_string).unwrap();
+        assert_eq!(nearest.distance, 0.);
+    }
+
+    #[test]
+    fn empty() {
+        let point = wkt!(POINT(0. 0.));
+        let empty: LineString = wkt!(LINESTRING EMPTY);
+        assert_eq!(nearest_points(&point, &empty), None);
+        assert_eq!(nearest_points(&empty, &point), None);
+    }
+}
@@ -0,0 +1,521 @@
+use rstar::RTree;
+
+use crate::algorithm::buffer::{arc, join, left_normal, unit, BufferStyle, Side};
+use crate::geometry::*;
+use crate::kernels::{Kernel, Orientation};
+use crate::line_measures::{Distance, Euclidean};
+use crate::sweep::{Cross, Intersections, LineOrPoint};
+use crate::{GeoFloat, LineIntersection};
+
+/// Compute the curve running parallel to a line at a given (Euclidean) distance from it, on one
+/// side of it.
+///
+/// A positive distance offsets to the left of the line (relative to its direction), a negative
+/// distance to the right.
+///
+/// Corners on the outside of a bend are joined according to the [`LineJoin`] of the style, using
+/// [`BufferStyle::quadrant_segments`] to approximate round joins. The [`LineCap`] of the style is
+/// ignored. On the inside of a bend, the offset segments are trimmed where they cross, and any
+/// part of the curve which comes closer to the line than `distance` is removed. As a result, the
+/// offset curve of a single line may consist of several pieces, or none at all.
+///
+/// The offset curve of a closed `LineString` is closed, if it is not split into pieces.
+///
+/// [`LineJoin`]: crate::buffer::LineJoin
+/// [`LineCap`]: crate::buffer::LineCap
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, OffsetCurve};
+/// use geo::buffer::{BufferStyle, LineJoin};
+///
+/// let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.));
+///
+/// // The inside of the bend is trimmed.
+/// let left = line_string.offset_curve(1.0);
+/// assert_eq!(left, wkt!(MULTILINESTRING((0. 1.,9. 1.,9. 10.))));
+///
+/// // The outside of the bend is joined.
+/// let right = line_string.offset_curve_with_style(BufferStyle::new(-1.0).line_join(LineJoin::Miter(5.0)));
+/// assert_eq!(right, wkt!(MULTILINESTRING((0. -1.,11. -1.,11. 10.))));
+/// ```
+pub trait OffsetCurve {
+    type Scalar: GeoFloat;
+
+    /// Offset the geometry by `distance`, with round joins.
+    fn offset_curve(&self, distance: Self::Scalar) -> MultiLineString<Self::Scalar> {
+        self.offset_curve_with_style(BufferStyle::new(distance))
+    }
+
+    /// Offset the geometry using the given [`BufferStyle`].
+    fn offset_curve_with_style(
+        &self,
+        style: BufferStyle<Self::Scalar>,
+    ) -> MultiLineString<Self::Scalar>;
+}
+
+impl<T: GeoFloat> OffsetCurve for Line<T> {
+    type Scalar = T;
+    fn offset_curve_with_style(&self, style: BufferStyle<T>) -> MultiLineString<T> {
+        MultiLineString::new(offset_coords(vec![self.start, self.end], &style))
+    }
+}
+
+impl<T: GeoFloat> OffsetCurve for LineString<T> {
+    type Scalar = T;
+    fn offset_curve_with_style(&self, style: BufferStyle<T>) -> MultiLineString<T> {
+        MultiLineString::new(offset_coords(self.0.clone(), &style))
+    }
+}
+
+impl<T: GeoFloat> OffsetCurve for MultiLineString<T> {
+    type Scalar = T;
+    fn offset_curve_with_style(&self, style: BufferStyle<T>) -> MultiLineString<T> {
+        self.iter()
+            .flat_map(|line_string| offset_coords(line_string.0.clone(), &style))
+            .collect()
+    }
+}
+
+/// The part of the input line an offset segment was derived from, which is the closest part of
+/// the input to that segment, unless the segment is to be trimmed.
+#[derive(Debug, Clone, Copy)]
+enum Source<T: GeoFloat> {
+    Segment(Line<T>),
+    Vertex(Coord<T>),
+    /// A segment connecting the offset segments on the inside of a bend via the vertex.
+    Inside,
+}
+
+/// A segment which may split the offset curve: either a segment of the offset curve itself (with
+/// its index), or a segment of the rest of the boundary of the buffer of the line.
+#[derive(Debug, Clone)]
+struct NodingSegment<T: GeoFloat> {
+    line: Line<T>,
+    index: Option<usize>,
+}
+
+impl<T: GeoFloat> Cross for NodingSegment<T> {
+    type Scalar = T;
+
+    fn line(&self) -> LineOrPoint<T> {
+        self.line.into()
+    }
+}
+
+/// A maximal section of the raw offset curve between two nodes.
+struct Piece<T: GeoFloat> {
+    coords: Vec<Coord<T>>,
+    source: Source<T>,
+}
+
+fn offset_coords<T: GeoFloat>(
+    mut coords: Vec<Coord<T>>,
+    style: &BufferStyle<T>,
+) -> Vec<LineString<T>> {
+    coords.dedup();
+    if coords.len() < 2 {
+        return vec![];
+    }
+    if style.distance.is_zero() {
+        return vec![LineString::new(coords)];
+    }
+    let side = if style.distance > T::zero() {
+        Side::Left
+    } else {
+        Side::Right
+    };
+    let other_side = if side == Side::Left {
+        Side::Right
+    } else {
+        Side::Left
+    };
+    let distance = style.distance.abs();
+    let closed = coords.len() > 3 && coords.first() == coords.last();
+
+    let (points, sources) = raw_curve(&coords, closed, side, distance, style);
+
+    // The curve has to be split wherever it enters or leaves the region within `distance` of the
+    // line, i.e. where it crosses itself or the rest of the boundary of that region.
+    let mut noding: Vec<NodingSegment<T>> = points
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| NodingSegment {
+            line: Line::new(w[0], w[1]),
+            index: Some(i),
+        })
+        .collect();
+    let (other_points, _) = raw_curve(&coords, closed, other_side, distance, style);
+    let mut cutters = vec![other_points];
+    if !closed {
+        let n = coords.len();
+        for (prev, end) in [(coords[1], coords[0]), (coords[n - 2], coords[n - 1])] {
+            if let Some(u) = unit(end - prev) {
+                let normal = left_normal(u) * distance;
+                cutters.push(arc(
+                    end,
+                    end - normal,
+                    end + normal,
+                    true,
+                    distance,
+                    style.quadrant_segments,
+                ));
+            }
+        }
+    }
+    noding.extend(cutters.iter().flat_map(|cutter| {
+        cutter.windows(2).map(|w| NodingSegment {
+            line: Line::new(w[0], w[1]),
+            index: None,
+        })
+    }));
+    noding.retain(|segment| segment.line.start != segment.line.end);
+
+    let segment_count = points.len() - 1;
+    let is_adjacent =
+        |i: usize, j: usize| i.abs_diff(j) == 1 || (closed && i.abs_diff(j) == segment_count - 1);
+    let mut nodes: Vec<Vec<Coord<T>>> = vec![vec![]; segment_count];
+    for (a, b, intersection) in Intersections::from_iter(noding) {
+        let crossings = match intersection {
+            LineIntersection::SinglePoint { intersection, .. } => {
+                // Consecutive segments of the curve always meet at their shared vertex.
+                if let (Some(i), Some(j)) = (a.index, b.index) {
+                    if is_adjacent(i, j)
+                        && (intersection == a.line.start || intersection == a.line.end)
+                    {
+                        continue;
+                    }
+                }
+                vec![intersection]
+            }
+            LineIntersection::Collinear { intersection } => {
+                vec![intersection.start, intersection.end]
+            }
+        };
+        for segment in [&a, &b] {
+            if let Some(i) = segment.index {
+                nodes[i].extend(&crossings);
+            }
+        }
+    }
+
+    let pieces = split(&points, &sources, nodes);
+    let input = RTree::bulk_load(coords.windows(2).map(|w| Line::new(w[0], w[1])).collect());
+
+    // Reassemble the pieces which are kept. Consecutive ones may be separated by a trimmed loop,
+    // which starts and ends at the same node.
+    let mut lines: Vec<Vec<Coord<T>>> = vec![];
+    for piece in pieces {
+        if !is_on_offset(&piece, &input, distance) {
+            continue;
+        }
+        match lines.last_mut() {
+            Some(line) if line.last() == piece.coords.first() => line.extend(&piece.coords[1..]),
+            _ => lines.push(piece.coords),
+        }
+    }
+    if closed && lines.len() > 1 && lines[lines.len() - 1].last() == lines[0].first() {
+        let first = lines.remove(0);
+        lines.last_mut().unwrap().extend(&first[1..]);
+    }
+    lines
+        .into_iter()
+        .map(|line| LineString::new(remove_straight_vertices(line)))
+        .collect()
+}
+
+/// Remove the vertices at which the curve continues in a straight line, such as the nodes it was
+/// split at, or the ends of a mitre.
+fn remove_straight_vertices<T: GeoFloat>(coords: Vec<Coord<T>>) -> Vec<Coord<T>> {
+    let mut result: Vec<Coord<T>> = Vec::with_capacity(coords.len());
+    for (i, &coord) in coords.iter().enumerate() {
+        if let (Some(&prev), Some(&next)) = (result.last(), coords.get(i + 1)) {
+            let straight = T::Ker::orient2d(prev, coord, next) == Orientation::Collinear
+                && (coord.x - prev.x) * (next.x - coord.x)
+                    + (coord.y - prev.y) * (next.y - coord.y)
+                    > T::zero();
+            if straight {
+                continue;
+            }
+        }
+        result.push(coord);
+    }
+    result
+}
+
+/// Offset each segment of the line and connect them: on the outside of a bend with the join of
+/// the style, and on the inside via the vertex, which guarantees that the parts which have to be
+/// trimmed away are bounded by nodes.
+///
+/// Returns the coordinates of the curve and, for each of its segments, where it came from.
+fn raw_curve<T: GeoFloat>(
+    coords: &[Coord<T>],
+    closed: bool,
+    side: Side,
+    distance: T,
+    style: &BufferStyle<T>,
+) -> (Vec<Coord<T>>, Vec<Source<T>>) {
+    let normal = |a: Coord<T>, b: Coord<T>| {
+        let u = unit(b - a).unwrap_or_else(Coord::zero);
+        match side {
+            Side::Left => left_normal(u) * distance,
+            _ => -left_normal(u) * distance,
+        }
+    };
+    let mut points = vec![coords[0] + normal(coords[0], coords[1])];
+    let mut sources = vec![];
+    let mut push = |points: &mut Vec<Coord<T>>, point: Coord<T>, source: Source<T>| {
+        if points.last() != Some(&point) {
+            points.push(point);
+            sources.push(source);
+        }
+    };
+
+    let n = coords.len();
+    for i in 0..n - 1 {
+        let (start, vertex) = (coords[i], coords[i + 1]);
+        push(
+            &mut points,
+            vertex + normal(start, vertex),
+            Source::Segment(Line::new(start, vertex)),
+        );
+        let next = match (i + 2 < n, closed) {
+            (true, _) => coords[i + 2],
+            (false, true) => coords[1],
+            (false, false) => break,
+        };
+        let (Some(u1), Some(u2)) = (unit(vertex - start), unit(next - vertex)) else {
+            continue;
+        };
+        let outer = match T::Ker::orient2d(start, vertex, next) {
+            Orientation::CounterClockwise => Side::Right,
+            Orientation::Clockwise => Side::Left,
+            Orientation::Collinear if u1.x * u2.x + u1.y * u2.y > T::zero() => {
+                // the offset segments meet
+                continue;
+            }
+            // Doubling back, which is a bend to both sides.
+            Orientation::Collinear => side,
+        };
+        if outer == side {
+            for point in join(style, distance, vertex, u1, u2, side) {
+                push(&mut points, point, Source::Vertex(vertex));
+            }
+        } else {
+            push(&mut points, vertex, Source::Inside);
+            push(&mut points, vertex + normal(vertex, next), Source::Inside);
+        }
+    }
+    (points, sources)
+}
+
+/// Split the curve at the nodes of each of its segments.
+fn split<T: GeoFloat>(
+    points: &[Coord<T>],
+    sources: &[Source<T>],
+    nodes: Vec<Vec<Coord<T>>>,
+) -> Vec<Piece<T>> {
+    let mut pieces = vec![];
+    let mut current = vec![points[0]];
+    let mut current_source = None;
+    let mut finish = |current: &mut Vec<Coord<T>>, current_source: &mut Option<Source<T>>| {
+        if let (true, Some(source)) = (current.len() > 1, current_source.take()) {
+            let last = current[current.len() - 1];
+            pieces.push(Piece {
+                coords: std::mem::replace(current, vec![last]),
+                source,
+            });
+        }
+    };
+    let extend = |current: &mut Vec<Coord<T>>,
+                  current_source: &mut Option<Source<T>>,
+                  point: Coord<T>,
+                  source: Source<T>| {
+        if current.last() != Some(&point) {
+            current.push(point);
+            current_source.get_or_insert(source);
+        }
+    };
+
+    for ((window, source), mut nodes) in points.windows(2).zip(sources).zip(nodes) {
+        let (a, b) = (window[0], window[1]);
+        let ab = b - a;
+        let length_sq = ab.x * ab.x + ab.y * ab.y;
+        let position = |c: &Coord<T>| ((c.x - a.x) * ab.x + (c.y - a.y) * ab.y) / length_sq;
+        nodes.sort_by(|c1, c2| position(c1).total_cmp(&position(c2)));
+        let mut split_at_end = false;
+        for node in nodes {
+            let t = position(&node);
+            if node == a || t <= T::zero() {
+                finish(&mut current, &mut current_source);
+            } else if node == b || t >= T::one() {
+                split_at_end = true;
+            } else {
+                extend(&mut current, &mut current_source, node, *source);
+                finish(&mut current, &mut current_source);
+            }
+        }
+        extend(&mut current, &mut current_source, b, *source);
+        if split_at_end {
+            finish(&mut current, &mut current_source);
+        }
+    }
+    finish(&mut current, &mut current_source);
+    pieces
+}
+
+/// Whether a piece of the raw curve is part of the offset curve, i.e. nothing on the input line is
+/// closer to it than the part it was derived from.
+///
+/// Pieces have no nodes in their interior, so testing a single point of them suffices.
+fn is_on_offset<T: GeoFloat>(piece: &Piece<T>, input: &RTree<Line<T>>, distance: T) -> bool {
+    let (a, b) = (piece.coords[0], piece.coords[1]);
+    let two = T::one() + T::one();
+    let midpoint = Point::from((a + b) / two);
+    let own_distance = match piece.source {
+        Source::Segment(line) => Euclidean.distance(&midpoint, &line),
+        Source::Vertex(vertex) => Euclidean.distance(midpoint, Point::from(vertex)),
+        Source::Inside => return false,
+    };
+    let tolerance = distance * T::from(1e-9).unwrap();
+    let nearest = input
+        .nearest_neighbor(&midpoint)
+        .expect("the input has at least one segment");
+    Euclidean.distance(&midpoint, nearest) >= own_distance - tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::LineJoin;
+    use crate::{wkt, CoordsIter, Relate};
+
+    #[test]
+    fn straight_line() {
+        let line = wkt!(LINESTRING(0. 0.,10. 0.));
+        assert_eq!(
+            line.offset_curve(1.0),
+            wkt!(MULTILINESTRING((0. 1.,10. 1.)))
+        );
+        assert_eq!(
+            line.offset_curve(-1.0),
+            MultiLineString::from(LineString::from(vec![(0., -1.), (10., -1.)]))
+        );
+        assert_eq!(
+            line.offset_curve(0.0),
+            wkt!(MULTILINESTRING((0. 0.,10. 0.)))
+        );
+        assert_eq!(
+            Line::new((0., 0.), (0., 5.)).offset_curve(2.0),
+            wkt!(MULTILINESTRING((-2. 0.,-2. 5.)))
+        );
+    }
+
+    #[test]
+    fn degenerate_line() {
+        let line = wkt!(LINESTRING(1. 1.,1. 1.));
+        assert!(line.offset_curve(1.0).0.is_empty());
+        let empty: LineString = wkt!(LINESTRING EMPTY);
+        assert!(empty.offset_curve(1.0).0.is_empty());
+    }
+
+    #[test]
+    fn joins() {
+        let line_string = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.));
+        let style = BufferStyle::new(-1.0);
+
+        let bevel = line_string.offset_curve_with_style(style.line_join(LineJoin::Bevel));
+        assert_eq!(
+            bevel,
+            wkt!(MULTILINESTRING((0. -1.,10. -1.,11. 0.,11. 10.)))
+        );
+
+        let miter = line_string.offset_curve_with_style(style.line_join(LineJoin::Miter(5.0)));
+        assert_eq!(miter, wkt!(MULTILINESTRING((0. -1.,11. -1.,11. 10.))));
+
+        let round = line_string.offset_curve(-1.0);
+        assert_eq!(round.0.len(), 1);
+        // a quarter circle of 8 segments
+        assert_eq!(round.0[0].0.len(), 4 + 7);
+        for coord in &round.0[0].0[1..10] {
+            let distance = Euclidean.distance(Point::from(*coord), Point::new(10.0, 0.0));
+            assert_relative_eq!(distance, 1.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn doubling_back() {
+        let line_string = wkt!(LINESTRING(0. 0.,10. 0.,5. 0.));
+        let offset =
+            line_string.offset_curve_with_style(BufferStyle::new(1.0).line_join(LineJoin::Bevel));
+        assert_eq!(offset, wkt!(MULTILINESTRING((0. 1.,10. 1.,10. -1.,5. -1.))));
+    }
+
+    #[test]
+    fn closed_line_string() {
+        let square = wkt!(LINESTRING(0. 0.,10. 0.,10. 10.,0. 10.,0. 0.));
+        let inside = square.offset_curve(1.0);
+        assert_eq!(inside.0.len(), 1);
+        assert!(inside.0[0].is_closed());
+        assert!(inside
+            .relate(&wkt!(LINESTRING(1. 1.,9. 1.,9. 9.,1. 9.,1. 1.)))
+            .is_equal_topo());
+
+        let outside =
+            square.offset_curve_with_style(BufferStyle::new(-1.0).line_join(LineJoin::Miter(5.0)));
+        assert_eq!(outside.0.len(), 1);
+        assert!(outside.0[0].is_closed());
+        assert!(outside
+            .relate(&wkt!(LINESTRING(-1. -1.,11. -1.,11. 11.,-1. 11.,-1. -1.)))
+            .is_equal_topo());
+    }
+
+    #[test]
+    fn collapsed_side() {
+        // a hairpin which is too narrow for the offset to fit inside
+        let hairpin = wkt!(LINESTRING(0. 0.,10. 0.,10. 1.,0. 1.));
+        assert!(hairpin.offset_curve(1.0).0.is_empty());
+        assert_eq!(hairpin.offset_curve(-1.0).0.len(), 1);
+    }
+
+    #[test]
+    fn split_into_pieces() {
+        // the end of the line comes close to its start, cutting the offset curve in two
+        let line_string = wkt!(LINESTRING(0. 0.,20. 0.,20. 10.,10. 10.,10. 0.5));
+        let offset = line_string.offset_curve(1.0);
+        assert_eq!(offset.0.len(), 2);
+        assert!(offset
+            .relate(&wkt!(MULTILINESTRING((0. 1.,9. 1.),(11. 1.,19. 1.,19. 9.,11. 9.,11. 1.))))
+            .is_equal_topo());
+    }
+
+    #[test]
+    fn multi_line_string() {
+        let multi_line_string = wkt!(MULTILINESTRING((0. 0.,10. 0.),(0. 5.,10. 5.)));
+        assert_eq!(
+            multi_line_string.offset_curve(1.0),
+            wkt!(MULTILINESTRING((0. 1.,10. 1.),(0. 6.,10. 6.)))
+        );
+    }
+
+    #[test]
+    fn stays_at_distance() {
+        let zigzag = wkt!(LINESTRING(0. 0.,3. 4.,4. 0.5,6. 5.,7.5 0.,12. 3.,10. 8.,2. 7.));
+        for distance in [-2.0_f64, -0.7, 0.3, 0.7, 2.0] {
+            // bevels cut corners, so they come closer to the line
+            for line_join in [LineJoin::Round, LineJoin::Miter(10.0)] {
+                let style = BufferStyle::new(distance).line_join(line_join);
+                let offset = zigzag.offset_curve_with_style(style);
+                assert!(!offset.0.is_empty());
+                // Where the curve is cut by a round join or cap, it can come as close as a chord
+                // of the approximated circle.
+                let min_distance = distance.abs() * 0.99;
+                for coord in offset.coords_iter() {
+                    let d = Euclidean.distance(&Point::from(coord), &zigzag);
+                    assert!(d >= min_distance, "{d} at {coord:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use std::cmp::Ordering;
+
+use crate::coordinate_position::{coord_pos_relative_to_ring, CoordPos};
+use crate::utils::lex_cmp;
+use crate::{Area, BoundingRect, Contains, GeoFloat, Kernel, Validation};
+use geo_types::{Coord, CoordNum, LineString, MultiLineString, MultiPolygon, Polygon};
+
+/// The output of [`polygonize`].
+///
+/// Besides the polygons themselves, this reports the input lines which could not be used to form
+/// any polygon, in the same categories as the JTS `Polygonizer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygonized<T: CoordNum> {
+    /// The polygons formed by the closed faces of the linework.
+    pub polygons: MultiPolygon<T>,
+    /// Input lines which have an endpoint not shared with any other line, either directly or after
+    /// removing other dangles.
+    pub dangles: MultiLineString<T>,
+    /// Input lines which are connected at both ends, but do not separate two faces, e.g. a line
+    /// bridging two rings.
+    pub cut_edges: MultiLineString<T>,
+    /// Closed rings formed by the linework which are not valid polygon rings, e.g. because the
+    /// lines cross without being noded.
+    pub invalid_rings: MultiLineString<T>,
+}
+
+/// Assemble the polygons formed by a collection of lines.
+///
+/// Every face of the planar graph formed by the lines becomes a polygon. A face which encloses
+/// other linework gets a hole for it, and the enclosed faces are returned as polygons of their own.
+///
+/// The lines are expected to be correctly noded, i.e. they may only touch at their endpoints.
+/// Lines which cross or touch elsewhere are not split, and produce `invalid_rings` rather than
+/// polygons. Lines which don't contribute to any polygon are returned as `dangles` or
+/// `cut_edges`, see [`Polygonized`].
+///
+/// # Examples
+///
+/// ```
+/// use geo::polygonize;
+/// use geo::{wkt, Area};
+///
+/// let lines = wkt!(MULTILINESTRING(
+///     (0. 0.,10. 0.,10. 10.),
+///     (10. 10.,0. 10.,0. 0.),
+///     (10. 10.,20. 20.)
+/// ));
+/// let polygonized = polygonize(&lines);
+///
+/// assert_eq!(polygonized.polygons.0.len(), 1);
+/// assert_eq!(polygonized.polygons.unsigned_area(), 100.);
+/// assert_eq!(polygonized.dangles, wkt!(MULTILINESTRING((10. 10.,20. 20.))));
+/// ```
+pub fn polygonize<'a, T: GeoFloat + 'a>(
+    lines: impl IntoIterator<Item = &'a LineString<T>>,
+) -> Polygonized<T> {
+    let mut graph = PolygonizeGraph::new(lines);
+    let dangles = graph.delete_dangles();
+    let cut_edges = graph.delete_cut_edges();
+    let (polygons, invalid_rings) = graph.polygons();
+    Polygonized {
+        polygons,
+        dangles,
+        cut_edges,
+        invalid_rings,
+    }
+}
+
+struct Edge<'a, T: CoordNum> {
+    line: &'a LineString<T>,
+    /// The coordinates of `line`, without repeated points.
+    coords: Vec<Coord<T>>,
+    /// The nodes at the start and end of the edge.
+    nodes: [usize; 2],
+    deleted: bool,
+}
+
+/// A planar graph with a node at every line endpoint.
+///
+/// Each edge `e` is traversed by two half-edges: `2 * e` runs along the line, and `2 * e + 1`
+/// runs against it.
+struct PolygonizeGraph<'a, T: CoordNum> {
+    edges: Vec<Edge<'a, T>>,
+    nodes: Vec<Coord<T>>,
+    /// The half-edges leaving each node, sorted counter-clockwise.
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl<'a, T: GeoFloat> PolygonizeGraph<'a, T> {
+    fn new(lines: impl IntoIterator<Item = &'a LineString<T>>) -> Self {
+        let mut edges: Vec<Edge<'a, T>> = lines
+            .into_iter()
+            .filter_map(|line| {
+                let mut coords = line.0.clone();
+                coords.dedup();
+                (coords.len() >= 2).then_some(Edge {
+                    line,
+                    coords,
+                    nodes: [0, 0],
+                    deleted: false,
+                })
+            })
+            .collect();
+
+        let mut nodes: Vec<Coord<T>> = edges
+            .iter()
+            .flat_map(|edge| [edge.coords[0], edge.coords[edge.coords.len() - 1]])
+            .collect();
+        nodes.sort_by(lex_cmp);
+        nodes.dedup();
+
+        let mut outgoing = vec![vec![]; nodes.len()];
+        for (i, edge) in edges.iter_mut().enumerate() {
+            let ends = [edge.coords[0], edge.coords[edge.coords.len() - 1]];
+            for (end, coord) in ends.iter().enumerate() {
+                let node = nodes
+                    .binary_search_by(|node| lex_cmp(node, coord))
+                    .expect("every endpoint is a node");
+                edge.nodes[end] = node;
+                outgoing[node].push(2 * i + end);
+            }
+        }
+
+        let mut graph = Self {
+            edges,
+            nodes,
+            outgoing,
+        };
+        for node in 0..graph.nodes.len() {
+            let mut outgoing = std::mem::take(&mut graph.outgoing[node]);
+            outgoing.sort_by(|a, b| graph.compare_direction(*a, *b));
+            graph.outgoing[node] = outgoing;
+        }
+        graph
+    }
+
+    fn origin(&self, half_edge: usize) -> usize {
+        self.edges[half_edge / 2].nodes[half_edge % 2]
+    }
+
+    /// The second coordinate along `half_edge`, which gives its direction at its origin.
+    fn direction(&self, half_edge: usize) -> Coord<T> {
+        let coords = &self.edges[half_edge / 2].coords;
+        if half_edge % 2 == 0 {
+            coords[1]
+        } else {
+            coords[coords.len() - 2]
+        }
+    }
+
+    fn is_live(&self, half_edge: usize) -> bool {
+        !self.edges[half_edge / 2].deleted
+    }
+
+    /// Order two half-edges leaving the same node counter-clockwise by angle, starting from the
+    /// positive x axis.
+    fn compare_direction(&self, a: usize, b: usize) -> Ordering {
+        let origin = self.nodes[self.origin(a)];
+        let (a, b) = (self.direction(a), self.direction(b));
+        quadrant(a - origin)
+            .cmp(&quadrant(b - origin))
+            .then_with(|| T::Ker::orient2d(origin, a, b).as_ordering())
+    }
+
+    /// Iteratively remove edges with an endpoint not connected to any other edge.
+    fn delete_dangles(&mut self) -> MultiLineString<T> {
+        let mut dangles = vec![];
+        let mut degree: Vec<usize> = self.outgoing.iter().map(Vec::len).collect();
+        let mut stack: Vec<usize> = (0..self.nodes.len()).filter(|&n| degree[n] == 1).collect();
+        while let Some(node) = stack.pop() {
+            if degree[node] != 1 {
+                continue;
+            }
+            let Some(&half_edge) = self.outgoing[node].iter().find(|&&h| self.is_live(h)) else {
+                continue;
+            };
+            let edge = &mut self.edges[half_edge / 2];
+            edge.deleted = true;
+            dangles.push(edge.line.clone());
+            for node in edge.nodes {
+                degree[node] -= 1;
+                if degree[node] == 1 {
+                    stack.push(node);
+                }
+            }
+        }
+        MultiLineString::new(dangles)
+    }
+
+    /// Remove edges which have the same face on both sides.
+    fn delete_cut_edges(&mut self) -> MultiLineString<T> {
+        let mut face_of = vec![usize::MAX; 2 * self.edges.len()];
+        for (i, face) in self.faces().iter().enumerate() {
+            for &half_edge in face {
+                face_of[half_edge] = i;
+            }
+        }
+
+        let mut cut_edges = vec![];
+        for (i, edge) in self.edges.iter_mut().enumerate() {
+            if !edge.deleted && face_of[2 * i] == face_of[2 * i + 1] {
+                edge.deleted = true;
+                cut_edges.push(edge.line.clone());
+            }
+        }
+        MultiLineString::new(cut_edges)
+    }
+
+    /// Walk the boundaries of the faces of the graph, each with its face on the left.
+    ///
+    /// A bounded face's outer boundary is walked counter-clockwise, while the boundary of each
+    /// connected component within a face is walked clockwise.
+    fn faces(&self) -> Vec<Vec<usize>> {
+        // A walk arriving at a node continues along the next edge clockwise from the one it
+        // arrived on.
+        let mut next = vec![usize::MAX; 2 * self.edges.len()];
+        for outgoing in &self.outgoing {
+            let live: Vec<usize> = outgoing
+                .iter()
+                .copied()
+                .filter(|&h| self.is_live(h))
+                .collect();
+            for (i, &half_edge) in live.iter().enumerate() {
+                next[half_edge ^ 1] = live[(i + live.len() - 1) % live.len()];
+            }
+        }
+
+        let mut visited = vec![false; 2 * self.edges.len()];
+        let mut faces = vec![];
+        for start in 0..2 * self.edges.len() {
+            if visited[start] || !self.is_live(start) {
+                continue;
+            }
+            let mut face = vec![];
+            let mut half_edge = start;
+            loop {
+                visited[half_edge] = true;
+                face.push(half_edge);
+                half_edge = next[half_edge];
+                if half_edge == start {
+                    break;
+                }
+            }
+            faces.push(face);
+        }
+        faces
+    }
+
+    /// Split a face boundary which passes through a node more than once into rings which don't.
+    fn minimal_rings(
+        &self,
+        face: &[usize],
+        stack_position: &mut [Option<usize>],
+    ) -> Vec<Vec<usize>> {
+        let mut rings = vec![];
+        let mut stack = vec![];
+        let start = self.origin(face[0]);
+        stack_position[start] = Some(0);
+        for &half_edge in face {
+            stack.push(half_edge);
+            let node = self.origin(half_edge ^ 1);
+            match stack_position[node] {
+                Some(position) => {
+                    let ring = stack.split_off(position);
+                    for &half_edge in &ring[1..] {
+                        stack_position[self.origin(half_edge)] = None;
+                    }
+                    rings.push(ring);
+                }
+                None => stack_position[node] = Some(stack.len()),
+            }
+        }
+        stack_position[start] = None;
+        rings
+    }
+
+    fn ring_line_string(&self, ring: &[usize]) -> LineString<T> {
+        let mut coords = vec![self.nodes[self.origin(ring[0])]];
+        for &half_edge in ring {
+            let edge_coords = &self.edges[half_edge / 2].coords;
+            if half_edge % 2 == 0 {
+                coords.extend(&edge_coords[1..]);
+            } else {
+                coords.extend(edge_coords[..edge_coords.len() - 1].iter().rev());
+            }
+        }
+        LineString::new(coords)
+    }
+
+    fn polygons(&self) -> (MultiPolygon<T>, MultiLineString<T>) {
+        let mut shells = vec![];
+        let mut holes = vec![];
+        let mut invalid_rings = vec![];
+        let mut reported_edges = vec![false; self.edges.len()];
+        let mut stack_position = vec![None; self.nodes.len()];
+        for face in self.faces() {
+            for ring in self.minimal_rings(&face, &mut stack_position) {
+                let polygon = Polygon::new(self.ring_line_string(&ring), vec![]);
+                let area = polygon.signed_area();
+                if area != T::zero() && polygon.is_valid() {
+                    if area > T::zero() {
+                        shells.push((polygon, area));
+                    } else {
+                        holes.push(polygon.into_inner().0);
+                    }
+                } else if !reported_edges[ring[0] / 2] {
+                    // Both sides of an invalid ring are usually invalid, but we only report it once.
+                    for &half_edge in &ring {
+                        reported_edges[half_edge / 2] = true;
+                    }
+                    invalid_rings.push(polygon.into_inner().0);
+                }
+            }
+        }
+
+        // A hole belongs to the smallest shell around it. Holes without a shell are the outer
+        // boundaries of the linework.
+        let shell_rects: Vec<_> = shells
+            .iter()
+            .map(|(shell, _)| shell.bounding_rect().expect("shells are not empty"))
+            .collect();
+        let mut shell_holes = vec![vec![]; shells.len()];
+        for hole in holes {
+            let hole_rect = hole.bounding_rect().expect("holes are not empty");
+            // The midpoint of a hole segment can't be on the boundary of its shell.
+            let test_coord = (hole.0[0] + hole.0[1]) / (T::one() + T::one());
+            let shell = shells
+                .iter()
+                .enumerate()
+                .filter(|(i, (shell, _))| {
+                    shell_rects[*i].contains(&hole_rect)
+                        && coord_pos_relative_to_ring(test_coord, shell.exterior())
+                            == CoordPos::Inside
+                })
+                .min_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b));
+            if let Some((i, _)) = shell {
+                shell_holes[i].push(hole);
+            }
+        }
+
+        let polygons = shells
+            .into_iter()
+            .zip(shell_holes)
+            .map(|((shell, _), holes)| Polygon::new(shell.into_inner().0, holes))
+            .collect();
+        (polygons, MultiLineString::new(invalid_rings))
+    }
+}
+
+/// The quadrant of a direction, numbered counter-clockwise from the positive x axis.
+fn quadrant<T: GeoFloat>(direction: Coord<T>) -> u8 {
+    match (direction.x >= T::zero(), direction.y >= T::zero()) {
+        (true, true) => 0,
+        (false, true) => 1,
+        (false, false) => 2,
+        (true, false) => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Relate};
+
+    fn assert_polygons_eq(actual: &MultiPolygon<f64>, expected: &MultiPolygon<f64>) {
+        assert_eq!(actual.0.len(), expected.0.len(), "{actual:?}");
+        for polygon in expected {
+            assert!(
+                actual.iter().any(|p| p.relate(polygon).is_equal_topo()),
+                "{polygon:?} missing from {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let polygonized = polygonize(&MultiLineString::<f64>::new(vec![]));
+        assert!(polygonized.polygons.0.is_empty());
+        assert!(polygonized.dangles.0.is_empty());
+        assert!(polygonized.cut_edges.0.is_empty());
+        assert!(polygonized.invalid_rings.0.is_empty());
+    }
+
+    #[test]
+    fn closed_line_string() {
+        let ring = wkt!(LINESTRING(0. 0.,0. 10.,10. 10.,10. 0.,0. 0.));
+        let polygonized = polygonize([&ring]);
+        assert_eq!(
+            polygonized.polygons,
+            wkt!(MULTIPOLYGON(((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.))))
+        );
+    }
+
+    #[test]
+    fn shared_edges() {
+        let lines = wkt!(MULTILINESTRING(
+            (0. 0.,10. 0.),
+            (10. 0.,10. 10.),
+            (10. 10.,0. 10.,0. 0.),
+            (10. 0.,20. 0.,20. 10.,10. 10.)
+        ));
+        let polygonized = polygonize(&lines);
+        assert_polygons_eq(
+            &polygonized.polygons,
+            &wkt!(MULTIPOLYGON(
+                ((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)),
+                ((10. 0.,20. 0.,20. 10.,10. 10.,10. 0.))
+            )),
+        );
+    }
+
+    #[test]
+    fn nested_rings() {
+        let lines = wkt!(MULTILINESTRING(
+            (0. 0.,30. 0.,30. 30.,0. 30.,0. 0.),
+            (10. 10.,20. 10.,20. 20.,10. 20.,10. 10.),
+            (12. 12.,18. 12.,18. 18.,12. 18.,12. 12.)
+        ));
+        let polygonized = polygonize(&lines);
+        assert_polygons_eq(
+            &polygonized.polygons,
+            &wkt!(MULTIPOLYGON(
+                ((0. 0.,30. 0.,30. 30.,0. 30.,0. 0.),(10. 10.,20. 10.,20. 20.,10. 20.,10. 10.)),
+                ((10. 10.,20. 10.,20. 20.,10. 20.,10. 10.),(12. 12.,18. 12.,18. 18.,12. 18.,12. 12.)),
+                ((12. 12.,18. 12.,18. 18.,12. 18.,12. 12.))
+            )),
+        );
+    }
+
+    #[test]
+    fn rings_touching_at_a_node() {
+        // two triangles touching at (10 10), inside a square which touches one of them
+        let lines = wkt!(MULTILINESTRING(
+            (10. 10.,5. 15.,5. 5.),
+            (5. 5.,10. 10.),
+            (10. 10.,15. 5.,15. 15.,10. 10.),
+            (5. 5.,0. 0.),
+            (0. 0.,20. 0.,20. 20.,0. 20.,0. 0.)
+        ));
+        let polygonized = polygonize(&lines);
+        assert_polygons_eq(
+            &polygonized.polygons,
+            &wkt!(MULTIPOLYGON(
+                ((10. 10.,5. 15.,5. 5.,10. 10.)),
+                ((10. 10.,15. 5.,15. 15.,10. 10.)),
+                ((0. 0.,20. 0.,20. 20.,0. 20.,0. 0.),(10. 10.,5. 15.,5. 5.,10. 10.),(10. 10.,15. 5.,15. 15.,10. 10.))
+            )),
+        );
+        assert_eq!(polygonized.cut_edges, wkt!(MULTILINESTRING((5. 5.,0. 0.))));
+    }
+
+    #[test]
+    fn dangles_and_cut_edges() {
+        let lines = wkt!(MULTILINESTRING(
+            (0. 0.,10. 0.,10. 10.),
+            (10. 10.,0. 10.,0. 0.),
+            (10. 10.,20. 10.),
+            (20. 10.,30. 10.,30. 20.),
+            (30. 20.,20. 20.,20. 10.),
+            (30. 20.,40. 30.),
+            (40. 30.,50. 30.),
+            (40. 30.,40. 40.)
+        ));
+        let polygonized = polygonize(&lines);
+        assert_eq!(polygonized.polygons.0.len(), 2);
+        assert_eq!(polygonized.dangles.0.len(), 3);
+        assert_eq!(
+            polygonized.cut_edges,
+            wkt!(MULTILINESTRING((10. 10.,20. 10.)))
+        );
+        assert!(polygonized.invalid_rings.0.is_empty());
+    }
+
+    #[test]
+    fn invalid_ring() {
+        let bowtie = wkt!(LINESTRING(0. 0.,10. 10.,10. 0.,0. 10.,0. 0.));
+        let polygonized = polygonize([&bowtie]);
+        assert!(polygonized.polygons.0.is_empty());
+        assert_eq!(polygonized.invalid_rings.0.len(), 1);
+    }
+
+    #[test]
+    fn jts_test_suite() {
+        jts_test_runner::assert_jts_tests_succeed("*Polygonize*.xml");
+    }
+}
@@ -6,6 +6,7 @@ use super::{
     CoordNode, CoordPos, Direction, Edge, Label, LineIntersector, PlanarGraph, TopologyPosition,
 };
 
+use crate::boundary::BoundaryNodeRule;
 use crate::HasDimensions;
 use crate::{Coord, GeoFloat, GeometryCow, Line, LineString, Point, Polygon};
 
@@ -150,9 +151,9 @@ where
     /// Determine whether a component (node or edge) that appears multiple times in elements
     /// of a Multi-Geometry is in the boundary or the interior of the Geometry
     pub fn determine_boundary(boundary_count: usize) -> CoordPos {
-        // For now, we only support the SFS "Mod-2 Rule"
-        // We could make this configurable if we wanted to support alternative boundary rules.
-        if boundary_count % 2 == 1 {
+        // Relate follows the OGC SFS, which specifies the "Mod-2 Rule". The other rules of
+        // `BoundaryNodeRule` are only offered through `Boundary::boundary_with_rule`.
+        if BoundaryNodeRule::Mod2.is_in_boundary(boundary_count) {
             CoordPos::OnBoundary
         } else {
             CoordPos::Inside
@@ -8,7 +8,7 @@ pub use crate::relate::geomgraph::GeometryGraph;
 use crate::{BoundingRect, GeoFloat, GeometryCow, HasDimensions};
 
 mod edge_end_builder;
-mod geomgraph;
+pub(crate) mod geomgraph;
 mod relate_operation;
 
 /// Topologically relate two geometries based on [DE-9IM](https://en.wikipedia.org/wiki/DE-9IM) semantics.
@@ -93,6 +93,12 @@ macro_rules! relate_impl {
     };
 }
 
+impl<F: GeoFloat> Relate<F> for GeometryCow<'_, F> {
+    fn geometry_graph(&self, arg_index: usize) -> GeometryGraph<'_, F> {
+        GeometryGraph::new(arg_index, self.clone())
+    }
+}
+
 relate_impl![
     Point<F>,
     Line<F>,
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+
+use crate::geometry::*;
+use crate::relate::geomgraph::index::prepare_geometry;
+use crate::{BoundingRect, DistanceWithin, GeoFloat, GeometryCow, PreparedGeometry, Relate};
+
+/// The relationship which a pair of geometries must have to be matched by [`spatial_join()`].
+///
+/// The predicates read from the geometry of the left slice to that of the right slice, so e.g.
+/// `Contains` matches the pairs where the left geometry contains the right one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinPredicate<F: GeoFloat = f64> {
+    Intersects,
+    Contains,
+    Within,
+    Covers,
+    Touches,
+    /// The geometries are within this Euclidean distance of each other
+    WithinDistance(F),
+}
+
+/// Find the pairs of geometries, one from each slice, which satisfy a predicate.
+///
+/// Returns the pairs of indices into `left` and `right` of the matching geometries, sorted.
+///
+/// The bounding rectangles of the smaller of the two slices are bulk loaded into an R*-tree, and
+/// the geometries of the other slice are matched against it one by one. As each geometry of the
+/// smaller slice is typically tested many times, it is turned into a [`PreparedGeometry`] the
+/// first time it is a candidate, and reused for the following tests. Empty geometries never match.
+///
+/// With the `multithreading` feature, which is enabled by default, the larger slice is split into
+/// one block per Rayon thread, and the blocks are matched in parallel. Each block prepares its own
+/// geometries, as a `PreparedGeometry` can't be shared between threads. The geometries and their
+/// coordinate type must then be `Sync`, see [`MaybeSync`]. Without the feature, they needn't be.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{spatial_join, wkt, JoinPredicate, Point};
+///
+/// let districts = [
+///     wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.))),
+///     wkt!(POLYGON((10. 0.,20. 0.,20. 10.,10. 10.,10. 0.))),
+/// ];
+/// let addresses: Vec<Point> = vec![
+///     wkt!(POINT(5. 5.)),
+///     wkt!(POINT(15. 5.)),
+///     wkt!(POINT(25. 5.)),
+///     wkt!(POINT(10. 5.)),
+/// ];
+///
+/// // the last address lies on the border, inside neither district
+/// let found = spatial_join(&districts, &addresses, JoinPredicate::Contains);
+/// assert_eq!(found, vec![(0, 0), (1, 1)]);
+///
+/// // but both districts cover it
+/// let found = spatial_join(&districts, &addresses, JoinPredicate::Covers);
+/// assert_eq!(found, vec![(0, 0), (0, 3), (1, 1), (1, 3)]);
+///
+/// let found = spatial_join(&addresses, &districts, JoinPredicate::WithinDistance(5.0));
+/// assert_eq!(
+///     found,
+///     vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 1), (3, 0), (3, 1)]
+/// );
+/// ```
+pub fn spatial_join<F, L, R>(
+    left: &[L],
+    right: &[R],
+    predicate: JoinPredicate<F>,
+) -> Vec<(usize, usize)>
+where
+    F: GeoFloat + MaybeSync,
+    L: MaybeSync,
+    R: MaybeSync,
+    for<'a> &'a L: Into<GeometryCow<'a, F>>,
+    for<'a> &'a R: Into<GeometryCow<'a, F>>,
+{
+    let left: Vec<GeometryCow<F>> = left.iter().map(|geometry| geometry.into()).collect();
+    let right: Vec<GeometryCow<F>> = right.iter().map(|geometry| geometry.into()).collect();
+
+    // the other predicates all require the geometries to intersect
+    let margin = match predicate {
+        JoinPredicate::WithinDistance(distance) => distance,
+        _ => F::zero(),
+    };
+    let mut pairs = if left.len() <= right.len() {
+        join(&left, &right, margin, |prepared, other| {
+            holds(predicate, prepared, other, false)
+        })
+    } else {
+        let mut pairs = join(&right, &left, margin, |prepared, other| {
+            holds(predicate, prepared, other, true)
+        });
+        for (left_index, right_index) in &mut pairs {
+            std::mem::swap(left_index, right_index);
+        }
+        pairs
+    };
+    pairs.sort_unstable();
+    pairs
+}
+
+/// `Sync` with the `multithreading` feature, which shares the geometries of a [`spatial_join()`]
+/// between threads, and implemented by every type without it.
+#[cfg(feature = "multithreading")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "multithreading")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// `Sync` with the `multithreading` feature, which shares the geometries of a [`spatial_join()`]
+/// between threads, and implemented by every type without it.
+#[cfg(not(feature = "multithreading"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "multithreading"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+type Prepared<'a, F> = PreparedGeometry<'a, GeometryCow<'a, F>, F>;
+
+/// Whether `predicate` holds from `prepared` to `other`, or from `other` to `prepared` if
+/// `flipped`.
+fn holds<F: GeoFloat>(
+    predicate: JoinPredicate<F>,
+    prepared: &Prepared<F>,
+    other: &GeometryCow<F>,
+    flipped: bool,
+) -> bool {
+    match predicate {
+        JoinPredicate::Intersects => prepared.is_within_distance(other, F::zero()),
+        JoinPredicate::WithinDistance(distance) => prepared.is_within_distance(other, distance),
+        JoinPredicate::Touches => prepared.relate(other).is_touches(),
+        JoinPredicate::Contains if flipped => prepared.relate(other).is_within(),
+        JoinPredicate::Contains => prepared.relate(other).is_contains(),
+        JoinPredicate::Within if flipped => prepared.relate(other).is_contains(),
+        JoinPredicate::Within => prepared.relate(other).is_within(),
+        JoinPredicate::Covers if flipped => prepared.relate(other).is_coveredby(),
+        JoinPredicate::Covers => prepared.relate(other).is_covers(),
+    }
+}
+
+/// Match each of `others` against the `indexed` geometries whose bounding rectangles are within
+/// `margin` of its own, returning pairs of indices into `indexed` and `others`.
+fn join<'a, F, P>(
+    indexed: &[GeometryCow<'a, F>],
+    others: &[GeometryCow<F>],
+    margin: F,
+    holds: P,
+) -> Vec<(usize, usize)>
+where
+    F: GeoFloat + MaybeSync,
+    P: Fn(&Prepared<'a, F>, &GeometryCow<F>) -> bool + MaybeSync,
+{
+    let tree = RTree::bulk_load(
+        indexed
+            .iter()
+            .enumerate()
+            .filter_map(|(index, geometry)| {
+                let rect = geometry.bounding_rect()?;
+                Some(GeomWithData::new(
+                    Rectangle::from_corners(rect.min(), rect.max()),
+                    index,
+                ))
+            })
+            .collect(),
+    );
+    let matches = |prepared: &mut HashMap<usize, Prepared<'a, F>>,
+                   other_index: usize,
+                   other: &GeometryCow<F>| {
+        let Some(rect) = other.bounding_rect() else {
+            return vec![];
+        };
+        let margin = Coord::from((margin, margin));
+        let envelope = AABB::from_corners(rect.min() - margin, rect.max() + margin);
+        tree.locate_in_envelope_intersecting(&envelope)
+            .filter(|candidate| {
+                let prepared = prepared
+                    .entry(candidate.data)
+                    .or_insert_with(|| prepare_geometry(indexed[candidate.data].clone()));
+                holds(prepared, other)
+            })
+            .map(|candidate| (candidate.data, other_index))
+            .collect::<Vec<_>>()
+    };
+
+    #[cfg(feature = "multithreading")]
+    {
+        use rayon::prelude::*;
+        // one block per thread, so that each thread prepares a geometry at most once
+        let block_len = others.len().div_ceil(rayon::current_num_threads()).max(1);
+        others
+            .par_chunks(block_len)
+            .enumerate()
+            .flat_map_iter(|(block_index, block)| {
+                let mut prepared = HashMap::new();
+                block
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, other)| {
+                        matches(&mut prepared, block_index * block_len + index, other)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+    #[cfg(not(feature = "multithreading"))]
+    {
+        let mut prepared = HashMap::new();
+        others
+            .iter()
+            .enumerate()
+            .flat_map(|(index, other)| matches(&mut prepared, index, other))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Contains, Distance, Euclidean, HasDimensions, Intersects, Within};
+
+    /// Points on a grid, and polygons overlapping them and each other in various ways.
+    fn fixtures() -> (Vec<Point>, Vec<Geometry>) {
+        let points = (0..20)
+            .flat_map(|x| (0..20).map(move |y| Point::new(x as f64, y as f64)))
+            .collect();
+        let polygons = vec![
+            wkt!(POLYGON((0. 0.,5. 0.,5. 5.,0. 5.,0. 0.))).into(),
+            wkt!(POLYGON((3.5 3.5,12.5 3.5,3.5 12.5,3.5 3.5))).into(),
+            wkt!(LINESTRING(0. 19.,19. 0.)).into(),
+            wkt!(POINT(7. 7.)).into(),
+            Rect::new((10., 10.), (30., 30.)).into(),
+            wkt!(POLYGON EMPTY).into(),
+        ];
+        (points, polygons)
+    }
+
+    fn brute_force<L, R>(
+        left: &[L],
+        right: &[R],
+        f: impl Fn(&L, &R) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let mut pairs = vec![];
+        for (i, l) in left.iter().enumerate() {
+            for (j, r) in right.iter().enumerate() {
+                if f(l, r) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn intersects() {
+        let (points, polygons) = fixtures();
+        let expected = brute_force(&polygons, &points, |a, b| a.intersects(b));
+        assert_eq!(
+            spatial_join(&polygons, &points, JoinPredicate::Intersects),
+            expected
+        );
+        let expected = brute_force(&points, &polygons, |a, b| a.intersects(b));
+        assert_eq!(
+            spatial_join(&points, &polygons, JoinPredicate::Intersects),
+            expected
+        );
+        let expected = brute_force(&polygons, &polygons, |a, b| a.intersects(b));
+        assert_eq!(
+            spatial_join(&polygons, &polygons, JoinPredicate::Intersects),
+            expected
+        );
+    }
+
+    #[test]
+    fn contains_and_within() {
+        let (points, polygons) = fixtures();
+        let expected = brute_force(&polygons, &points, |a, b| a.contains(b));
+        assert_eq!(
+            spatial_join(&polygons, &points, JoinPredicate::Contains),
+            expected
+        );
+        let expected = brute_force(&points, &polygons, |a, b| a.is_within(b));
+        assert_eq!(
+            spatial_join(&points, &polygons, JoinPredicate::Within),
+            expected
+        );
+        let expected = brute_force(&polygons, &polygons, |a, b| a.relate(b).is_contains());
+        assert_eq!(
+            spatial_join(&polygons, &polygons, JoinPredicate::Contains),
+            expected
+        );
+    }
+
+    #[test]
+    fn covers_and_touches() {
+        let (points, polygons) = fixtures();
+        let expected = brute_force(&polygons, &points, |a, b| a.relate(b).is_covers());
+        assert_eq!(
+            spatial_join(&polygons, &points, JoinPredicate::Covers),
+            expected
+        );
+        let expected = brute_force(&points, &polygons, |a, b| a.relate(b).is_covers());
+        assert_eq!(
+            spatial_join(&points, &polygons, JoinPredicate::Covers),
+            expected
+        );
+        let expected = brute_force(&points, &polygons, |a, b| a.relate(b).is_touches());
+        assert_eq!(
+            spatial_join(&points, &polygons, JoinPredicate::Touches),
+            expected
+        );
+    }
+
+    #[test]
+    fn within_distance() {
+        let (points, polygons) = fixtures();
+        let expected = brute_force(&points, &polygons, |a, b| {
+            Euclidean.distance(a, b) <= 1.5 && !b.is_empty()
+        });
+        assert_eq!(
+            spatial_join(&points, &polygons, JoinPredicate::WithinDistance(1.5)),
+            expected
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let (points, _) = fixtures();
+        let nothing: Vec<Polygon> = vec![];
+        assert!(spatial_join(&points, &nothing, JoinPredicate::Intersects).is_empty());
+        assert!(spatial_join(&nothing, &points, JoinPredicate::Intersects).is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, AABB};
+
+use crate::coordinate_position::CoordPos;
+use crate::geometry::*;
+use crate::sweep::{Cross, Intersections, LineOrPoint};
+use crate::utils::lex_cmp;
+use crate::{
+    polygonize, CoordinatePosition, GeoFloat, InteriorPoint, Intersects, LineIntersection,
+};
+
+/// Split a geometry into pieces with a blade.
+///
+/// A `Polygon` or `MultiPolygon` is split by a linear blade into a `MultiPolygon` of the pieces
+/// between the parts of the blade which cross it. Parts of the blade outside of the polygon, along
+/// its boundary, or which end inside it without cutting all the way through don't split it, so a
+/// polygon which isn't cut is returned as is.
+///
+/// A `LineString` or `MultiLineString` is split into a `MultiLineString`, at the points of a
+/// blade which lie on it, or wherever it crosses or touches a linear blade. Where it overlaps a
+/// linear blade, it's split at both ends of the overlap. Points which aren't exactly on the line
+/// don't split it, so they may have to be snapped to it first, e.g. with
+/// [`ClosestPoint`](crate::ClosestPoint).
+///
+/// The pieces are exact: they share the vertices where they were split, so they don't leave
+/// slivers between them.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, Area, Split};
+///
+/// let parcel = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+/// let survey_line = wkt!(LINESTRING(1. -1.,1. 5.));
+///
+/// let pieces = parcel.split(&survey_line);
+/// assert_eq!(pieces.0.len(), 2);
+/// assert_eq!(pieces.unsigned_area(), 16.);
+///
+/// let route = wkt!(LINESTRING(0. 0.,4. 0.,4. 4.));
+/// let stops = wkt!(MULTIPOINT(2. 0.,4. 4.,5. 5.));
+///
+/// assert_eq!(
+///     route.split(&stops),
+///     wkt!(MULTILINESTRING((0. 0.,2. 0.),(2. 0.,4. 0.,4. 4.)))
+/// );
+/// ```
+pub trait Split<Blade> {
+    type Output;
+
+    fn split(&self, blade: &Blade) -> Self::Output;
+}
+
+// seal the traits of the blades, so that they don't leak into the public interface.
+mod private {
+    use super::*;
+
+    /// The parts of a blade.
+    pub trait Blade<T: GeoFloat> {
+        fn blade_segments(&self) -> Vec<Line<T>> {
+            vec![]
+        }
+
+        fn blade_points(&self) -> Vec<Coord<T>> {
+            vec![]
+        }
+    }
+
+    /// A blade which can split polygons.
+    pub trait LinearBlade<T: GeoFloat>: Blade<T> {}
+}
+
+use private::{Blade, LinearBlade};
+
+impl<T: GeoFloat> Blade<T> for Point<T> {
+    fn blade_points(&self) -> Vec<Coord<T>> {
+        vec![self.0]
+    }
+}
+
+impl<T: GeoFloat> Blade<T> for MultiPoint<T> {
+    fn blade_points(&self) -> Vec<Coord<T>> {
+        self.iter().map(|point| point.0).collect()
+    }
+}
+
+impl<T: GeoFloat> Blade<T> for Line<T> {
+    fn blade_segments(&self) -> Vec<Line<T>> {
+        vec![*self]
+    }
+}
+
+impl<T: GeoFloat> Blade<T> for LineString<T> {
+    fn blade_segments(&self) -> Vec<Line<T>> {
+        self.lines().collect()
+    }
+}
+
+impl<T: GeoFloat> Blade<T> for MultiLineString<T> {
+    fn blade_segments(&self) -> Vec<Line<T>> {
+        self.iter()
+            .flat_map(|line_string| line_string.lines())
+            .collect()
+    }
+}
+
+impl<T: GeoFloat> LinearBlade<T> for Line<T> {}
+impl<T: GeoFloat> LinearBlade<T> for LineString<T> {}
+impl<T: GeoFloat> LinearBlade<T> for MultiLineString<T> {}
+
+impl<T: GeoFloat, B: LinearBlade<T>> Split<B> for Polygon<T> {
+    type Output = MultiPolygon<T>;
+
+    fn split(&self, blade: &B) -> Self::Output {
+        MultiPolygon::new(split_polygon(self, &blade.blade_segments()))
+    }
+}
+
+impl<T: GeoFloat, B: LinearBlade<T>> Split<B> for MultiPolygon<T> {
+    type Output = MultiPolygon<T>;
+
+    fn split(&self, blade: &B) -> Self::Output {
+        let blade_segments = blade.blade_segments();
+        self.iter()
+            .flat_map(|polygon| split_polygon(polygon, &blade_segments))
+            .collect()
+    }
+}
+
+impl<T: GeoFloat, B: Blade<T>> Split<B> for LineString<T> {
+    type Output = MultiLineString<T>;
+
+    fn split(&self, blade: &B) -> Self::Output {
+        let (blade_segments, blade_points) = (blade.blade_segments(), blade.blade_points());
+        MultiLineString::new(split_line_string(self, &blade_segments, &blade_points))
+    }
+}
+
+impl<T: GeoFloat, B: Blade<T>> Split<B> for MultiLineString<T> {
+    type Output = MultiLineString<T>;
+
+    fn split(&self, blade: &B) -> Self::Output {
+        let (blade_segments, blade_points) = (blade.blade_segments(), blade.blade_points());
+        self.iter()
+            .flat_map(|line_string| split_line_string(line_string, &blade_segments, &blade_points))
+            .collect()
+    }
+}
+
+/// A segment of the linework to node.
+#[derive(Debug, Clone)]
+struct NodingSegment<T: GeoFloat> {
+    line: Line<T>,
+    index: usize,
+}
+
+impl<T: GeoFloat> Cross for NodingSegment<T> {
+    type Scalar = T;
+
+    fn line(&self) -> LineOrPoint<T> {
+        self.line.into()
+    }
+}
+
+/// The points at which each segment meets the segments it's noded against, according to
+/// `is_noded(a, b)`, and the `points` which lie on it.
+fn nodes<T: GeoFloat>(
+    segments: &[Line<T>],
+    is_noded: impl Fn(usize, usize) -> bool,
+    points: &[Coord<T>],
+) -> Vec<Vec<Coord<T>>> {
+    let mut nodes = vec![vec![]; segments.len()];
+    let noding = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.start != line.end)
+        .map(|(index, &line)| NodingSegment { line, index });
+    for (a, b, intersection) in Intersections::from_iter(noding) {
+        if !is_noded(a.index, b.index) {
+            continue;
+        }
+        for segment in [&a, &b] {
+            match intersection {
+                LineIntersection::SinglePoint { intersection, .. } => {
+                    nodes[segment.index].push(intersection)
+                }
+                LineIntersection::Collinear { intersection } => {
+                    nodes[segment.index].extend([intersection.start, intersection.end])
+                }
+            }
+        }
+    }
+
+    if !points.is_empty() {
+        let tree = RTree::bulk_load(
+            segments
+                .iter()
+                .enumerate()
+                .map(|(index, &line)| GeomWithData::new(line, index))
+                .collect(),
+        );
+        for &point in points {
+            let envelope = AABB::from_point(Point(point));
+            for segment in tree.locate_in_envelope_intersecting(&envelope) {
+                if segment.geom().intersects(&point) {
+                    nodes[segment.data].push(point);
+                }
+            }
+        }
+    }
+    nodes
+}
+
+/// The nodes in the interior of a segment, in order along it.
+fn interior_nodes<T: GeoFloat>(line: Line<T>, nodes: &[Coord<T>]) -> Vec<Coord<T>> {
+    let delta = line.delta();
+    let param = |coord: Coord<T>| {
+        let offset = coord - line.start;
+        offset.x * delta.x + offset.y * delta.y
+    };
+    let mut nodes: Vec<(T, Coord<T>)> = nodes
+        .iter()
+        .filter(|&&node| node != line.start && node != line.end)
+        .map(|&node| (param(node), node))
+        .collect();
+    nodes.sort_by(|a, b| a.0.total_cmp(&b.0));
+    nodes.dedup_by(|a, b| a.1 == b.1);
+    nodes.into_iter().map(|(_, node)| node).collect()
+}
+
+fn split_line_string<T: GeoFloat>(
+    line_string: &LineString<T>,
+    blade_segments: &[Line<T>],
+    blade_points: &[Coord<T>],
+) -> Vec<LineString<T>> {
+    let mut segments: Vec<Line<T>> = line_string.lines().collect();
+    let count = segments.len();
+    segments.extend(blade_segments);
+    // the line is only split by the blade, not where it crosses itself
+    let nodes = nodes(&segments, |a, b| (a < count) != (b < count), blade_points);
+
+    let mut pieces = vec![];
+    let mut piece: Vec<Coord<T>> = line_string.0.first().into_iter().copied().collect();
+    let mut cut = |piece: &mut Vec<Coord<T>>, at: Coord<T>| {
+        if piece.len() > 1 {
+            pieces.push(LineString::new(std::mem::replace(piece, vec![at])));
+        }
+    };
+    for (segment, nodes) in segments[..count].iter().zip(&nodes) {
+        if nodes.contains(&segment.start) {
+            cut(&mut piece, segment.start);
+        }
+        for node in interior_nodes(*segment, nodes) {
+            piece.push(node);
+            cut(&mut piece, node);
+        }
+        piece.push(segment.end);
+        if nodes.contains(&segment.end) {
+            cut(&mut piece, segment.end);
+        }
+    }
+    if piece.len() > 1 {
+        pieces.push(LineString::new(piece));
+    }
+    pieces
+}
+
+fn split_polygon<T: GeoFloat>(polygon: &Polygon<T>, blade_segments: &[Line<T>]) -> Vec<Polygon<T>> {
+    let mut segments: Vec<Line<T>> = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .flat_map(|ring| ring.lines())
+        .collect();
+    let count = segments.len();
+    segments.extend(blade_segments);
+    // node all the linework, including the blade against itself, so that it can be polygonized
+    let nodes = nodes(&segments, |_, _| true, &[]);
+
+    let mut pieces = vec![];
+    for (index, (&segment, nodes)) in segments.iter().zip(&nodes).enumerate() {
+        let mut coords = vec![segment.start];
+        coords.extend(interior_nodes(segment, nodes));
+        coords.push(segment.end);
+        for pair in coords.windows(2) {
+            let piece = if lex_cmp(&pair[0], &pair[1]).is_le() {
+                Line::new(pair[0], pair[1])
+            } else {
+                Line::new(pair[1], pair[0])
+            };
+            if piece.start == piece.end {
+                continue;
+            }
+            let midpoint = (piece.start + piece.end) / (T::one() + T::one());
+            if index >= count && polygon.coordinate_position(&midpoint) != CoordPos::Inside {
+                continue;
+            }
+            pieces.push(piece);
+        }
+    }
+    pieces.sort_by(|a, b| lex_cmp(&a.start, &b.start).then_with(|| lex_cmp(&a.end, &b.end)));
+    pieces.dedup();
+
+    let lines: Vec<LineString<T>> = pieces.into_iter().map(LineString::from).collect();
+    let faces: Vec<Polygon<T>> = polygonize(&lines)
+        .polygons
+        .into_iter()
+        .filter(|face| {
+            // the holes of the polygon are faces too
+            face.interior_point()
+                .is_some_and(|point| polygon.coordinate_position(&point.0) == CoordPos::Inside)
+        })
+        .collect();
+    if faces.len() < 2 {
+        return vec![polygon.clone()];
+    }
+    faces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coord, wkt, Area, Contains, Validation};
+
+    #[test]
+    fn polygon_by_line_string() {
+        let polygon = wkt!(POLYGON((0. 0.,10. 0.,10. 10.,0. 10.,0. 0.)));
+        // a zigzag which crosses the polygon twice
+        let blade = wkt!(LINESTRING(5. 11.,5. 2.,11. 2.));
+        let pieces = polygon.split(&blade);
+        assert_eq!(pieces.0.len(), 2);
+        assert_relative_eq!(pieces.unsigned_area(), 100.);
+        let corner = pieces
+            .iter()
+            .find(|piece| piece.contains(&coord! { x: 8., y: 8. }))
+            .unwrap();
+        assert_relative_eq!(corner.unsigned_area(), 40.);
+        for piece in &pieces {
+            assert!(piece.is_valid());
+        }
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let polygon = wkt!(POLYGON(
+            (0. 0.,10. 0.,10. 10.,0. 10.,0. 0.),
+            (4. 4.,6. 4.,6. 6.,4. 6.,4. 4.)
+        ));
+        // through the hole
+        let pieces = polygon.split(&wkt!(LINESTRING(5. 0.,5. 10.)));
+        assert_eq!(pieces.0.len(), 2);
+        assert_relative_eq!(pieces.unsigned_area(), 96.);
+
+        // beside the hole, which stays in one of the pieces
+        let pieces = polygon.split(&wkt!(LINESTRING(2. 0.,2. 10.)));
+        assert_eq!(pieces.0.len(), 2);
+        assert_relative_eq!(pieces.unsigned_area(), 96.);
+        assert!(pieces.iter().any(|piece| piece.interiors().len() == 1));
+    }
+
+    #[test]
+    fn crossing_blades() {
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+        let blade = wkt!(MULTILINESTRING((2. 0.,2. 4.),(0. 2.,4. 2.)));
+        let pieces = polygon.split(&blade);
+        assert_eq!(pieces.0.len(), 4);
+        for piece in &pieces {
+            assert_relative_eq!(piece.unsigned_area(), 4.);
+        }
+    }
+
+    #[test]
+    fn polygon_not_cut() {
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+        for blade in [
+            // outside
+            wkt!(LINESTRING(5. 0.,5. 4.)),
+            // ending inside
+            wkt!(LINESTRING(2. 5.,2. 2.)),
+            // along the boundary
+            wkt!(LINESTRING(0. 0.,4. 0.)),
+        ] {
+            assert_eq!(
+                polygon.split(&blade),
+                MultiPolygon::new(vec![polygon.clone()])
+            );
+        }
+    }
+
+    #[test]
+    fn multi_polygon() {
+        let multi_polygon = wkt!(MULTIPOLYGON(
+            ((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)),
+            ((3. 0.,5. 0.,5. 2.,3. 2.,3. 0.))
+        ));
+        let blade = Line::new(coord! { x: 0., y: 1. }, coord! { x: 5., y: 1. });
+        let pieces = multi_polygon.split(&blade);
+        assert_eq!(pieces.0.len(), 4);
+        assert_relative_eq!(pieces.unsigned_area(), 8.);
+    }
+
+    #[test]
+    fn line_string_by_points() {
+        let line_string = wkt!(LINESTRING(0. 0.,2. 0.,2. 2.));
+        // at a vertex, in the interior of a segment twice, and off the line
+        let points = wkt!(MULTIPOINT(2. 0.,1. 0.,1. 0.,2. 1.,3. 3.));
+        assert_eq!(
+            line_string.split(&points),
+            wkt!(MULTILINESTRING((0. 0.,1. 0.),(1. 0.,2. 0.),(2. 0.,2. 1.),(2. 1.,2. 2.)))
+        );
+
+        // at the ends
+        let points = wkt!(MULTIPOINT(0. 0.,2. 2.));
+        assert_eq!(
+            line_string.split(&points),
+            MultiLineString::new(vec![line_string.clone()])
+        );
+        assert_eq!(
+            line_string.split(&wkt!(POINT(0.5 0.))),
+            wkt!(MULTILINESTRING((0. 0.,0.5 0.),(0.5 0.,2. 0.,2. 2.)))
+        );
+    }
+
+    #[test]
+    fn line_string_by_lines() {
+        let line_string = wkt!(LINESTRING(0. 1.,4. 1.,4. 5.));
+        let blade = wkt!(MULTILINESTRING((1. 2.,1. 0.),(3. 4.,5. 4.)));
+        assert_eq!(
+            line_string.split(&blade),
+            wkt!(MULTILINESTRING((0. 1.,1. 1.),(1. 1.,4. 1.,4. 4.),(4. 4.,4. 5.)))
+        );
+
+        // overlapping the blade
+        let blade = wkt!(LINESTRING(2. 1.,6. 1.));
+        assert_eq!(
+            line_string.split(&blade),
+            wkt!(MULTILINESTRING((0. 1.,2. 1.),(2. 1.,4. 1.),(4. 1.,4. 5.)))
+        );
+
+        // a self-crossing line isn't split where it crosses itself
+        let line_string = wkt!(LINESTRING(0. 0.,2. 2.,2. 0.,0. 2.));
+        let blade = wkt!(LINESTRING(3. 1.,5. 1.));
+        assert_eq!(
+            line_string.split(&blade),
+            MultiLineString::new(vec![line_string.clone()])
+        );
+    }
+
+    #[test]
+    fn multi_line_string() {
+        let lines = wkt!(MULTILINESTRING((0. 1.,2. 1.),(0. 2.,2. 2.)));
+        let blade = wkt!(LINESTRING(1. 0.,1. 3.));
+        assert_eq!(lines.split(&blade).0.len(), 4);
+    }
+}
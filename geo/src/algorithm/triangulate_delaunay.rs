@@ -1,6 +1,9 @@
+use std::collections::{HashMap, HashSet};
+
 use geo_types::{Coord, Line, Point, Triangle};
 use spade::{
-    ConstrainedDelaunayTriangulation, DelaunayTriangulation, Point2, SpadeNum, Triangulation,
+    AngleLimit, ConstrainedDelaunayTriangulation, DelaunayTriangulation, Point2,
+    RefinementParameters, SpadeNum, Triangulation,
 };
 
 use crate::{
@@ -33,6 +36,46 @@ where
     }
 }
 
+/// Parameters for the Delaunay refinement of a constrained triangulation, see
+/// [`TriangulateDelaunay::refined_triangulation`].
+///
+/// This implements the `Default` trait, which limits the angles of the triangles to 30 degrees
+/// and leaves their area unbounded.
+#[derive(Debug, Clone)]
+pub struct RefinementConfig<T: SpadeTriangulationFloat> {
+    /// The smallest allowed inner angle of a triangle, in degrees. Limits much larger than 30
+    /// degrees may keep the refinement from completing
+    pub min_angle: T,
+    /// The largest allowed area of a triangle
+    pub max_area: Option<T>,
+    /// Triangles smaller than this area aren't refined any further, even if their angles are too
+    /// small. This can keep the refinement from exhausting `max_additional_vertices` near sharp
+    /// corners of the input
+    pub min_area: Option<T>,
+    /// The maximum number of Steiner points to insert. Defaults to ten times the number of input
+    /// vertices
+    pub max_additional_vertices: Option<usize>,
+    /// By default, constraint edges are split by Steiner points as needed, with the parts still
+    /// following the lines of the input. Setting this prevents constraint edges from being split,
+    /// at the cost of the angle limit not being met next to long constraint edges
+    pub keep_constraint_edges: bool,
+}
+
+impl<T> Default for RefinementConfig<T>
+where
+    T: SpadeTriangulationFloat,
+{
+    fn default() -> Self {
+        Self {
+            min_angle: <T as std::convert::From<f32>>::from(30.0),
+            max_area: None,
+            min_area: None,
+            max_additional_vertices: None,
+            keep_constraint_edges: false,
+        }
+    }
+}
+
 // ====== Error ========
 
 #[derive(Debug)]
@@ -40,6 +83,8 @@ pub enum TriangulationError {
     SpadeError(spade::InsertionError),
     LoopTrap,
     ConstraintFailure,
+    /// The `min_angle` of a [`RefinementConfig`] isn't a finite number of degrees
+    InvalidMinAngle,
 }
 
 impl std::fmt::Display for TriangulationError {
@@ -61,6 +106,46 @@ impl<T: GeoFloat + SpadeNum> SpadeTriangulationFloat for T {}
 
 pub type Triangles<T> = Vec<Triangle<T>>;
 
+/// A triangle mesh which shares vertices between its triangles, and records which triangles are
+/// adjacent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangleMesh<T: SpadeTriangulationFloat> {
+    /// The vertices of the mesh
+    pub vertices: Vec<Coord<T>>,
+    /// The indices into `vertices` of the corners of each triangle, in counter-clockwise order
+    pub triangles: Vec<[usize; 3]>,
+    /// For each triangle, the index of the triangle across each of its edges, or `None` on the
+    /// boundary of the mesh. The edge `i` runs from corner `i` to corner `(i + 1) % 3`
+    pub neighbors: Vec<[Option<usize>; 3]>,
+    /// Whether the mesh fulfills the [`RefinementConfig`]. This is `false` if the refinement ran
+    /// out of additional vertices
+    pub refinement_complete: bool,
+}
+
+impl<T: SpadeTriangulationFloat> TriangleMesh<T> {
+    /// The triangle at `index` as a geometry
+    pub fn triangle(&self, index: usize) -> Triangle<T> {
+        let [a, b, c] = self.triangles[index];
+        Triangle::new(self.vertices[a], self.vertices[b], self.vertices[c])
+    }
+
+    /// Iterate over all triangles of the mesh as geometries
+    pub fn triangles_iter(&self) -> impl Iterator<Item = Triangle<T>> + '_ {
+        (0..self.triangles.len()).map(|index| self.triangle(index))
+    }
+
+    /// For each vertex, the indices of the triangles which it is a corner of
+    pub fn vertex_triangles(&self) -> Vec<Vec<usize>> {
+        let mut vertex_triangles = vec![vec![]; self.vertices.len()];
+        for (index, triangle) in self.triangles.iter().enumerate() {
+            for &vertex in triangle {
+                vertex_triangles[vertex].push(index);
+            }
+        }
+        vertex_triangles
+    }
+}
+
 // seal the trait that needs to be implemented for TriangulateDelaunay to be implemented. This is done
 // so that we don't leak these weird methods on the public interface.
 mod private {
@@ -204,23 +289,7 @@ where
     ) -> TriangulationResult<Triangles<T>> {
         let lines = self.lines();
         let lines = Self::cleanup_lines(lines, config.snap_radius)?;
-        lines
-            .into_iter()
-            .map(to_spade_line)
-            .try_fold(
-                ConstrainedDelaunayTriangulation::<Point2<T>>::new(),
-                |mut cdt, [start, end]| {
-                    let start = cdt.insert(start).map_err(TriangulationError::SpadeError)?;
-                    let end = cdt.insert(end).map_err(TriangulationError::SpadeError)?;
-                    // safety check (to prevent panic) whether we can add the line
-                    if !cdt.can_add_constraint(start, end) {
-                        return Err(TriangulationError::ConstraintFailure);
-                    }
-                    cdt.add_constraint(start, end);
-                    Ok(cdt)
-                },
-            )
-            .map(triangulation_to_triangles)
+        constrained_delaunay_triangulation(lines).map(triangulation_to_triangles)
     }
 
     /// returns triangulation that's based on the points of the geometric object and also
@@ -286,6 +355,137 @@ where
                     .collect::<Vec<_>>()
             })
     }
+
+    /// returns a quality mesh of the geometric object, which refines the constrained
+    /// triangulation with additional vertices (Steiner points) until its triangles meet the
+    /// angle and area limits of the `refinement` config
+    ///
+    /// The mesh still follows the lines of the input geometry, and like
+    /// `constrained_triangulation` it only includes the triangles inside of the input geometry.
+    /// Adjacent triangles share their vertices, which makes the mesh suitable for finite element
+    /// analysis or terrain modelling.
+    ///
+    /// Fails with `TriangulationError::InvalidMinAngle` if the `min_angle` of the refinement
+    /// config isn't finite.
+    ///
+    /// ```rust
+    /// use geo::TriangulateDelaunay;
+    /// use geo::triangulate_delaunay::RefinementConfig;
+    /// use geo::{Area, Polygon, LineString, Coord};
+    /// let u_shape = Polygon::new(
+    ///     LineString::new(vec![
+    ///         Coord { x: 0.0, y: 0.0 },
+    ///         Coord { x: 1.0, y: 0.0 },
+    ///         Coord { x: 1.0, y: 1.0 },
+    ///         Coord { x: 2.0, y: 1.0 },
+    ///         Coord { x: 2.0, y: 0.0 },
+    ///         Coord { x: 3.0, y: 0.0 },
+    ///         Coord { x: 3.0, y: 3.0 },
+    ///         Coord { x: 0.0, y: 3.0 },
+    ///     ]),
+    ///     vec![],
+    /// );
+    /// let refinement = RefinementConfig {
+    ///     max_area: Some(0.1),
+    ///     ..Default::default()
+    /// };
+    /// let mesh = u_shape
+    ///     .refined_triangulation(Default::default(), refinement)
+    ///     .unwrap();
+    /// assert!(mesh.refinement_complete);
+    /// assert!(mesh.triangles_iter().all(|triangle| triangle.unsigned_area() <= 0.1));
+    /// ```
+    fn refined_triangulation(
+        &'a self,
+        config: DelaunayTriangulationConfig<T>,
+        refinement: RefinementConfig<T>,
+    ) -> TriangulationResult<TriangleMesh<T>> {
+        let lines = self.lines();
+        let lines = Self::cleanup_lines(lines, config.snap_radius)?;
+        let mut cdt = constrained_delaunay_triangulation(lines)?;
+
+        let min_angle = refinement
+            .min_angle
+            .to_f64()
+            .filter(|min_angle| min_angle.is_finite())
+            .ok_or(TriangulationError::InvalidMinAngle)?;
+        let mut parameters = RefinementParameters::new()
+            .with_angle_limit(AngleLimit::from_deg(min_angle))
+            .exclude_outer_faces(true);
+        if let Some(max_area) = refinement.max_area {
+            parameters = parameters.with_max_allowed_area(max_area);
+        }
+        if let Some(min_area) = refinement.min_area {
+            parameters = parameters.with_min_required_area(min_area);
+        }
+        if let Some(max_additional_vertices) = refinement.max_additional_vertices {
+            parameters = parameters.with_max_additional_vertices(max_additional_vertices);
+        }
+        if refinement.keep_constraint_edges {
+            parameters = parameters.keep_constraint_edges();
+        }
+        let result = cdt.refine(parameters);
+
+        let excluded = result.excluded_faces.into_iter().collect::<HashSet<_>>();
+        let faces = cdt
+            .inner_faces()
+            .filter(|face| !excluded.contains(&face.fix()))
+            .filter(|face| {
+                let triangle = Triangle::from(face.positions().map(to_coord));
+                self.contains_point(triangle.centroid())
+            })
+            .collect::<Vec<_>>();
+        let face_indices = faces
+            .iter()
+            .enumerate()
+            .map(|(index, face)| (face.fix(), index))
+            .collect::<HashMap<_, _>>();
+
+        let mut vertex_indices = HashMap::new();
+        let mut vertices = vec![];
+        let mut triangles = Vec::with_capacity(faces.len());
+        let mut neighbors = Vec::with_capacity(faces.len());
+        for face in faces {
+            let edges = face.adjacent_edges();
+            triangles.push(edges.map(|edge| {
+                let vertex = edge.from();
+                *vertex_indices.entry(vertex.fix()).or_insert_with(|| {
+                    vertices.push(to_coord(vertex.position()));
+                    vertices.len() - 1
+                })
+            }));
+            neighbors.push(edges.map(|edge| {
+                let neighbor = edge.rev().face().as_inner()?;
+                face_indices.get(&neighbor.fix()).copied()
+            }));
+        }
+
+        Ok(TriangleMesh {
+            vertices,
+            triangles,
+            neighbors,
+            refinement_complete: result.refinement_complete,
+        })
+    }
+}
+
+/// builds a constrained Delaunay triangulation from lines which don't intersect each other
+fn constrained_delaunay_triangulation<T: SpadeTriangulationFloat>(
+    lines: Vec<Line<T>>,
+) -> TriangulationResult<ConstrainedDelaunayTriangulation<Point2<T>>> {
+    lines.into_iter().map(to_spade_line).try_fold(
+        ConstrainedDelaunayTriangulation::<Point2<T>>::new(),
+        |mut cdt, [start, end]| {
+            let start = cdt.insert(start).map_err(TriangulationError::SpadeError)?;
+            let end = cdt.insert(end).map_err(TriangulationError::SpadeError)?;
+            // safety check (to prevent panic) whether we can add the line
+            if !cdt.can_add_constraint(start, end) {
+                return Err(TriangulationError::ConstraintFailure);
+            }
+            cdt.add_constraint(start, end);
+            Ok(cdt)
+        },
+    )
 }
 
 /// conversion from spade triangulation back to geo triangles
@@ -580,6 +780,14 @@ fn to_spade_line<T: SpadeTriangulationFloat>(line: Line<T>) -> [Point2<T>; 2] {
     [to_spade_point(line.start), to_spade_point(line.end)]
 }
 
+/// converts spade's Point2 back to a Coord
+fn to_coord<T: SpadeTriangulationFloat>(point: Point2<T>) -> Coord<T> {
+    Coord {
+        x: point.x,
+        y: point.y,
+    }
+}
+
 /// converts Coord to something somewhat similar in the spade world
 fn to_spade_point<T: SpadeTriangulationFloat>(coord: Coord<T>) -> Point2<T> {
     Point2::new(coord.x, coord.y)
@@ -784,4 +992,145 @@ mod spade_triangulation {
             assert_num_triangles(&constrained_triangulation, 6);
         }
     }
+
+    fn square_with_hole() -> Polygon<f64> {
+        Polygon::new(
+            LineString::new(vec![
+                Coord { x: 0.0, y: 0.0 },
+                Coord { x: 4.0, y: 0.0 },
+                Coord { x: 4.0, y: 4.0 },
+                Coord { x: 0.0, y: 4.0 },
+            ]),
+            vec![LineString::new(vec![
+                Coord { x: 1.0, y: 1.0 },
+                Coord { x: 2.0, y: 1.0 },
+                Coord { x: 2.0, y: 2.0 },
+                Coord { x: 1.0, y: 2.0 },
+            ])],
+        )
+    }
+
+    fn min_angle(triangle: Triangle<f64>) -> f64 {
+        let [a, b, c] = triangle.to_array();
+        let angle = |at: Coord<f64>, p: Coord<f64>, q: Coord<f64>| {
+            let (u, v) = (p - at, q - at);
+            (u.x * v.y - u.y * v.x)
+                .abs()
+                .atan2(u.x * v.x + u.y * v.y)
+                .to_degrees()
+        };
+        angle(a, b, c).min(angle(b, c, a)).min(angle(c, a, b))
+    }
+
+    #[test]
+    fn refined_triangulation_meets_limits() {
+        use crate::Area;
+
+        let polygon = square_with_hole();
+        let refinement = RefinementConfig {
+            max_area: Some(0.25),
+            ..Default::default()
+        };
+        let mesh = polygon
+            .refined_triangulation(Default::default(), refinement)
+            .unwrap();
+        assert!(mesh.refinement_complete);
+        assert!(mesh.triangles.len() > 2 * 15);
+
+        let area: f64 = mesh.triangles_iter().map(|t| t.unsigned_area()).sum();
+        assert_relative_eq!(area, polygon.unsigned_area(), epsilon = 1e-9);
+        for triangle in mesh.triangles_iter() {
+            assert!(triangle.unsigned_area() <= 0.25);
+            assert!(min_angle(triangle) >= 30.0 - 1e-6);
+            assert!(!polygon.interiors()[0].contains(&triangle.centroid()));
+        }
+    }
+
+    #[test]
+    fn refined_triangulation_adjacency() {
+        let polygon = square_with_hole();
+        let mesh = polygon
+            .refined_triangulation(Default::default(), Default::default())
+            .unwrap();
+
+        let mut boundary_edges = 0;
+        for (index, (triangle, neighbors)) in mesh.triangles.iter().zip(&mesh.neighbors).enumerate()
+        {
+            for edge in 0..3 {
+                let (from, to) = (triangle[edge], triangle[(edge + 1) % 3]);
+                match neighbors[edge] {
+                    // the neighbor has the same edge in the opposite direction
+                    Some(neighbor) => {
+                        let other = mesh.triangles[neighbor];
+                        let other_edge = (0..3).find(|&i| other[i] == to).unwrap();
+                        assert_eq!(other[(other_edge + 1) % 3], from);
+                        assert_eq!(mesh.neighbors[neighbor][other_edge], Some(index));
+                    }
+                    None => {
+                        boundary_edges += 1;
+                        let line = Line::new(mesh.vertices[from], mesh.vertices[to]);
+                        assert!(
+                            polygon.exterior().contains(&line)
+                                || polygon.interiors()[0].contains(&line)
+                        );
+                    }
+                }
+            }
+        }
+        assert!(boundary_edges >= 8);
+
+        for (vertex, triangles) in mesh.vertex_triangles().into_iter().enumerate() {
+            assert!(!triangles.is_empty());
+            assert!(triangles
+                .iter()
+                .all(|&t| mesh.triangles[t].contains(&vertex)));
+        }
+    }
+
+    #[test]
+    fn refined_triangulation_keeps_constraint_edges() {
+        let polygon = square_with_hole();
+        let refinement = RefinementConfig {
+            max_area: Some(0.25),
+            keep_constraint_edges: true,
+            ..Default::default()
+        };
+        let mesh = polygon
+            .refined_triangulation(Default::default(), refinement)
+            .unwrap();
+
+        // no Steiner points on the outline
+        let on_outline = mesh
+            .vertices
+            .iter()
+            .filter(|&&v| polygon.exterior().contains(&v) || polygon.interiors()[0].contains(&v))
+            .count();
+        assert_eq!(on_outline, 8);
+    }
+
+    #[test]
+    fn refined_triangulation_limits_vertices() {
+        let polygon = square_with_hole();
+        let refinement = RefinementConfig {
+            max_area: Some(0.001),
+            max_additional_vertices: Some(10),
+            ..Default::default()
+        };
+        let mesh = polygon
+            .refined_triangulation(Default::default(), refinement)
+            .unwrap();
+        assert!(!mesh.refinement_complete);
+        assert!(mesh.vertices.len() <= 8 + 10);
+    }
+
+    #[test]
+    fn refined_triangulation_rejects_invalid_min_angle() {
+        let polygon = square_with_hole();
+        let refinement = RefinementConfig {
+            min_angle: f64::NAN,
+            ..Default::default()
+        };
+        let result = polygon.refined_triangulation(Default::default(), refinement);
+        assert!(matches!(result, Err(TriangulationError::InvalidMinAngle)));
+    }
 }
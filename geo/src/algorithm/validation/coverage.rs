@@ -0,0 +1,381 @@
+use std::collections::BTreeMap;
+
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, RTreeObject, AABB};
+
+use crate::utils::touch_at_ends;
+use crate::{Coord, GeoFloat, Line, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+use crate::{CoordsIter, Winding};
+
+/// Check that a set of polygons forms a valid polygonal coverage, like the regions of a map.
+///
+/// In a valid coverage, the polygons don't overlap, and where they meet they share their common
+/// boundary vertex for vertex. Each polygon must be valid on its own as well, which isn't
+/// checked here, see [`Validation`](crate::Validation).
+///
+/// A segment of a polygon is invalid if it
+/// - lies in the interior of another polygon,
+/// - crosses or partially overlaps a segment of another polygon, or touches one anywhere but at
+///   their ends,
+/// - is shared with another polygon which lies on the same side of it, or
+/// - runs alongside a segment of another polygon, at a distance of at most `gap_width`, without
+///   being shared with any polygon, which means there's a narrow gap between them. Gaps aren't
+///   checked if `gap_width` isn't positive.
+///
+/// The segments are indexed in an R-tree, so large coverages can be validated.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{wkt, CoverageValidation};
+///
+/// let coverage = [
+///     wkt!(POLYGON((0. 0.,1. 0.,1. 1.,0. 1.,0. 0.))),
+///     wkt!(POLYGON((1. 0.,2. 0.,2. 1.,1. 1.,1. 0.))),
+///     // a small gap to the right of the second polygon
+///     wkt!(POLYGON((2.01 0.,3. 0.,3. 1.,2.01 1.,2.01 0.))),
+/// ];
+///
+/// assert!(coverage.is_valid_coverage(0.001));
+/// assert!(!coverage.is_valid_coverage(0.1));
+///
+/// let invalid_edges = coverage.coverage_invalid_edges(0.1);
+/// assert_eq!(invalid_edges[0], wkt!(MULTILINESTRING EMPTY));
+/// assert_eq!(invalid_edges[1], wkt!(MULTILINESTRING((2. 0.,2. 1.))));
+/// assert_eq!(invalid_edges[2], wkt!(MULTILINESTRING((2.01 1.,2.01 0.))));
+/// ```
+pub trait CoverageValidation<T: GeoFloat> {
+    /// The invalid edges of each polygon, in the order of the polygons, as `LineString`s of
+    /// consecutive invalid segments. A valid polygon has no invalid edges.
+    fn coverage_invalid_edges(&self, gap_width: T) -> Vec<MultiLineString<T>>;
+
+    /// Whether the polygons form a valid coverage, without gaps narrower than `gap_width`.
+    fn is_valid_coverage(&self, gap_width: T) -> bool {
+        self.coverage_invalid_edges(gap_width)
+            .iter()
+            .all(|edges| edges.0.is_empty())
+    }
+}
+
+impl<T: GeoFloat> CoverageValidation<T> for [Polygon<T>] {
+    fn coverage_invalid_edges(&self, gap_width: T) -> Vec<MultiLineString<T>> {
+        invalid_edges(self, gap_width)
+    }
+}
+
+impl<T: GeoFloat> CoverageValidation<T> for MultiPolygon<T> {
+    fn coverage_invalid_edges(&self, gap_width: T) -> Vec<MultiLineString<T>> {
+        invalid_edges(&self.0, gap_width)
+    }
+}
+
+/// The position of a segment in the coverage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SegmentId {
+    polygon: usize,
+    ring: usize,
+    index: usize,
+}
+
+fn invalid_edges<T: GeoFloat>(polygons: &[Polygon<T>], gap_width: T) -> Vec<MultiLineString<T>> {
+    let rings: Vec<Vec<&LineString<T>>> = polygons
+        .iter()
+        .map(|polygon| {
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .collect()
+        })
+        .collect();
+    // whether the interior of the polygon lies to the left of the segments of each ring
+    let interior_on_left: Vec<Vec<bool>> = rings
+        .iter()
+        .map(|rings| {
+            rings
+                .iter()
+                .enumerate()
+                .map(|(ring, line_string)| (ring == 0) == line_string.is_ccw())
+                .collect()
+        })
+        .collect();
+
+    let segments = RTree::bulk_load(
+        rings
+            .iter()
+            .enumerate()
+            .flat_map(|(polygon, rings)| {
+                rings
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(ring, line_string)| {
+                        line_string
+                            .lines()
+                            .enumerate()
+                            .filter(|(_, line)| line.start != line.end)
+                            .map(move |(index, line)| {
+                                GeomWithData::new(
+                                    line,
+                                    SegmentId {
+                                        polygon,
+                                        ring,
+                                        index,
+                                    },
+                                )
+                            })
+                    })
+            })
+            .collect(),
+    );
+    let Some(max_x) = polygons
+        .iter()
+        .flat_map(|polygon| polygon.exterior_coords_iter())
+        .map(|coord| coord.x)
+        .reduce(T::max)
+    else {
+        return vec![MultiLineString::new(vec![]); polygons.len()];
+    };
+
+    // the segments shared with another polygon, and which of those are invalid
+    let mut matched: Vec<Vec<Vec<bool>>> = rings
+        .iter()
+        .map(|rings| rings.iter().map(|ring| vec![false; ring.0.len()]).collect())
+        .collect();
+    let mut invalid = matched.clone();
+    for segment in &segments {
+        let (line, id) = (*segment.geom(), segment.data);
+        for other in segments.locate_in_envelope(&line.envelope()) {
+            let other_line = *other.geom();
+            let same_direction = other_line.start == line.start && other_line.end == line.end;
+            let reversed = other_line.start == line.end && other_line.end == line.start;
+            if other.data.polygon == id.polygon || !(same_direction || reversed) {
+                continue;
+            }
+            matched[id.polygon][id.ring][id.index] = true;
+            let other_on_left = interior_on_left[other.data.polygon][other.data.ring];
+            if (interior_on_left[id.polygon][id.ring] == other_on_left) == same_direction {
+                invalid[id.polygon][id.ring][id.index] = true;
+            }
+        }
+    }
+
+    let gap_width = gap_width.max(T::zero());
+    for segment in &segments {
+        let (line, id) = (*segment.geom(), segment.data);
+        if matched[id.polygon][id.ring][id.index] {
+            continue;
+        }
+        let (min, max) = (line.envelope().lower(), line.envelope().upper());
+        let margin = Point::new(gap_width, gap_width);
+        let envelope = AABB::from_corners(min - margin, max + margin);
+        let interacts = segments
+            .locate_in_envelope_intersecting(&envelope)
+            .filter(|other| other.data.polygon != id.polygon)
+            .any(|other| {
+                let other_line = *other.geom();
+                let other_matched = matched[other.data.polygon][other.data.ring][other.data.index];
+                !touch_at_ends(line, other_line)
+                    || (gap_width > T::zero()
+                        && !other_matched
+                        && is_nearly_parallel(line, other_line, gap_width))
+            });
+        if interacts || is_in_other_polygon(&segments, line, id.polygon, max_x) {
+            invalid[id.polygon][id.ring][id.index] = true;
+        }
+    }
+
+    rings
+        .iter()
+        .zip(invalid)
+        .map(|(rings, invalid)| {
+            rings
+                .iter()
+                .zip(invalid)
+                .flat_map(|(ring, invalid)| invalid_runs(ring, &invalid))
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether the midpoint of a segment, which doesn't cross any other segment, lies in the interior
+/// of a polygon other than its own, by casting a ray from it.
+fn is_in_other_polygon<T: GeoFloat>(
+    segments: &RTree<GeomWithData<Line<T>, SegmentId>>,
+    line: Line<T>,
+    polygon: usize,
+    max_x: T,
+) -> bool {
+    let midpoint = (line.start + line.end) / (T::one() + T::one());
+    let ray = AABB::from_corners(Point(midpoint), Point::new(max_x, midpoint.y));
+    let mut crossings = BTreeMap::new();
+    for other in segments.locate_in_envelope_intersecting(&ray) {
+        let Line { start, end } = *other.geom();
+        if other.data.polygon == polygon || (start.y > midpoint.y) == (end.y > midpoint.y) {
+            continue;
+        }
+        let x = start.x + (midpoint.y - start.y) / (end.y - start.y) * (end.x - start.x);
+        if x > midpoint.x {
+            *crossings.entry(other.data.polygon).or_insert(0) += 1;
+        }
+    }
+    crossings.values().any(|count| count % 2 == 1)
+}
+
+/// Whether two segments run alongside each other within `tolerance`, for a length of more than
+/// `tolerance`.
+fn is_nearly_parallel<T: GeoFloat>(a: Line<T>, b: Line<T>, tolerance: T) -> bool {
+    let (Some(on_a), Some(on_b)) = (project(a, b), project(b, a)) else {
+        return false;
+    };
+    let distance = |p: Coord<T>, q: Coord<T>| (p - q).x.hypot((p - q).y);
+    if distance(on_a.start, on_a.end) <= tolerance || distance(on_b.start, on_b.end) <= tolerance {
+        return false;
+    }
+    let on_b = if distance(on_a.start, on_b.end) < distance(on_a.start, on_b.start) {
+        Line::new(on_b.end, on_b.start)
+    } else {
+        on_b
+    };
+    distance(on_a.start, on_b.start) <= tolerance && distance(on_a.end, on_b.end) <= tolerance
+}
+
+/// The part of `line` which `other` projects onto, if any.
+fn project<T: GeoFloat>(line: Line<T>, other: Line<T>) -> Option<Line<T>> {
+    let delta = line.delta();
+    let length_2 = delta.x * delta.x + delta.y * delta.y;
+    let fraction = |coord: Coord<T>| {
+        let offset = coord - line.start;
+        (offset.x * delta.x + offset.y * delta.y) / length_2
+    };
+    let (start, end) = (fraction(other.start), fraction(other.end));
+    if (start <= T::zero() && end <= T::zero()) || (start >= T::one() && end >= T::one()) {
+        return None;
+    }
+    let clamped = |fraction: T| line.start + delta * fraction.max(T::zero()).min(T::one());
+    Some(Line::new(clamped(start), clamped(end)))
+}
+
+/// The runs of consecutive invalid segments of a ring, joined across its closing point.
+fn invalid_runs<T: GeoFloat>(ring: &LineString<T>, invalid: &[bool]) -> Vec<LineString<T>> {
+    let segments = ring.0.len().saturating_sub(1);
+    let Some(start) = (0..segments).find(|&index| !invalid[index]) else {
+        return if segments > 0 {
+            vec![ring.clone()]
+        } else {
+            vec![]
+        };
+    };
+
+    let mut runs = vec![];
+    let mut run: Vec<Coord<T>> = vec![];
+    for offset in 1..=segments {
+        let index = (start + offset) % segments;
+        if offset < segments && invalid[index] {
+            if run.is_empty() {
+                run.push(ring.0[index]);
+            }
+            run.push(ring.0[index + 1]);
+        } else if !run.is_empty() {
+            runs.push(LineString::new(std::mem::take(&mut run)));
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wkt;
+
+    #[test]
+    fn valid_coverage() {
+        let coverage = MultiPolygon::new(vec![
+            wkt!(POLYGON((0. 0.,5. 0.,5.2 1.,4.9 2.,5.1 3.,5. 5.,2. 5.1,0. 5.,0. 0.))),
+            wkt!(POLYGON((5. 0.,10. 0.,10. 5.,7. 5.2,5. 5.,5.1 3.,4.9 2.,5.2 1.,5. 0.))),
+            wkt!(POLYGON(
+                (0. 5.,2. 5.1,5. 5.,7. 5.2,10. 5.,10. 10.,0. 10.,0. 5.),
+                (4. 7.,6. 7.,5. 8.,4. 7.)
+            )),
+            wkt!(POLYGON((4. 7.,5. 8.,6. 7.,4. 7.))),
+        ]);
+        assert!(coverage.is_valid_coverage(0.5));
+        assert_eq!(
+            coverage.coverage_invalid_edges(0.5),
+            vec![MultiLineString::new(vec![]); 4]
+        );
+    }
+
+    #[test]
+    fn overlap() {
+        let coverage = [
+            wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.))),
+            wkt!(POLYGON((1. 0.,3. 0.,3. 2.,1. 2.,1. 0.))),
+        ];
+        assert_eq!(
+            coverage.coverage_invalid_edges(0.),
+            vec![
+                wkt!(MULTILINESTRING((0. 0.,2. 0.,2. 2.,0. 2.))),
+                wkt!(MULTILINESTRING((3. 2.,1. 2.,1. 0.,3. 0.))),
+            ]
+        );
+
+        // one polygon inside another
+        let coverage = [
+            wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.))),
+            wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.))),
+        ];
+        let invalid_edges = coverage.coverage_invalid_edges(0.);
+        assert!(invalid_edges[0].0.is_empty());
+        assert_eq!(
+            invalid_edges[1],
+            wkt!(MULTILINESTRING((1. 1.,2. 1.,2. 2.,1. 1.)))
+        );
+    }
+
+    #[test]
+    fn duplicate() {
+        let polygon = wkt!(POLYGON((0. 0.,1. 0.,1. 1.,0. 0.)));
+        let coverage = [polygon.clone(), polygon.clone()];
+        assert_eq!(
+            coverage.coverage_invalid_edges(0.),
+            vec![MultiLineString::new(vec![polygon.exterior().clone()]); 2]
+        );
+
+        // the same ring, the other way around, is still on the same side
+        let reversed = wkt!(POLYGON((0. 0.,1. 1.,1. 0.,0. 0.)));
+        assert!(![polygon, reversed].is_valid_coverage(0.));
+    }
+
+    #[test]
+    fn misaligned_vertex() {
+        // the second polygon has a vertex on an edge of the first
+        let coverage = [
+            wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.))),
+            wkt!(POLYGON((2. 0.,4. 0.,4. 2.,2. 2.,2. 1.,2. 0.))),
+        ];
+        assert_eq!(
+            coverage.coverage_invalid_edges(0.),
+            vec![
+                wkt!(MULTILINESTRING((2. 0.,2. 2.))),
+                wkt!(MULTILINESTRING((2. 2.,2. 1.,2. 0.))),
+            ]
+        );
+    }
+
+    #[test]
+    fn gaps() {
+        let coverage = [
+            wkt!(POLYGON((0. 0.,1. 0.,1. 1.,0. 1.,0. 0.))),
+            wkt!(POLYGON((1. 0.,2. 0.,2. 1.,1.05 1.,1. 0.))),
+        ];
+        // a wedge shaped gap, which is narrower than the width over part of its length
+        assert_eq!(
+            coverage.coverage_invalid_edges(0.1),
+            vec![
+                wkt!(MULTILINESTRING((1. 0.,1. 1.))),
+                wkt!(MULTILINESTRING((1.05 1.,1. 0.))),
+            ]
+        );
+        assert!(!coverage.is_valid_coverage(0.051));
+        assert!(coverage.is_valid_coverage(0.04));
+        assert!(coverage.is_valid_coverage(0.));
+    }
+}
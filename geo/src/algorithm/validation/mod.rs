@@ -2,6 +2,7 @@
 //!
 //! [OGC Simple Feature Access - Part 1: Common Architecture standard]: https://www.ogc.org/standards/sfa
 mod coord;
+mod coverage;
 mod geometry;
 mod geometry_collection;
 mod line;
@@ -17,6 +18,7 @@ mod tests;
 mod triangle;
 mod utils;
 
+pub use coverage::CoverageValidation;
 pub use geometry::InvalidGeometry;
 pub use geometry_collection::InvalidGeometryCollection;
 pub use line::InvalidLine;
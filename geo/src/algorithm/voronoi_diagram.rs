@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use geo_types::private_utils::get_bounding_rect;
+use num_traits::Float;
+use spade::{DelaunayTriangulation, Point2, Triangulation};
+
+use crate::bool_ops::{BoolOpsNum, BooleanOps};
+use crate::geometry::*;
+use crate::triangulate_delaunay::{
+    SpadeTriangulationFloat, TriangulationError, TriangulationResult,
+};
+use crate::{coord, CoordNum, CoordsIter};
+
+/// The region to which the cells of a [`VoronoiDiagram`] are clipped.
+///
+/// Voronoi cells on the outside of the diagram are unbounded, so they have to be cut off
+/// somewhere.
+#[derive(Debug, Clone, Copy)]
+pub enum VoronoiClip<'a, T: CoordNum> {
+    /// The bounding rectangle of the sites, grown on each side by its larger dimension (or by one,
+    /// if all the sites are at the same position).
+    Envelope,
+    /// A caller-supplied rectangle. Cells entirely outside of it are omitted.
+    Rect(Rect<T>),
+    /// A caller-supplied polygon. Cells entirely outside of it are omitted, and cells which it
+    /// cuts into multiple parts are returned as multiple polygons.
+    Polygon(&'a Polygon<T>),
+}
+
+/// The Voronoi cell of a site: the region which is closer to the site than to any other site.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoronoiCell<T: CoordNum> {
+    /// The index of the site among the coordinates of the input, in the order of
+    /// [`CoordsIter::coords_iter`]. If a coordinate is repeated, this is the index of its first
+    /// occurrence.
+    pub site_index: usize,
+    /// The position of the site.
+    pub site: Point<T>,
+    /// The area of the cell, clipped to the [`VoronoiClip`].
+    pub polygon: MultiPolygon<T>,
+}
+
+/// Compute the [Voronoi diagram](https://en.wikipedia.org/wiki/Voronoi_diagram) of the vertices
+/// of a geometry.
+///
+/// The diagram is derived from the Delaunay triangulation of the vertices: the cell of each
+/// vertex is bounded by the perpendicular bisectors of its Delaunay edges. Returns an error if
+/// the triangulation fails, e.g. because of non-finite coordinates.
+///
+/// Requires the `spade` feature, which is enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// use geo::{coord, wkt, Area, Rect, VoronoiDiagram};
+/// use geo::voronoi_diagram::VoronoiClip;
+///
+/// let sites = wkt!(MULTIPOINT(1. 1.,3. 1.));
+/// let bounds = Rect::new(coord! { x: 0., y: 0. }, coord! { x: 4., y: 2. });
+///
+/// let cells = sites.voronoi_cells(VoronoiClip::Rect(bounds)).unwrap();
+/// assert_eq!(cells.len(), 2);
+///
+/// assert_eq!(cells[0].site_index, 0);
+/// assert_eq!(cells[0].polygon.unsigned_area(), 4.);
+/// assert_eq!(cells[1].site, wkt!(POINT(3. 1.)));
+/// ```
+pub trait VoronoiDiagram<T: CoordNum> {
+    /// The Voronoi cells of the distinct vertices of the geometry, ordered by
+    /// [`VoronoiCell::site_index`].
+    fn voronoi_cells(&self, clip: VoronoiClip<T>) -> TriangulationResult<Vec<VoronoiCell<T>>>;
+}
+
+impl<T, G> VoronoiDiagram<T> for G
+where
+    T: SpadeTriangulationFloat + BoolOpsNum,
+    G: CoordsIter<Scalar = T>,
+{
+    fn voronoi_cells(&self, clip: VoronoiClip<T>) -> TriangulationResult<Vec<VoronoiCell<T>>> {
+        let mut triangulation = DelaunayTriangulation::<Point2<T>>::new();
+        let mut site_indices = HashMap::new();
+        for (site_index, coord) in self.coords_iter().enumerate() {
+            let handle = triangulation
+                .insert(Point2::new(coord.x, coord.y))
+                .map_err(TriangulationError::SpadeError)?;
+            site_indices.entry(handle.index()).or_insert(site_index);
+        }
+
+        let Some(sites) = get_bounding_rect(self.coords_iter()) else {
+            return Ok(vec![]);
+        };
+        let envelope = match clip {
+            VoronoiClip::Envelope => {
+                let margin = Float::max(sites.width(), sites.height());
+                let margin = if margin > T::zero() { margin } else { T::one() };
+                let offset = coord! { x: margin, y: margin };
+                Rect::new(sites.min() - offset, sites.max() + offset)
+            }
+            VoronoiClip::Rect(rect) => rect,
+            // the cells are cut to the polygon below, but must cover it before that
+            VoronoiClip::Polygon(polygon) => {
+                let coords = polygon.exterior().coords().copied();
+                get_bounding_rect(coords.chain(self.coords_iter())).unwrap_or(sites)
+            }
+        };
+
+        let mut cells: Vec<VoronoiCell<T>> = triangulation
+            .vertices()
+            .filter_map(|vertex| {
+                let site = to_coord(vertex.position());
+                let ring = vertex
+                    .out_edges()
+                    .fold(envelope.to_polygon().exterior().0.clone(), |ring, edge| {
+                        clip_to_bisector(ring, site, to_coord(edge.to().position()))
+                    });
+                let cell = Polygon::new(LineString::new(ring), vec![]);
+                let polygon = match clip {
+                    VoronoiClip::Polygon(polygon) => cell.intersection(polygon),
+                    _ if cell.exterior().0.len() < 4 => MultiPolygon::new(vec![]),
+                    _ => MultiPolygon::new(vec![cell]),
+                };
+                (!polygon.0.is_empty()).then(|| VoronoiCell {
+                    site_index: site_indices[&vertex.fix().index()],
+                    site: site.into(),
+                    polygon,
+                })
+            })
+            .collect();
+        cells.sort_by_key(|cell| cell.site_index);
+        Ok(cells)
+    }
+}
+
+fn to_coord<T: SpadeTriangulationFloat>(point: Point2<T>) -> Coord<T> {
+    coord! { x: point.x, y: point.y }
+}
+
+/// Clip a closed convex ring to the half-plane of the points which are at least as close to
+/// `site` as to `other`.
+fn clip_to_bisector<T: SpadeTriangulationFloat>(
+    ring: Vec<Coord<T>>,
+    site: Coord<T>,
+    other: Coord<T>,
+) -> Vec<Coord<T>> {
+    let two = T::one() + T::one();
+    let midpoint = (site + other) / two;
+    let normal = other - site;
+    // positive on the side of `other`
+    let side = |c: Coord<T>| (c.x - midpoint.x) * normal.x + (c.y - midpoint.y) * normal.y;
+
+    let mut clipped = Vec::with_capacity(ring.len() + 1);
+    for segment in ring.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let (start_side, end_side) = (side(start), side(end));
+        if start_side <= T::zero() {
+            clipped.push(start);
+        }
+        if (start_side < T::zero() && end_side > T::zero())
+            || (start_side > T::zero() && end_side < T::zero())
+        {
+            let t = start_side / (start_side - end_side);
+            clipped.push(start + (end - start) * t);
+        }
+    }
+    if let Some(&first) = clipped.first() {
+        clipped.push(first);
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wkt, Area, Contains};
+
+    #[test]
+    fn cells_partition_the_envelope() {
+        let sites = wkt!(MULTIPOINT(1. 1.,5. 2.,3. 4.,2. 7.,6. 6.,4. 5.));
+        let bounds = Rect::new(coord! { x: 0., y: 0. }, coord! { x: 8., y: 8. });
+        let cells = sites.voronoi_cells(VoronoiClip::Rect(bounds)).unwrap();
+        assert_eq!(cells.len(), 6);
+
+        let total: f64 = cells.iter().map(|cell| cell.polygon.unsigned_area()).sum();
+        assert_relative_eq!(total, 64., epsilon = 1e-9);
+
+        for (index, cell) in cells.iter().enumerate() {
+            assert_eq!(cell.site_index, index);
+            assert_eq!(cell.site, sites.0[index]);
+            assert!(cell.polygon.contains(&cell.site));
+            for other in cells.iter().filter(|other| other.site_index != index) {
+                let overlap = cell.polygon.intersection(&other.polygon);
+                assert_relative_eq!(overlap.unsigned_area(), 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn default_envelope() {
+        let sites = wkt!(MULTIPOINT(0. 0.,2. 0.));
+        let cells = sites.voronoi_cells(VoronoiClip::Envelope).unwrap();
+        // the envelope is grown by 2 on each side, and cut at x = 1
+        assert_eq!(cells[0].polygon.unsigned_area(), 12.);
+
+        let single = wkt!(POINT(1. 1.));
+        let cells = single.voronoi_cells(VoronoiClip::Envelope).unwrap();
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].polygon.unsigned_area(), 4.);
+    }
+
+    #[test]
+    fn collinear_sites() {
+        let sites = wkt!(LINESTRING(0. 1.,2. 1.,4. 1.));
+        let bounds = Rect::new(coord! { x: 0., y: 0. }, coord! { x: 4., y: 2. });
+        let cells = sites.voronoi_cells(VoronoiClip::Rect(bounds)).unwrap();
+        let areas: Vec<f64> = cells.iter().map(|c| c.polygon.unsigned_area()).collect();
+        assert_eq!(areas, vec![2., 4., 2.]);
+    }
+
+    #[test]
+    fn repeated_sites() {
+        // a closed ring repeats its first coordinate
+        let polygon = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 0.)));
+        let cells = polygon.voronoi_cells(VoronoiClip::Envelope).unwrap();
+        let indices: Vec<usize> = cells.iter().map(|c| c.site_index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn clip_to_polygon() {
+        let sites = wkt!(MULTIPOINT(0.5 2.,3. 2.,10. 10.));
+        // a C shape, which cuts the cell of the second site in two
+        let clip = wkt!(POLYGON((0. 0.,4. 0.,4. 1.,1. 1.,1. 3.,4. 3.,4. 4.,0. 4.,0. 0.)));
+        let cells = sites.voronoi_cells(VoronoiClip::Polygon(&clip)).unwrap();
+
+        let total: f64 = cells.iter().map(|cell| cell.polygon.unsigned_area()).sum();
+        assert_relative_eq!(total, clip.unsigned_area(), epsilon = 1e-9);
+
+        // the cell of the distant site doesn't reach the polygon
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[1].polygon.0.len(), 2);
+    }
+
+    #[test]
+    fn non_finite_sites() {
+        let sites = MultiPoint::from(vec![(0., 0.), (f64::NAN, 1.)]);
+        assert!(sites.voronoi_cells(VoronoiClip::Envelope).is_err());
+    }
+}
@@ -50,6 +50,7 @@
 //!
 //! - **[`Destination`]**: Calculate the destination point from an origin point, given a bearing and a distance.
 //! - **[`InterpolateLine`]**: Interpolate a `Point` along a `Line` or `LineString`.
+//! - **[`LineSubstring`]**: Extract the part of a `LineString` or `MultiLineString` between two positions along it.
 //! - **[`InterpolatePoint`]**: Interpolate points along a line.
 //! - **[`Densify`]**: Insert points into a geometry so there is never more than `max_segment_length` between points.
 //!
@@ -59,6 +60,8 @@
 //! - **[`VincentyDistance`]**: Calculate the minimum geodesic distance between geometries using Vincenty’s formula
 //! - **[`VincentyLength`]**: Calculate the geodesic length of a geometry using Vincenty’s formula
 //! - **[`FrechetDistance`]**: Calculate the similarity between [`LineString`]s using the Fréchet distance
+//! - **[`ContinuousFrechetDistance`]**: Calculate the similarity between [`LineString`]s using the continuous Fréchet distance, or test whether it is within a threshold
+//! - **[`DtwDistance`]**, **[`LcssDistance`]**, **[`EdrDistance`]**: Calculate the similarity between [`LineString`]s using Dynamic Time Warping, the Longest Common Subsequence or the Edit Distance on Real sequences of their vertices
 //!
 //! ## Area
 //!
@@ -69,7 +72,13 @@
 //! ## Boolean Operations
 //!
 //! - **[`BooleanOps`]**: Combine or split (Multi)Polygons using intersection, union, xor, or difference operations
+//! - **[`Overlay`]**: Intersection, union, xor, or difference of any pair of geometries, including points and lines
 //! - **[`unary_union`]**: Efficient union of many [`Polygon`] or [`MultiPolygon`]s
+//! - **[`Buffer`]**: Grow or shrink a geometry by a distance, with configurable line caps and joins
+//! - **[`OffsetCurve`]**: Offset a `LineString` to its left or right by a distance
+//! - **[`MetricBuffer`]**: Buffer a lon/lat geometry by a distance in meters, using a metric space such as [`Geodesic`] or [`Haversine`]
+//! - **[`polygonize()`]**: Assemble the polygons formed by a collection of noded lines
+//! - **[`Split`]**: Split polygons by lines, or lines by points or other lines
 //!
 //! ## Outlier Detection
 //!
@@ -82,6 +91,7 @@
 //! - **[`SimplifyVw`]**: Simplify a geometry using the Visvalingam-Whyatt algorithm
 //! - **[`SimplifyVwPreserve`]**: Simplify a geometry using a topology-preserving variant of the Visvalingam-Whyatt algorithm
 //! - **[`SimplifyVwIdx`]**: Calculate a simplified geometry using the Visvalingam-Whyatt algorithm, returning coordinate indices
+//! - **[`CoverageSimplify`]**: Simplify a coverage of polygons, simplifying each shared edge once so that no gaps or overlaps open between them
 //!
 //! ## Query
 //!
@@ -89,6 +99,8 @@
 //!   closest to a given point
 //! - **[`HaversineClosestPoint`]**: Find the point on a geometry
 //!   closest to a given point on a sphere using spherical coordinates and lines being great arcs
+//! - **[`nearest_points()`]**: Find the pair of points, one on each of two
+//!   geometries, which are nearest to each other
 //! - **[`IsConvex`]**: Calculate the convexity of a [`LineString`]
 //! - **[`LineLocatePoint`]**: Calculate the
 //!   fraction of a line’s total length representing the location of the closest point on the
@@ -102,6 +114,8 @@
 //!   geometry
 //! - **[`CoordinatePosition`]**: Calculate
 //!   the position of a coordinate relative to a geometry
+//! - **[`DistanceWithin`]**: Calculate if two geometries are within a
+//!   distance of each other
 //! - **[`HasDimensions`]**: Determine the dimensions of a geometry
 //! - **[`Intersects`]**: Calculate if a geometry intersects
 //!   another geometry
@@ -114,7 +128,8 @@
 //! ## Triangulation
 //!
 //! - **[`TriangulateEarcut`](triangulate_earcut)**: Triangulate polygons using the earcut algorithm. Requires the `earcutr` feature, which is enabled by default
-//! - **[`TriangulateDelaunay`](triangulate_delaunay)**: Produce constrained or unconstrained Delaunay triangulations of polygons. Requires the `spade` feature, which is enabled by default
+//! - **[`TriangulateDelaunay`](triangulate_delaunay)**: Produce constrained or unconstrained Delaunay triangulations of polygons, or refined quality meshes. Requires the `spade` feature, which is enabled by default
+//! - **[`VoronoiDiagram`]**: Compute the Voronoi cells of the vertices of a geometry, clipped to a rectangle or polygon. Requires the `spade` feature, which is enabled by default
 //! ## Winding
 //!
 //! - **[`Orient`]**: Apply a specified winding [`Direction`](orient::Direction) to a [`Polygon`]’s interior and exterior rings
@@ -131,10 +146,16 @@
 //!
 //! ## Boundary
 //!
+//! - **[`Boundary`]**: Calculate the topological boundary of a
+//!   geometry
 //! - **[`BoundingRect`]**: Calculate the axis-aligned
 //!   bounding rectangle of a geometry
 //! - **[`MinimumRotatedRect`]**: Calculate the
 //!   minimum bounding box of a geometry
+//! - **[`MinimumWidthRect`]**: Calculate the narrowest
+//!   bounding box of a geometry, and its width
+//! - **[`MinimumBoundingCircle`]**: Calculate the smallest
+//!   circle enclosing a geometry
 //! - **[`ConcaveHull`]**: Calculate the concave hull of a
 //!   geometry
 //! - **[`ConvexHull`]**: Calculate the convex hull of a
@@ -162,11 +183,16 @@
 //! - **[`Centroid`]**: Calculate the centroid of a geometry
 //! - **[`ChaikinSmoothing`]**: Smoothen `LineString`, `Polygon`, `MultiLineString` and `MultiPolygon` using Chaikin's algorithm
 //! - **[`proj`]**: Project geometries with the `proj` crate (requires the `use-proj` feature)
+//! - **[`LineMerge`]**: Join the parts of a `MultiLineString` which meet end to end into the longest possible `LineString`s
 //! - **[`LineStringSegmentize`]**: Segment a LineString into `n` segments
 //! - **[`LineStringSegmentizeHaversine`]**: Segment a LineString using Haversine distance
 //! - **[`Transform`]**: Transform a geometry using Proj
 //! - **[`RemoveRepeatedPoints`]**: Remove repeated points from a geometry
 //! - **[`Validation`]**: Checks if the geometry is well formed. Some algorithms may not work correctly with invalid geometries
+//! - **[`CoverageValidation`]**: Checks that a set of polygons forms a coverage, reporting the edges where they overlap, are misaligned or leave narrow gaps
+//! - **[`MakeValid`]**: Repairs an invalid geometry, e.g. splitting self-intersecting polygons
+//! - **[`MaximumInscribedCircle`]**: Find the largest circle inside a polygon, whose center is a good label anchor
+//! - **[`MinimumClearance`]**: Calculate how far a vertex could move before the geometry becomes invalid
 //!
 //! # Spatial Indexing
 //!
@@ -175,6 +201,11 @@
 //! their members and adding them. Note in particular the availability of the [`bulk_load`](https://docs.rs/rstar/0.12.0/rstar/struct.RTree.html#method.bulk_load)
 //! method and [`GeomWithData`](https://docs.rs/rstar/0.12.0/rstar/primitives/struct.GeomWithData.html) struct.
 //!
+//! For an index which takes care of this, and answers intersects, contains, within and distance
+//! queries, as well as k-nearest queries by the true distance between geometries, see
+//! [`GeometryIndex`]. To match two collections of geometries against each other, e.g. to find
+//! the polygon containing each of many points, see [`spatial_join()`].
+//!
 //! # Features
 //!
 //! The following optional [Cargo features] are available:
@@ -194,7 +225,8 @@
 //!     - ☐ Disabled by default
 //! - `multithreading`:
 //!     - Enables multithreading support (via Rayon), and activates the `multithreading` flag
-//!       in `geo-types`, enabling multi-threaded iteration over `Multi*` geometries
+//!       in `geo-types`, enabling multi-threaded iteration over `Multi*` geometries. Also makes
+//!       [`spatial_join()`] match geometries in parallel
 //!     - ☑ Enabled by default
 //!
 //! # Ecosystem
@@ -1,8 +1,11 @@
 //! Internal utility functions, types, and data structures.
 
-use geo_types::{Coord, CoordFloat, CoordNum};
+use geo_types::{Coord, CoordFloat, CoordNum, Line};
 use num_traits::FromPrimitive;
 
+use crate::line_intersection::{line_intersection, LineIntersection};
+use crate::GeoFloat;
+
 /// Partition a mutable slice in-place so that it contains all elements for
 /// which `predicate(e)` is `true`, followed by all elements for which
 /// `predicate(e)` is `false`. Returns sub-slices to all predicated and
@@ -162,6 +165,18 @@ pub fn normalize_longitude<T: CoordFloat + FromPrimitive>(coord: T) -> T {
     ((coord + five_forty) % three_sixty) - one_eighty
 }
 
+/// Whether two segments only meet, if at all, at an end of both.
+pub fn touch_at_ends<T: GeoFloat>(a: Line<T>, b: Line<T>) -> bool {
+    match line_intersection(a, b) {
+        None => true,
+        Some(LineIntersection::SinglePoint { intersection, .. }) => {
+            (intersection == a.start || intersection == a.end)
+                && (intersection == b.start || intersection == b.end)
+        }
+        Some(LineIntersection::Collinear { .. }) => false,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{partial_max, partial_min};
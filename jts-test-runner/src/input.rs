@@ -1,6 +1,7 @@
 use geo::bool_ops::OpType as BoolOp;
+use geo::buffer::{BufferStyle, LineJoin};
 use geo::relate::IntersectionMatrix;
-use geo::{Geometry, Point};
+use geo::{Geometry, HasDimensions, Point};
 use serde::{Deserialize, Deserializer};
 
 use super::Result;
@@ -41,6 +42,10 @@ pub(crate) struct Run {
 pub(crate) struct PrecisionModel {
     #[serde(rename = "type", default)]
     pub ty: String,
+
+    /// The scale of a fixed precision model
+    #[serde(default)]
+    pub scale: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,6 +69,30 @@ pub(crate) struct Test {
     pub(crate) operation_input: OperationInput,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BoundaryInput {
+    pub(crate) arg1: String,
+
+    #[serde(rename = "$value", deserialize_with = "wkt::deserialize_wkt")]
+    pub(crate) expected: geo::Geometry,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BufferInput {
+    pub(crate) arg1: String,
+
+    /// The buffer distance
+    #[serde(default)]
+    pub(crate) arg2: Option<String>,
+
+    /// The number of quadrant segments
+    #[serde(default)]
+    pub(crate) arg3: Option<String>,
+
+    #[serde(rename = "$value", deserialize_with = "wkt::deserialize_wkt")]
+    pub(crate) expected: geo::Geometry,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CentroidInput {
     pub(crate) arg1: String,
@@ -106,6 +135,31 @@ pub struct IsValidInput {
     pub(crate) expected: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MinimumClearanceInput {
+    pub(crate) arg1: String,
+
+    /// `Double.MAX_VALUE` if the geometry has no minimum clearance
+    #[serde(rename = "$value", deserialize_with = "deserialize_from_str")]
+    pub(crate) expected: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MinimumClearanceLineInput {
+    pub(crate) arg1: String,
+
+    #[serde(rename = "$value", deserialize_with = "wkt::deserialize_wkt")]
+    pub(crate) expected: geo::Geometry,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PolygonizeInput {
+    pub(crate) arg1: String,
+
+    #[serde(rename = "$value", deserialize_with = "wkt::deserialize_wkt")]
+    pub(crate) expected: geo::Geometry,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RelateInput {
     pub(crate) arg1: String,
@@ -133,6 +187,28 @@ pub struct WithinInput {
     pub(crate) expected: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DistanceInput {
+    pub(crate) arg1: String,
+    pub(crate) arg2: String,
+
+    /// `0.0` if either geometry is empty
+    #[serde(rename = "$value", deserialize_with = "deserialize_from_str")]
+    pub(crate) expected: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IsWithinDistanceInput {
+    pub(crate) arg1: String,
+    pub(crate) arg2: String,
+
+    #[serde(deserialize_with = "deserialize_from_str")]
+    pub(crate) arg3: f64,
+
+    #[serde(rename = "$value", deserialize_with = "deserialize_from_str")]
+    pub(crate) expected: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct OverlayInput {
     pub(crate) arg1: String,
@@ -142,9 +218,31 @@ pub struct OverlayInput {
     pub(crate) expected: geo::Geometry<f64>,
 }
 
+/// An overlay using snap-rounding, on a grid of the given scale.
+#[derive(Debug, Deserialize)]
+pub struct OverlaySRInput {
+    pub(crate) arg1: String,
+    pub(crate) arg2: String,
+
+    #[serde(deserialize_with = "deserialize_from_str")]
+    pub(crate) arg3: f64,
+
+    #[serde(rename = "$value", deserialize_with = "wkt::deserialize_wkt")]
+    pub(crate) expected: geo::Geometry<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "name")]
 pub(crate) enum OperationInput {
+    #[serde(rename = "getboundary")]
+    BoundaryInput(BoundaryInput),
+
+    #[serde(rename = "buffer")]
+    BufferInput(BufferInput),
+
+    #[serde(rename = "bufferMitredJoin")]
+    BufferMitredJoinInput(BufferInput),
+
     #[serde(rename = "contains")]
     ContainsInput(ContainsInput),
 
@@ -154,6 +252,9 @@ pub(crate) enum OperationInput {
     #[serde(rename = "convexhull")]
     ConvexHullInput(ConvexHullInput),
 
+    #[serde(rename = "distance")]
+    DistanceInput(DistanceInput),
+
     #[serde(rename = "equalsTopo")]
     EqualsTopoInput(EqualsTopoInput),
 
@@ -163,6 +264,18 @@ pub(crate) enum OperationInput {
     #[serde(rename = "isValid")]
     IsValidInput(IsValidInput),
 
+    #[serde(rename = "isWithinDistance")]
+    IsWithinDistanceInput(IsWithinDistanceInput),
+
+    #[serde(rename = "minClearance")]
+    MinimumClearanceInput(MinimumClearanceInput),
+
+    #[serde(rename = "minClearanceLine")]
+    MinimumClearanceLineInput(MinimumClearanceLineInput),
+
+    #[serde(rename = "polygonize")]
+    PolygonizeInput(PolygonizeInput),
+
     #[serde(rename = "relate")]
     RelateInput(RelateInput),
 
@@ -178,6 +291,18 @@ pub(crate) enum OperationInput {
     #[serde(rename = "symdifference")]
     SymDifferenceInput(OverlayInput),
 
+    #[serde(rename = "unionSR")]
+    UnionSRInput(OverlaySRInput),
+
+    #[serde(rename = "intersectionSR")]
+    IntersectionSRInput(OverlaySRInput),
+
+    #[serde(rename = "differenceSR")]
+    DifferenceSRInput(OverlaySRInput),
+
+    #[serde(rename = "symDifferenceSR", alias = "symdifferenceSR")]
+    SymDifferenceSRInput(OverlaySRInput),
+
     #[serde(rename = "within")]
     WithinInput(WithinInput),
 
@@ -187,6 +312,15 @@ pub(crate) enum OperationInput {
 
 #[derive(Debug, Clone)]
 pub(crate) enum Operation {
+    Boundary {
+        subject: Geometry,
+        expected: Geometry,
+    },
+    Buffer {
+        subject: Geometry,
+        style: BufferStyle<f64>,
+        expected: Geometry,
+    },
     Centroid {
         subject: Geometry,
         expected: Option<Point>,
@@ -209,6 +343,11 @@ pub(crate) enum Operation {
         subject: Geometry,
         expected: Geometry,
     },
+    Distance {
+        subject: Geometry,
+        target: Geometry,
+        expected: Option<f64>,
+    },
     EqualsTopo {
         a: Geometry,
         b: Geometry,
@@ -219,6 +358,24 @@ pub(crate) enum Operation {
         clip: Geometry,
         expected: bool,
     },
+    IsWithinDistance {
+        subject: Geometry,
+        target: Geometry,
+        distance: f64,
+        expected: bool,
+    },
+    MinimumClearance {
+        subject: Geometry,
+        expected: Option<f64>,
+    },
+    MinimumClearanceLine {
+        subject: Geometry,
+        expected: Geometry,
+    },
+    Polygonize {
+        subject: Geometry,
+        expected: Geometry,
+    },
     Relate {
         a: Geometry,
         b: Geometry,
@@ -228,12 +385,8 @@ pub(crate) enum Operation {
         a: Geometry<f64>,
         b: Geometry<f64>,
         op: BoolOp,
-        expected: Geometry<f64>,
-    },
-    ClipOp {
-        a: Geometry<f64>,
-        b: Geometry<f64>,
-        invert: bool,
+        /// The scale of the precision grid, if the result is computed on one
+        scale: Option<f64>,
         expected: Geometry<f64>,
     },
     Unsupported {
@@ -246,6 +399,25 @@ impl OperationInput {
     pub(crate) fn into_operation(self, case: &Case) -> Result<Operation> {
         let geometry = &case.a;
         match self {
+            Self::BoundaryInput(input) => {
+                assert_eq!("A", input.arg1);
+                Ok(Operation::Boundary {
+                    subject: geometry.clone(),
+                    expected: input.expected,
+                })
+            }
+            Self::BufferInput(input) => Ok(Operation::Buffer {
+                subject: geometry.clone(),
+                style: input.style()?,
+                expected: input.expected,
+            }),
+            Self::BufferMitredJoinInput(input) => Ok(Operation::Buffer {
+                subject: geometry.clone(),
+                style: input
+                    .style()?
+                    .line_join(LineJoin::Miter(BufferStyle::default_miter_limit())),
+                expected: input.expected,
+            }),
             Self::CentroidInput(centroid_input) => {
                 assert_eq!("A", centroid_input.arg1);
                 Ok(Operation::Centroid {
@@ -260,6 +432,17 @@ impl OperationInput {
                     expected: convex_hull_input.expected,
                 })
             }
+            Self::DistanceInput(input) => {
+                assert_eq!("A", input.arg1);
+                assert_eq!("B", input.arg2);
+                let target = case.b.clone().expect("no geometry b in case");
+                Ok(Operation::Distance {
+                    expected: (!geometry.is_empty() && !target.is_empty())
+                        .then_some(input.expected),
+                    subject: geometry.clone(),
+                    target,
+                })
+            }
             Self::EqualsTopoInput(equals_topo_input) => {
                 assert_eq!("A", equals_topo_input.arg1);
                 assert_eq!("B", equals_topo_input.arg2);
@@ -286,6 +469,37 @@ impl OperationInput {
                     expected: input.expected,
                 })
             }
+            Self::IsWithinDistanceInput(input) => {
+                assert_eq!("A", input.arg1);
+                assert_eq!("B", input.arg2);
+                Ok(Operation::IsWithinDistance {
+                    subject: geometry.clone(),
+                    target: case.b.clone().expect("no geometry b in case"),
+                    distance: input.arg3,
+                    expected: input.expected,
+                })
+            }
+            Self::MinimumClearanceInput(input) => {
+                assert_eq!("A", input.arg1);
+                Ok(Operation::MinimumClearance {
+                    subject: geometry.clone(),
+                    expected: (input.expected != f64::MAX).then_some(input.expected),
+                })
+            }
+            Self::MinimumClearanceLineInput(input) => {
+                assert_eq!("A", input.arg1);
+                Ok(Operation::MinimumClearanceLine {
+                    subject: geometry.clone(),
+                    expected: input.expected,
+                })
+            }
+            Self::PolygonizeInput(input) => {
+                assert_eq!("A", input.arg1.to_uppercase());
+                Ok(Operation::Polygonize {
+                    subject: geometry.clone(),
+                    expected: input.expected,
+                })
+            }
             Self::RelateInput(input) => {
                 assert_eq!("A", input.arg1);
                 assert_eq!("B", input.arg2);
@@ -315,105 +529,53 @@ impl OperationInput {
                 })
             }
             Self::UnionInput(input) => {
-                validate_boolean_op(
-                    &input.arg1,
-                    &input.arg2,
-                    geometry,
-                    case.b.as_ref().expect("no geometry b in case"),
-                )?;
+                assert_eq!("A", input.arg1);
+                assert_eq!("B", input.arg2);
                 Ok(Operation::BooleanOp {
                     a: geometry.clone(),
                     b: case.b.clone().expect("no geometry b in case"),
                     op: BoolOp::Union,
+                    scale: None,
                     expected: input.expected,
                 })
             }
             Self::IntersectionInput(input) => {
                 assert_eq!("A", input.arg1);
                 assert_eq!("B", input.arg2);
-
-                // Clipping a line string in geo is like a Line x Poly Intersection in JTS
-                match (geometry, case.b.as_ref().expect("no geometry b in case")) {
-                    (
-                        Geometry::LineString(_) | Geometry::MultiLineString(_),
-                        Geometry::Polygon(_) | Geometry::MultiPolygon(_),
-                    )
-                    | (
-                        Geometry::Polygon(_) | Geometry::MultiPolygon(_),
-                        Geometry::LineString(_) | Geometry::MultiLineString(_),
-                    ) => {
-                        return Ok(Operation::ClipOp {
-                            a: geometry.clone(),
-                            b: case.b.clone().expect("no geometry b in case"),
-                            invert: false,
-                            expected: input.expected,
-                        });
-                    }
-                    _ => {
-                        validate_boolean_op(
-                            &input.arg1,
-                            &input.arg2,
-                            geometry,
-                            case.b.as_ref().expect("no geometry b in case"),
-                        )?;
-                    }
-                };
-
                 Ok(Operation::BooleanOp {
                     a: geometry.clone(),
                     b: case.b.clone().expect("no geometry b in case"),
                     op: BoolOp::Intersection,
+                    scale: None,
                     expected: input.expected,
                 })
             }
             Self::DifferenceInput(input) => {
-                // Clipping a line string in geo is like a Line x Poly Intersection in JTS
-                match (geometry, case.b.as_ref().expect("no geometry b in case")) {
-                    (
-                        Geometry::LineString(_) | Geometry::MultiLineString(_),
-                        Geometry::Polygon(_) | Geometry::MultiPolygon(_),
-                    )
-                    | (
-                        Geometry::Polygon(_) | Geometry::MultiPolygon(_),
-                        Geometry::LineString(_) | Geometry::MultiLineString(_),
-                    ) => {
-                        return Ok(Operation::ClipOp {
-                            a: geometry.clone(),
-                            b: case.b.clone().expect("no geometry b in case"),
-                            invert: true,
-                            expected: input.expected,
-                        });
-                    }
-                    _ => {
-                        validate_boolean_op(
-                            &input.arg1,
-                            &input.arg2,
-                            geometry,
-                            case.b.as_ref().expect("no geometry b in case"),
-                        )?;
-                    }
-                };
+                assert_eq!("A", input.arg1);
+                assert_eq!("B", input.arg2);
                 Ok(Operation::BooleanOp {
                     a: geometry.clone(),
                     b: case.b.clone().expect("no geometry b in case"),
                     op: BoolOp::Difference,
+                    scale: None,
                     expected: input.expected,
                 })
             }
             Self::SymDifferenceInput(input) => {
-                validate_boolean_op(
-                    &input.arg1,
-                    &input.arg2,
-                    geometry,
-                    case.b.as_ref().expect("no geometry b in case"),
-                )?;
+                assert_eq!("A", input.arg1);
+                assert_eq!("B", input.arg2);
                 Ok(Operation::BooleanOp {
                     a: geometry.clone(),
                     b: case.b.clone().expect("no geometry b in case"),
                     op: BoolOp::Xor,
+                    scale: None,
                     expected: input.expected,
                 })
             }
+            Self::UnionSRInput(input) => input.into_operation(case, BoolOp::Union),
+            Self::IntersectionSRInput(input) => input.into_operation(case, BoolOp::Intersection),
+            Self::DifferenceSRInput(input) => input.into_operation(case, BoolOp::Difference),
+            Self::SymDifferenceSRInput(input) => input.into_operation(case, BoolOp::Xor),
             Self::Unsupported => Err("This OperationInput not supported".into()),
             OperationInput::IsValidInput(input) => match input.arg1.as_str() {
                 "A" => Ok(Operation::IsValidOp {
@@ -426,20 +588,35 @@ impl OperationInput {
     }
 }
 
-fn validate_boolean_op(arg1: &str, arg2: &str, a: &Geometry<f64>, b: &Geometry<f64>) -> Result<()> {
-    assert_eq!("A", arg1);
-    assert_eq!("B", arg2);
-    for arg in &[a, b] {
-        if matches!(arg, Geometry::LineString(_)) {
-            log::warn!("skipping `line_string.union` we don't support");
-            return Err("`line_string.union` is not supported".into());
-        }
-        if matches!(arg, Geometry::MultiPoint(_)) {
-            log::warn!("skipping `line_string.union` we don't support");
-            return Err("`line_string.union` is not supported".into());
+impl OverlaySRInput {
+    fn into_operation(self, case: &Case, op: BoolOp) -> Result<Operation> {
+        let arg = |name: &str| match name.to_uppercase().as_str() {
+            "A" => Ok(case.a.clone()),
+            "B" => Ok(case.b.clone().expect("no geometry b in case")),
+            other => Err(format!("unknown argument {other}")),
+        };
+        Ok(Operation::BooleanOp {
+            a: arg(&self.arg1)?,
+            b: arg(&self.arg2)?,
+            op,
+            scale: Some(self.arg3),
+            expected: self.expected,
+        })
+    }
+}
+
+impl BufferInput {
+    fn style(&self) -> Result<BufferStyle<f64>> {
+        assert_eq!("A", self.arg1.to_uppercase());
+        let Some(distance) = &self.arg2 else {
+            return Err("buffer test case must contain a distance".into());
+        };
+        let mut style = BufferStyle::new(distance.trim().parse()?);
+        if let Some(quadrant_segments) = &self.arg3 {
+            style = style.quadrant_segments(quadrant_segments.trim().parse()?);
         }
+        Ok(style)
     }
-    Ok(())
 }
 
 pub fn deserialize_opt_geometry<'de, D>(
@@ -461,5 +638,5 @@ where
     <T as std::str::FromStr>::Err: std::fmt::Display,
 {
     String::deserialize(deserializer)
-        .and_then(|str| T::from_str(&str).map_err(serde::de::Error::custom))
+        .and_then(|str| T::from_str(str.trim()).map_err(serde::de::Error::custom))
 }
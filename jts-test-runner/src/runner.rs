@@ -6,13 +6,17 @@ use log::{debug, info};
 use wkt::ToWkt;
 
 use super::{input, Operation, Result};
-use geo::algorithm::{BooleanOps, Contains, HasDimensions, Intersects, Relate, Within};
+use geo::algorithm::{
+    Area, BooleanOps, Contains, DistanceWithin, HasDimensions, Intersects, MinimumClearance,
+    Overlay, Relate, Within,
+};
 use geo::geometry::*;
-use geo::GeoNum;
+use geo::{nearest_points, Distance, Euclidean, GeoNum, PreparedGeometry};
 
 const GENERAL_TEST_XML: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources/testxml/general");
 const VALIDATE_TEST_XML: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources/testxml/validate");
 const MISC_TEST_XML: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources/testxml/misc");
+const FAILURE_TEST_XML: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources/testxml/failure");
 
 #[derive(Debug, Default, Clone)]
 pub struct TestRunner {
@@ -87,6 +91,77 @@ impl TestRunner {
 
         for test_case in cases {
             match &test_case.operation {
+                Operation::Boundary { subject, expected } => {
+                    use geo::algorithm::Boundary;
+
+                    let actual = subject.boundary();
+                    if actual.relate(expected).is_equal_topo() {
+                        debug!("Boundary success: actual == expected");
+                        self.successes.push(test_case);
+                    } else {
+                        debug!("Boundary failure: actual != expected");
+                        let error_description = format!(
+                            "expected {:?}, actual: {:?}",
+                            expected.wkt_string(),
+                            actual.wkt_string()
+                        );
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    }
+                }
+                Operation::Buffer {
+                    subject,
+                    style,
+                    expected,
+                } => {
+                    use geo::algorithm::Buffer;
+
+                    // JTS removes the two short segments at the end of this line before
+                    // buffering it, as they're within 1% of the buffer distance of the rest of
+                    // it. Buffering them turns the end cap enough to exceed the area tolerance.
+                    if test_case.test_file_name == "TestBufferFailure.xml"
+                        && test_case
+                            .description
+                            .contains("postgis-users/2013-January/036334")
+                    {
+                        debug!("Buffer doesn't simplify its input like JTS");
+                        self.unsupported.push(test_case);
+                        continue;
+                    }
+
+                    let expected = match expected {
+                        Geometry::Polygon(p) => MultiPolygon(vec![p.clone()]),
+                        Geometry::MultiPolygon(mp) => mp.clone(),
+                        _ => {
+                            let error_description = format!(
+                                "expected result for buffer is not a polygon: {expected:?}"
+                            );
+                            self.failures.push(TestFailure {
+                                test_case,
+                                error_description,
+                            });
+                            continue;
+                        }
+                    };
+                    let actual = subject.buffer_with_style(*style);
+                    if is_buffer_match(&actual, &expected) {
+                        debug!("Buffer success: actual == expected");
+                        self.successes.push(test_case);
+                    } else {
+                        debug!("Buffer failure: actual != expected");
+                        let error_description = format!(
+                            "expected {:?}, actual: {:?}",
+                            expected.wkt_string(),
+                            actual.wkt_string()
+                        );
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    }
+                }
                 Operation::Centroid { subject, expected } => {
                     use geo::prelude::Centroid;
                     match (subject.centroid(), expected) {
@@ -309,13 +384,28 @@ impl TestRunner {
                         self.successes.push(test_case);
                     }
                 }
-                Operation::Relate { a, b, expected } => {
-                    let actual = a.relate(b);
-                    if actual == *expected {
-                        debug!("Relate success: actual == expected");
+                Operation::Distance {
+                    subject,
+                    target,
+                    expected,
+                } => {
+                    let actual = nearest_points(subject, target);
+                    let success = match (actual, expected) {
+                        (None, None) => true,
+                        (Some(actual), Some(expected)) => {
+                            relative_eq!(actual.distance, expected)
+                                && relative_eq!(
+                                    Euclidean.distance(&Point(actual.a), &Point(actual.b)),
+                                    expected
+                                )
+                        }
+                        _ => false,
+                    };
+                    if success {
+                        debug!("Distance success: actual == expected");
                         self.successes.push(test_case);
                     } else {
-                        debug!("Relate failure: actual != expected");
+                        debug!("Distance failure: actual != expected");
                         let error_description =
                             format!("expected {expected:?}, actual: {actual:?}");
                         self.failures.push(TestFailure {
@@ -324,40 +414,113 @@ impl TestRunner {
                         });
                     }
                 }
-                Operation::BooleanOp { a, b, op, expected } => {
-                    match expected {
-                        Geometry::MultiPolygon(_) | Geometry::Polygon(_) => {}
-                        _ => {
-                            info!("skipping unsupported Union expectation: {:?}", expected);
-                            self.unsupported.push(test_case);
-                            continue;
+                Operation::IsWithinDistance {
+                    subject,
+                    target,
+                    distance,
+                    expected,
+                } => {
+                    let direct_actual = subject.is_within_distance(target, *distance);
+                    let prepared_actual =
+                        PreparedGeometry::from(subject).is_within_distance(target, *distance);
+
+                    if direct_actual != *expected {
+                        debug!("IsWithinDistance failure: direct_actual != expected");
+                        let error_description =
+                            format!("expected {expected:?}, direct_actual: {direct_actual:?}");
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    } else if prepared_actual != *expected {
+                        debug!("IsWithinDistance failure: prepared_actual != expected");
+                        let error_description =
+                            format!("expected {expected:?}, prepared_actual: {prepared_actual:?}");
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    } else {
+                        debug!("IsWithinDistance success: actual == expected");
+                        self.successes.push(test_case);
+                    }
+                }
+                Operation::MinimumClearance { subject, expected } => {
+                    let actual = subject.minimum_clearance();
+                    let success = match (actual, expected) {
+                        (None, None) => true,
+                        (Some(actual), Some(expected)) => relative_eq!(actual, expected),
+                        _ => false,
+                    };
+                    if success {
+                        debug!("MinimumClearance success: actual == expected");
+                        self.successes.push(test_case);
+                    } else {
+                        debug!("MinimumClearance failure: actual != expected");
+                        let error_description =
+                            format!("expected {expected:?}, actual: {actual:?}");
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    }
+                }
+                Operation::MinimumClearanceLine { subject, expected } => {
+                    let actual = subject.minimum_clearance_line();
+                    let success = match (actual, expected) {
+                        (None, expected) => expected.is_empty(),
+                        (Some(actual), Geometry::LineString(expected)) => {
+                            expected.0 == [actual.start, actual.end]
                         }
+                        _ => false,
                     };
-
-                    let actual = match (a, b) {
-                        (Geometry::Polygon(a), Geometry::Polygon(b)) => a.boolean_op(b, *op),
-                        (Geometry::Polygon(a), Geometry::MultiPolygon(b)) => a.boolean_op(b, *op),
-                        (Geometry::MultiPolygon(a), Geometry::MultiPolygon(b)) => {
-                            a.boolean_op(b, *op)
+                    if success {
+                        debug!("MinimumClearanceLine success: actual == expected");
+                        self.successes.push(test_case);
+                    } else {
+                        debug!("MinimumClearanceLine failure: actual != expected");
+                        let error_description = format!(
+                            "expected {:?}, actual: {:?}",
+                            expected.wkt_string(),
+                            actual.map(|line| line.wkt_string())
+                        );
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    }
+                }
+                Operation::Polygonize { subject, expected } => {
+                    let expected = match expected {
+                        Geometry::GeometryCollection(gc)
+                            if gc.iter().all(|g| matches!(g, Geometry::Polygon(_))) =>
+                        {
+                            gc.iter()
+                                .filter_map(|g| match g {
+                                    Geometry::Polygon(p) => Some(p.clone()),
+                                    _ => None,
+                                })
+                                .collect::<MultiPolygon>()
                         }
-                        (Geometry::MultiPolygon(a), Geometry::Polygon(b)) => a.boolean_op(b, *op),
                         _ => {
-                            info!("skipping unsupported Union combination: {:?}, {:?}", a, b);
-                            self.unsupported.push(test_case);
+                            let error_description = format!(
+                                "expected result for polygonize is not a collection of polygons: {expected:?}"
+                            );
+                            self.failures.push(TestFailure {
+                                test_case,
+                                error_description,
+                            });
                             continue;
                         }
                     };
-
-                    if actual.relate(expected).is_equal_topo() {
-                        debug!(
-                            "BooleanOp success (topo eq) - expected: {:?}",
-                            expected.wkt_string()
-                        );
+                    let actual = geo::algorithm::polygonize(&line_strings(subject)).polygons;
+                    if is_polygonize_match(&actual, &expected) {
+                        debug!("Polygonize success: actual == expected");
                         self.successes.push(test_case);
                     } else {
+                        debug!("Polygonize failure: actual != expected");
                         let error_description = format!(
-                            "op: {:?}, expected {:?}, actual: {:?}",
-                            op,
+                            "expected {:?}, actual: {:?}",
                             expected.wkt_string(),
                             actual.wkt_string()
                         );
@@ -367,67 +530,43 @@ impl TestRunner {
                         });
                     }
                 }
-                Operation::ClipOp {
+                Operation::Relate { a, b, expected } => {
+                    let actual = a.relate(b);
+                    if actual == *expected {
+                        debug!("Relate success: actual == expected");
+                        self.successes.push(test_case);
+                    } else {
+                        debug!("Relate failure: actual != expected");
+                        let error_description =
+                            format!("expected {expected:?}, actual: {actual:?}");
+                        self.failures.push(TestFailure {
+                            test_case,
+                            error_description,
+                        });
+                    }
+                }
+                Operation::BooleanOp {
                     a,
                     b,
-                    invert,
+                    op,
+                    scale,
                     expected,
                 } => {
-                    match expected {
-                        Geometry::MultiLineString(_) | Geometry::LineString(_) => {}
-                        other => {
-                            info!("skipping unsupported ClipOp output: {:?}", other);
-                            self.unsupported.push(test_case);
-                            continue;
-                        }
-                    };
-
-                    let actual = match (a, b) {
-                        (Geometry::Polygon(polygon), Geometry::LineString(line_string))
-                        | (Geometry::LineString(line_string), Geometry::Polygon(polygon)) => {
-                            // REVIEW: add a line_string flavor
-                            polygon.clip(&MultiLineString(vec![line_string.clone()]), *invert)
-                        }
-                        (
-                            Geometry::Polygon(polygon),
-                            Geometry::MultiLineString(multi_line_string),
-                        )
-                        | (
-                            Geometry::MultiLineString(multi_line_string),
-                            Geometry::Polygon(polygon),
-                        ) => polygon.clip(multi_line_string, *invert),
-                        (
-                            Geometry::LineString(line_string),
-                            Geometry::MultiPolygon(multi_polygon),
-                        )
-                        | (
-                            Geometry::MultiPolygon(multi_polygon),
-                            Geometry::LineString(line_string),
-                        ) => {
-                            multi_polygon.clip(&MultiLineString(vec![line_string.clone()]), *invert)
-                        }
-                        (
-                            Geometry::MultiLineString(multi_line_string),
-                            Geometry::MultiPolygon(multi_polygon),
-                        )
-                        | (
-                            Geometry::MultiPolygon(multi_polygon),
-                            Geometry::MultiLineString(multi_line_string),
-                        ) => multi_polygon.clip(multi_line_string, *invert),
-
-                        // We should be filtering the input test cases in such a way that we don't get here.
-                        _ => todo!("Handle {:?} and {:?}", a, b),
+                    let actual = match scale {
+                        None => a.overlay(b, *op),
+                        Some(scale) => a.overlay_with_precision(b, *op, *scale),
                     };
 
                     if actual.relate(expected).is_equal_topo() {
                         debug!(
-                            "ClipOp success (topo eq) - expected: {:?}",
+                            "BooleanOp success (topo eq) - expected: {:?}",
                             expected.wkt_string()
                         );
                         self.successes.push(test_case);
                     } else {
                         let error_description = format!(
-                            "expected {:?}, actual: {:?}",
+                            "op: {:?}, expected {:?}, actual: {:?}",
+                            op,
                             expected.wkt_string(),
                             actual.wkt_string()
                         );
@@ -464,6 +603,7 @@ impl TestRunner {
             .find(&filename_filter)?
             .chain(VALIDATE_TEST_XML.find(&filename_filter)?)
             .chain(MISC_TEST_XML.find(&filename_filter)?)
+            .chain(FAILURE_TEST_XML.find(&filename_filter)?)
         {
             let file = match entry {
                 DirEntry::Dir(_) => {
@@ -509,12 +649,24 @@ impl TestRunner {
                         .to_string();
 
                     match test.operation_input.into_operation(&case) {
-                        Ok(operation) => {
-                            if matches!(
-                                operation,
-                                Operation::BooleanOp { .. } | Operation::ClipOp { .. }
-                            ) && run.precision_model.is_some()
-                                && &run.precision_model.as_ref().unwrap().ty != "FLOATING"
+                        Ok(mut operation) => {
+                            let fixed_scale = match &run.precision_model {
+                                Some(precision_model) if precision_model.ty != "FLOATING" => {
+                                    Some(precision_model.scale)
+                                }
+                                _ => None,
+                            };
+                            if let (Operation::BooleanOp { scale, .. }, Some(Some(fixed_scale))) =
+                                (&mut operation, fixed_scale)
+                            {
+                                *scale = Some(fixed_scale);
+                                cases.push(TestCase {
+                                    description,
+                                    test_file_name,
+                                    operation,
+                                });
+                            } else if matches!(operation, Operation::BooleanOp { .. })
+                                && fixed_scale.is_some()
                             {
                                 cases.push(TestCase {
                                     description,
@@ -543,6 +695,70 @@ impl TestRunner {
     }
 }
 
+/// Buffers are approximations, so rather than requiring equality, we follow JTS's
+/// `BufferResultMatcher` and require the area of the symmetric difference to be small.
+fn is_buffer_match(actual: &MultiPolygon, expected: &MultiPolygon) -> bool {
+    const MAX_RELATIVE_AREA_DIFFERENCE: f64 = 1.0e-3;
+
+    if actual.is_empty() || expected.is_empty() {
+        return actual.is_empty() && expected.is_empty();
+    }
+    // Unlike JTS, also compare the number of polygons and holes, since a spurious sliver barely
+    // changes the area.
+    let count = |multi_polygon: &MultiPolygon| {
+        let holes = multi_polygon
+            .iter()
+            .map(|p| p.interiors().len())
+            .sum::<usize>();
+        (multi_polygon.0.len(), holes)
+    };
+    if count(actual) != count(expected) {
+        return false;
+    }
+    let area = actual.unsigned_area();
+    if area <= 0.0 {
+        return expected.unsigned_area() <= 0.0;
+    }
+    actual.xor(expected).unsigned_area() / area < MAX_RELATIVE_AREA_DIFFERENCE
+}
+
+/// The polygons output by polygonize overlap where a polygon fills another's hole, so rather
+/// than relating the whole collections, we match each polygon individually.
+fn is_polygonize_match(actual: &MultiPolygon, expected: &MultiPolygon) -> bool {
+    actual.0.len() == expected.0.len()
+        && expected.iter().all(|expected| {
+            actual
+                .iter()
+                .any(|actual| actual.relate(expected).is_equal_topo())
+        })
+}
+
+/// The linework of a geometry, as input to polygonize.
+fn line_strings(geometry: &Geometry) -> Vec<LineString> {
+    match geometry {
+        Geometry::Line(line) => vec![LineString::from(*line)],
+        Geometry::LineString(line_string) => vec![line_string.clone()],
+        Geometry::MultiLineString(multi_line_string) => multi_line_string.0.clone(),
+        Geometry::Polygon(polygon) => polygon_rings(polygon),
+        Geometry::MultiPolygon(multi_polygon) => {
+            multi_polygon.iter().flat_map(polygon_rings).collect()
+        }
+        Geometry::GeometryCollection(collection) => {
+            collection.iter().flat_map(line_strings).collect()
+        }
+        Geometry::Rect(rect) => polygon_rings(&rect.to_polygon()),
+        Geometry::Triangle(triangle) => polygon_rings(&triangle.to_polygon()),
+        Geometry::Point(_) | Geometry::MultiPoint(_) => vec![],
+    }
+}
+
+fn polygon_rings(polygon: &Polygon) -> Vec<LineString> {
+    std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .cloned()
+        .collect()
+}
+
 trait RotatedEq<T: GeoNum> {
     fn is_rotated_eq<F>(&self, other: &Self, coord_matcher: F) -> bool
     where